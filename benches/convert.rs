@@ -0,0 +1,42 @@
+#[macro_use]
+extern crate bencher;
+extern crate angular_units as angle;
+extern crate prisma;
+
+use angle::Deg;
+use bencher::Bencher;
+use prisma::{rgb_slice_to_hsv, FromColor, Hsv, Rgb};
+
+fn make_buffer() -> Vec<Rgb<f32>> {
+    (0..64 * 64 * 64)
+        .map(|i| {
+            let r = (i % 64) as f32 / 63.0;
+            let g = ((i / 64) % 64) as f32 / 63.0;
+            let b = (i / (64 * 64)) as f32 / 63.0;
+            Rgb::new(r, g, b)
+        })
+        .collect()
+}
+
+fn rgb_to_hsv_elementwise(bench: &mut Bencher) {
+    let src = make_buffer();
+    let mut dst = vec![Hsv::new(Deg(0.0), 0.0, 0.0); src.len()];
+    bench.iter(|| {
+        for (from, to) in src.iter().zip(dst.iter_mut()) {
+            *to = Hsv::from_color(from);
+        }
+        bencher::black_box(&dst);
+    });
+}
+
+fn rgb_to_hsv_slice(bench: &mut Bencher) {
+    let src = make_buffer();
+    let mut dst = vec![Hsv::new(Deg(0.0), 0.0, 0.0); src.len()];
+    bench.iter(|| {
+        rgb_slice_to_hsv(&src, &mut dst);
+        bencher::black_box(&dst);
+    });
+}
+
+benchmark_group!(benches, rgb_to_hsv_elementwise, rgb_to_hsv_slice);
+benchmark_main!(benches);
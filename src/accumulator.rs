@@ -0,0 +1,152 @@
+//! Streaming accumulation of colors
+
+use crate::color::Lerp;
+use std::iter::FromIterator;
+
+/// Computes the running mean of a stream of colors without storing them
+///
+/// Each pushed color moves the mean toward it by $`1/n`$ of the distance, where $`n`$ is the
+/// number of colors seen so far:
+///
+/// ```math
+/// \bar{x}_n = \bar{x}_{n-1} + \frac{x_n - \bar{x}_{n-1}}{n}
+/// ```
+///
+/// This is Welford's update for the mean. Unlike summing and dividing at the end, the
+/// intermediate value never grows beyond the range of the colors themselves, so it stays
+/// accurate for long streams of floating point colors. Integer channels are rounded at every
+/// update, and the mean stops moving once $`1/n`$ of the distance is less than one step, so cast
+/// such colors to a floating point format before accumulating them.
+///
+/// The update is performed with [`Lerp`](trait.Lerp.html), so any color that can be interpolated
+/// can be accumulated. For polar colors this means hues are averaged along the shortest arc
+/// between the running mean and each new sample.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ColorAccumulator<C> {
+    mean: Option<C>,
+    count: u64,
+}
+
+impl<C> ColorAccumulator<C>
+where
+    C: Lerp + Clone,
+{
+    /// Construct a new empty `ColorAccumulator`
+    pub fn new() -> Self {
+        ColorAccumulator {
+            mean: None,
+            count: 0,
+        }
+    }
+
+    /// Add a color to the accumulated mean
+    pub fn push(&mut self, color: C) {
+        self.count += 1;
+        self.mean = match self.mean.take() {
+            None => Some(color),
+            Some(mean) => {
+                let one: C::Position = num_traits::cast(1.0).unwrap();
+                let pos = one / num_traits::cast(self.count).unwrap();
+                Some(mean.lerp(&color, pos))
+            }
+        };
+    }
+
+    /// Returns the mean of all colors pushed so far, or `None` if nothing has been pushed
+    pub fn mean(&self) -> Option<C> {
+        self.mean.clone()
+    }
+
+    /// Returns the number of colors pushed so far
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Returns `true` if no colors have been pushed
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// Discard all accumulated colors
+    pub fn clear(&mut self) {
+        self.mean = None;
+        self.count = 0;
+    }
+}
+
+impl<C> Default for ColorAccumulator<C>
+where
+    C: Lerp + Clone,
+{
+    fn default() -> Self {
+        ColorAccumulator::new()
+    }
+}
+
+impl<C> Extend<C> for ColorAccumulator<C>
+where
+    C: Lerp + Clone,
+{
+    fn extend<I: IntoIterator<Item = C>>(&mut self, iter: I) {
+        for color in iter {
+            self.push(color);
+        }
+    }
+}
+
+impl<C> FromIterator<C> for ColorAccumulator<C>
+where
+    C: Lerp + Clone,
+{
+    fn from_iter<I: IntoIterator<Item = C>>(iter: I) -> Self {
+        let mut acc = ColorAccumulator::new();
+        acc.extend(iter);
+        acc
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rgb::Rgb;
+    use crate::xyz::Xyz;
+    use approx::*;
+
+    #[test]
+    fn test_mean() {
+        let mut acc = ColorAccumulator::new();
+        assert!(acc.is_empty());
+        assert_eq!(acc.mean(), None);
+
+        acc.push(Rgb::new(0.0, 0.2, 1.0));
+        assert_relative_eq!(acc.mean().unwrap(), Rgb::new(0.0, 0.2, 1.0));
+        acc.push(Rgb::new(1.0, 0.4, 0.0));
+        assert_relative_eq!(acc.mean().unwrap(), Rgb::new(0.5, 0.3, 0.5), epsilon = 1e-6);
+        acc.push(Rgb::new(0.5, 0.9, 0.2));
+        assert_relative_eq!(acc.mean().unwrap(), Rgb::new(0.5, 0.5, 0.4), epsilon = 1e-6);
+        assert_eq!(acc.count(), 3);
+
+        acc.clear();
+        assert!(acc.is_empty());
+        assert_eq!(acc.mean(), None);
+    }
+
+    #[test]
+    fn test_stability() {
+        // A large common offset makes a running sum lose the low digits of every sample
+        let offset = 1.0e6;
+        let colors = [
+            Xyz::new(offset + 0.1, offset + 0.6, offset + 0.9),
+            Xyz::new(offset + 0.3, offset + 0.2, offset + 0.7),
+            Xyz::new(offset + 0.8, offset + 0.4, offset + 0.5),
+        ];
+        let acc: ColorAccumulator<_> = colors.iter().cycle().take(30_000).cloned().collect();
+        assert_eq!(acc.count(), 30_000);
+        // Summing the samples and dividing is off by about 5e-7 here
+        assert_relative_eq!(
+            acc.mean().unwrap(),
+            Xyz::new(offset + 0.4, offset + 0.4, offset + 0.7),
+            epsilon = 5e-8
+        );
+    }
+}
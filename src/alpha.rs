@@ -2,11 +2,11 @@
 #![allow(non_camel_case_types)]
 
 use crate::channel::{
-    AngularChannelScalar, ColorChannel, NormalChannelScalar, PosNormalBoundedChannel,
-    PosNormalChannelScalar,
+    AngularChannelScalar, ChannelFormatCast, ColorChannel, FreeChannelScalar, NormalChannelScalar,
+    PosNormalBoundedChannel, PosNormalChannelScalar,
 };
 use crate::color::{
-    Bounded, Broadcast, Color, Color3, Color4, Flatten, FromTuple, HomogeneousColor, Invert, Lerp,
+    Bounded, Broadcast, Color, Color3, Color4, Flatten, FlattenMut, FromTuple, HomogeneousColor, Invert, Lerp,
     PolarColor,
 };
 use crate::convert::{FromColor, FromHsi, FromYCbCr};
@@ -24,7 +24,7 @@ use std::ops::{Deref, DerefMut};
 use std::slice;
 
 use crate::lms::Lms;
-use crate::{eHsi, Hsl, Hsv, Hwb, Lab, Lchab, Lchuv, Luv, Rgb, Rgi, XyY, Xyz};
+use crate::{eHsi, Hsl, Hsp, Hsv, Hwb, Lab, Lchab, Lchuv, Luv, Rgb, Rgi, XyY, Xyz, YCoCg};
 
 /// A wrapper around a color with an alpha channel
 ///
@@ -51,6 +51,12 @@ where
             alpha: PosNormalBoundedChannel::new_const(alpha),
         }
     }
+
+    impl_color_checked_new!(
+        fn new_checked(color: InnerColor, alpha: T) {
+            crate::channel::debug_check_bounded::<PosNormalBoundedChannel<T>>(&alpha);
+        }
+    );
     /// Break apart an `Alpha` into the inner color and alpha channel value
     pub fn decompose(self) -> (InnerColor, T) {
         (self.color, self.alpha.0)
@@ -78,10 +84,46 @@ where
     }
     /// Set the alpha channel value
     pub fn set_alpha(&mut self, alpha: T) {
+        crate::channel::debug_check_bounded::<PosNormalBoundedChannel<T>>(&alpha);
         self.alpha.0 = alpha
     }
 }
 
+impl<T, InnerColor> Alpha<T, InnerColor>
+where
+    T: PosNormalChannelScalar,
+    InnerColor: Color + HomogeneousColor<ChannelFormat = T> + Broadcast,
+{
+    /// Construct an `Alpha` with every channel of the inner color set to `value`
+    ///
+    /// Unlike `Broadcast::broadcast`, the alpha channel is set separately.
+    pub fn broadcast_with_alpha(value: T, alpha: T) -> Self {
+        Alpha::new(InnerColor::broadcast(value), alpha)
+    }
+}
+
+impl<T> Alpha<T, Rgb<T>>
+where
+    T: PosNormalChannelScalar,
+{
+    /// Construct a gray `Rgba` with red, green and blue set to `value`
+    pub fn gray(value: T, alpha: T) -> Self {
+        Alpha::broadcast_with_alpha(value, alpha)
+    }
+
+    /// Construct a floating point `Rgba` from 8-bit channel values
+    ///
+    /// Every channel, including alpha, is divided by 255, as with
+    /// [`Rgb::from_u8`](struct.Rgb.html#method.from_u8).
+    pub fn from_u8(r: u8, g: u8, b: u8, alpha: u8) -> Self
+    where
+        T: num_traits::Float,
+        u8: ChannelFormatCast<T>,
+    {
+        Alpha::new(Rgb::from_u8(r, g, b), alpha.cast())
+    }
+}
+
 impl<T, InnerColor> Color for Alpha<T, InnerColor>
 where
     T: PosNormalChannelScalar,
@@ -202,6 +244,52 @@ where
     }
 }
 
+impl<T, InnerColor> FlattenMut for Alpha<T, InnerColor>
+where
+    T: PosNormalChannelScalar,
+    InnerColor: Color + FlattenMut + HomogeneousColor<ChannelFormat = T>,
+{
+    impl_color_as_slice_mut!(T);
+}
+
+impl<T, InnerColor> Alpha<T, InnerColor>
+where
+    T: PosNormalChannelScalar + num_traits::Float,
+    InnerColor: Color + FlattenMut + HomogeneousColor<ChannelFormat = T>,
+{
+    /// Multiply each channel of the color by alpha, converting straight alpha to associated alpha
+    ///
+    /// Premultiplying is only meaningful for cartesian colors, where scaling every channel by the
+    /// same factor is a linear operation. Scaling the hue or saturation of a polar color does not
+    /// give a useful result, so this is only implemented for colors with a single channel type.
+    /// The color must also implement [`FlattenMut`](../trait.FlattenMut.html), a
+    /// `HomogeneousColor` whose channels can be scaled in place through a slice.
+    /// For `Rgba` specifically, [`PremultipliedRgba`](../struct.PremultipliedRgba.html) tracks
+    /// the form in the type instead.
+    pub fn premultiply(self) -> Self {
+        let (mut color, alpha) = self.decompose();
+        for c in color.as_slice_mut() {
+            *c = *c * alpha;
+        }
+        Alpha::new(color, alpha)
+    }
+    /// Divide each channel of the color by alpha, converting associated alpha to straight alpha
+    ///
+    /// This is the inverse of [`premultiply`](#method.premultiply). A fully transparent color has
+    /// lost its color information, and all of its color channels become zero.
+    pub fn unpremultiply(self) -> Self {
+        let (mut color, alpha) = self.decompose();
+        for c in color.as_slice_mut() {
+            *c = if alpha == T::zero() {
+                T::zero()
+            } else {
+                *c / alpha
+            };
+        }
+        Alpha::new(color, alpha)
+    }
+}
+
 impl<T, InnerColor> PolarColor for Alpha<T, InnerColor>
 where
     T: PosNormalChannelScalar,
@@ -333,6 +421,8 @@ pub type Rgba<T> = Alpha<T, Rgb<T>>;
 pub type Rgia<T> = Alpha<T, Rgi<T>>;
 /// An `Hsl` value with an alpha channel
 pub type Hsla<T, A> = Alpha<T, Hsl<T, A>>;
+/// An `Hsp` value with an alpha channel
+pub type Hspa<T, A> = Alpha<T, Hsp<T, A>>;
 /// An `Hsv` value with an alpha channel
 pub type Hsva<T, A> = Alpha<T, Hsv<T, A>>;
 /// An `Hwb` value with an alpha channel
@@ -343,6 +433,8 @@ pub type Hsia<T, A> = Alpha<T, Hsi<T, A>>;
 pub type eHsia<T, A> = Alpha<T, eHsi<T, A>>;
 /// An `YCbCr` value with an alpha channel
 pub type YCbCra<T, M> = Alpha<T, YCbCr<T, M>>;
+/// An `YCoCg` value with an alpha channel
+pub type YCoCga<T> = Alpha<T, YCoCg<T>>;
 /// An `Xyz` value with an alpha channel
 pub type Xyza<T> = Alpha<T, Xyz<T>>;
 /// An `XyY` value with an alpha channel
@@ -358,6 +450,114 @@ pub type Lchauv<T, W, A = Deg<T>> = Alpha<T, Lchuv<T, W, A>>;
 /// An `Lmsa` value with an alpha channel
 pub type Lmsa<T, M> = Alpha<T, Lms<T, M>>;
 
+/// Returns the alpha-weighted mean of the pixels whose alpha is above `alpha_threshold`
+///
+/// Pixels at or below the threshold are skipped entirely, so fully transparent pixels do not pull
+/// the mean toward whatever color they happen to hold. The remaining pixels are weighted by their
+/// alpha. Returns `None` if no pixel passes the threshold or the total weight is zero.
+///
+/// ```
+/// use prisma::{mean_opaque, Rgb, Rgba};
+///
+/// let pixels = [
+///     Rgba::new(Rgb::new(1.0, 0.0, 0.0), 1.0),
+///     Rgba::new(Rgb::new(0.0, 1.0, 0.0), 0.0),
+/// ];
+/// assert_eq!(mean_opaque(&pixels, 0.0), Some(Rgb::new(1.0, 0.0, 0.0)));
+/// ```
+pub fn mean_opaque(pixels: &[Rgba<f32>], alpha_threshold: f32) -> Option<Rgb<f32>> {
+    let mut sum = [0.0f64; 3];
+    let mut total_weight = 0.0f64;
+    for pixel in pixels.iter().filter(|p| p.alpha() > alpha_threshold) {
+        let weight = f64::from(pixel.alpha());
+        sum[0] += f64::from(pixel.red()) * weight;
+        sum[1] += f64::from(pixel.green()) * weight;
+        sum[2] += f64::from(pixel.blue()) * weight;
+        total_weight += weight;
+    }
+    if total_weight > 0.0 {
+        Some(Rgb::new(
+            (sum[0] / total_weight) as f32,
+            (sum[1] / total_weight) as f32,
+            (sum[2] / total_weight) as f32,
+        ))
+    } else {
+        None
+    }
+}
+
+/// Composite `src` over `dst` with the Porter-Duff "over" operator, using only integer math
+///
+/// Both colors have straight (non-premultiplied) alpha, and the result does too. When `dst` is
+/// opaque, each color channel is blended with the usual 8-bit approximation
+/// $`(s\alpha + d(255 - \alpha) + 127) / 255`$, where $`\alpha`$ is the alpha of `src`.
+/// Otherwise the channels are weighted by the alpha each color contributes and divided by the
+/// resulting alpha. Every result is within one of compositing in floating point and rounding.
+///
+/// If both colors are fully transparent, the result is transparent black.
+///
+/// ```
+/// use prisma::{over_u8, Rgb, Rgba};
+///
+/// let src = Rgba::new(Rgb::new(255, 0, 0), 128u8);
+/// let dst = Rgba::new(Rgb::new(0, 0, 255), 255u8);
+/// assert_eq!(over_u8(&src, &dst), Rgba::new(Rgb::new(128, 0, 127), 255));
+/// ```
+pub fn over_u8(src: &Rgba<u8>, dst: &Rgba<u8>) -> Rgba<u8> {
+    let src_alpha = u32::from(src.alpha());
+    let dst_weight = u32::from(dst.alpha()) * (255 - src_alpha);
+    // The output alpha, scaled by 255
+    let total = src_alpha * 255 + dst_weight;
+    if total == 0 {
+        return Rgba::new(Rgb::new(0, 0, 0), 0);
+    }
+    let blend = |s: u8, d: u8| -> u8 {
+        let sum = u32::from(s) * src_alpha * 255 + u32::from(d) * dst_weight;
+        ((sum + total / 2) / total) as u8
+    };
+    Rgba::new(
+        Rgb::new(
+            blend(src.red(), dst.red()),
+            blend(src.green(), dst.green()),
+            blend(src.blue(), dst.blue()),
+        ),
+        ((total + 127) / 255) as u8,
+    )
+}
+
+/// Composite `src` over `dst` in linear XYZ, for colors stored as chromaticity and luminance
+///
+/// Chromaticity coordinates do not blend linearly, so both colors are converted to `Xyz`, blended
+/// with the Porter-Duff "over" operator and converted back to `XyY`. Both colors have straight
+/// (non-premultiplied) alpha, and the result does too:
+///
+/// ```math
+/// \alpha = \alpha_s + \alpha_d(1 - \alpha_s), \quad
+/// XYZ = \frac{XYZ_s\alpha_s + XYZ_d\alpha_d(1 - \alpha_s)}{\alpha}
+/// ```
+///
+/// If both colors are fully transparent, the result is a transparent `XyY` of all zeros.
+pub fn composite_xyya<T>(src: &XyYa<T>, dst: &XyYa<T>) -> XyYa<T>
+where
+    T: FreeChannelScalar + PosNormalChannelScalar + num_traits::Float,
+{
+    let src_alpha = src.alpha();
+    let dst_weight = dst.alpha() * (T::one() - src_alpha);
+    let alpha = src_alpha + dst_weight;
+    if alpha <= T::zero() {
+        return XyYa::new(XyY::new(T::zero(), T::zero(), T::zero()), T::zero());
+    }
+    let s = Xyz::from_color(src.color());
+    let d = Xyz::from_color(dst.color());
+    let blend = |s: T, d: T| (s * src_alpha + d * dst_weight) / alpha;
+    let xyz = Xyz::new(
+        blend(s.x(), d.x()),
+        blend(s.y(), d.y()),
+        blend(s.z(), d.z()),
+    );
+    XyYa::new(XyY::from_color(&xyz), alpha)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -397,6 +597,32 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_broadcast_with_alpha() {
+        let c1 = Rgba::broadcast_with_alpha(0.5, 1.0);
+        assert_eq!(c1, Rgba::new(Rgb::new(0.5, 0.5, 0.5), 1.0));
+        assert_eq!(Rgba::gray(0.5, 1.0), c1);
+        assert_eq!(
+            Rgba::gray(200u8, 25u8),
+            Rgba::new(Rgb::new(200u8, 200, 200), 25)
+        );
+
+        let c2 = Xyza::broadcast_with_alpha(0.3, 0.6);
+        assert_eq!(c2, Xyza::new(Xyz::new(0.3, 0.3, 0.3), 0.6));
+    }
+
+    #[test]
+    fn test_rgba_from_u8() {
+        let c1 = Rgba::from_u8(255, 128, 0, 51);
+        assert_relative_eq!(
+            c1,
+            Rgba::new(Rgb::new(1.0, 0.50196, 0.0), 0.2),
+            epsilon = 1e-5
+        );
+        let c2: Rgba<f32> = Rgba::from_u8(0, 0, 255, 255);
+        assert_eq!(c2, Rgba::new(Rgb::new(0.0, 0.0, 1.0), 1.0));
+    }
+
     #[test]
     fn test_invert() {
         let c1 = Rgba::new(Rgb::new(30u8, 255u8, 200u8), 155u8);
@@ -435,6 +661,11 @@ mod test {
         let c1 = Rgba::new(Rgb::new(100u8, 50, 175), 254);
         assert_eq!(c1.as_slice(), &[100u8, 50, 175, 254]);
         assert_eq!(Rgba::from_slice(c1.as_slice()), c1);
+
+        let mut c2 = c1;
+        c2.as_slice_mut()[1] = 60;
+        c2.as_slice_mut()[3] = 10;
+        assert_eq!(c2, Rgba::new(Rgb::new(100u8, 60, 175), 10));
     }
 
     #[test]
@@ -462,4 +693,135 @@ mod test {
         let deserialized: Rgba<u8> = serde_json::from_str(&serialized).unwrap();
         assert_eq!(deserialized, color);
     }
+
+    #[test]
+    fn test_mean_opaque() {
+        let pixels = [
+            Rgba::new(Rgb::new(1.0, 0.0, 0.0), 1.0),
+            Rgba::new(Rgb::new(0.0, 0.0, 1.0), 0.0),
+            Rgba::new(Rgb::new(0.0, 1.0, 0.0), 0.5),
+            Rgba::new(Rgb::new(1.0, 1.0, 1.0), 0.0),
+        ];
+        let mean = mean_opaque(&pixels, 0.0).unwrap();
+        assert_relative_eq!(mean, Rgb::new(2.0 / 3.0, 1.0 / 3.0, 0.0), epsilon = 1e-6);
+
+        let mean = mean_opaque(&pixels, 0.5).unwrap();
+        assert_relative_eq!(mean, Rgb::new(1.0, 0.0, 0.0), epsilon = 1e-6);
+
+        assert_eq!(mean_opaque(&pixels, 1.0), None);
+        assert_eq!(mean_opaque(&[], 0.0), None);
+    }
+
+    #[test]
+    fn test_over_u8() {
+        let over_f64 = |src: &Rgba<u8>, dst: &Rgba<u8>| -> [f64; 4] {
+            let (sa, da) = (src.alpha() as f64 / 255.0, dst.alpha() as f64 / 255.0);
+            let alpha = sa + da * (1.0 - sa);
+            let blend = |s: u8, d: u8| {
+                if alpha == 0.0 {
+                    0.0
+                } else {
+                    (s as f64 * sa + d as f64 * da * (1.0 - sa)) / alpha
+                }
+            };
+            [
+                blend(src.red(), dst.red()),
+                blend(src.green(), dst.green()),
+                blend(src.blue(), dst.blue()),
+                alpha * 255.0,
+            ]
+        };
+
+        let values = [0u8, 1, 17, 64, 127, 128, 200, 254, 255];
+        for &sa in values.iter() {
+            for &da in values.iter() {
+                for &(s, d) in [(0u8, 255u8), (255, 0), (13, 240), (128, 127), (99, 99)].iter() {
+                    let src = Rgba::new(Rgb::new(s, d, 255 - s), sa);
+                    let dst = Rgba::new(Rgb::new(d, s, 200), da);
+                    let out = over_u8(&src, &dst);
+                    let expected = over_f64(&src, &dst);
+                    let (color, alpha) = out.to_tuple();
+                    let actual = [color.0, color.1, color.2, alpha];
+                    for (a, e) in actual.iter().zip(expected.iter()) {
+                        assert!((*a as f64 - e).abs() <= 1.0, "{:?} {:?}", out, expected);
+                    }
+                }
+            }
+        }
+
+        let src = Rgba::new(Rgb::new(200, 100, 50), 255u8);
+        let dst = Rgba::new(Rgb::new(10, 20, 30), 77u8);
+        assert_eq!(over_u8(&src, &dst), src);
+        let clear = Rgba::new(Rgb::new(200, 100, 50), 0u8);
+        assert_eq!(over_u8(&clear, &dst), dst);
+        assert_eq!(over_u8(&clear, &clear), Rgba::new(Rgb::new(0, 0, 0), 0));
+
+        // With an opaque destination this is the usual 8-bit blend
+        let dst = Rgba::new(Rgb::new(10, 20, 30), 255u8);
+        for sa in 0..=255u32 {
+            let src = Rgba::new(Rgb::new(250, 128, 3), sa as u8);
+            let out = over_u8(&src, &dst);
+            let mix = |s: u32, d: u32| ((s * sa + d * (255 - sa) + 127) / 255) as u8;
+            assert_eq!(out.red(), mix(250, 10));
+            assert_eq!(out.green(), mix(128, 20));
+            assert_eq!(out.blue(), mix(3, 30));
+            assert_eq!(out.alpha(), 255);
+        }
+    }
+
+    #[test]
+    fn test_composite_xyya() {
+        let src = XyYa::new(XyY::new(0.64, 0.33, 0.2126), 1.0);
+        let dst = XyYa::new(XyY::new(0.3127, 0.329, 0.5), 0.7);
+        assert_relative_eq!(composite_xyya(&src, &dst), src, epsilon = 1e-12);
+
+        let clear = XyYa::new(XyY::new(0.64, 0.33, 0.2126), 0.0);
+        assert_relative_eq!(composite_xyya(&clear, &dst), dst, epsilon = 1e-12);
+
+        let both_clear = composite_xyya(&clear, &XyYa::new(*dst.color(), 0.0));
+        assert_eq!(both_clear.alpha(), 0.0);
+        assert_eq!(both_clear.Y(), 0.0);
+
+        // Half coverage over an opaque background averages in XYZ, not in xy
+        let half = XyYa::new(*src.color(), 0.5);
+        let opaque = XyYa::new(*dst.color(), 1.0);
+        let out = composite_xyya(&half, &opaque);
+        let expected = Xyz::from_color(src.color()).lerp(&Xyz::from_color(dst.color()), 0.5);
+        assert_relative_eq!(out.alpha(), 1.0);
+        assert_relative_eq!(Xyz::from_color(out.color()), expected, epsilon = 1e-12);
+        assert_relative_eq!(out.Y(), (0.2126 + 0.5) / 2.0, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_premultiply() {
+        let c1 = Xyza::new(Xyz::new(0.4, 0.3, 0.8), 0.5);
+        let p1 = c1.premultiply();
+        assert_relative_eq!(
+            p1,
+            Xyza::new(Xyz::new(0.2, 0.15, 0.4), 0.5),
+            epsilon = 1e-12
+        );
+        assert_relative_eq!(p1.unpremultiply(), c1, epsilon = 1e-12);
+
+        for &alpha in [1.0, 0.75, 0.3, 0.01].iter() {
+            let c = Xyza::new(Xyz::new(0.95, 1.0, 1.09), alpha);
+            assert_relative_eq!(c.premultiply().unpremultiply(), c, epsilon = 1e-12);
+        }
+        let opaque = Xyza::new(Xyz::new(0.1, 0.5, 0.9), 1.0);
+        assert_eq!(opaque.premultiply(), opaque);
+
+        let clear = Xyza::new(Xyz::new(0.1, 0.5, 0.9), 0.0);
+        assert_eq!(clear.premultiply(), Xyza::new(Xyz::new(0.0, 0.0, 0.0), 0.0));
+        assert_eq!(
+            clear.unpremultiply(),
+            Xyza::new(Xyz::new(0.0, 0.0, 0.0), 0.0)
+        );
+
+        let c2 = Rgba::new(Rgb::new(1.0f32, 0.5, 0.0), 0.5);
+        let p2 = c2.premultiply();
+        assert_eq!(
+            crate::PremultipliedRgba::new(*p2.color(), p2.alpha()),
+            crate::PremultipliedRgba::from(c2)
+        );
+    }
 }
@@ -0,0 +1,590 @@
+//! Utilities for analyzing collections of colors, such as image buffers
+
+use crate::channel::{ChannelFormatCast, FreeChannelScalar, PosNormalChannelScalar};
+use crate::color_space::{ColorSpace, ConvertToXyz};
+use crate::convert::FromColor;
+use crate::encoding::EncodedColor;
+use crate::lab::{rgb_to_lab_in_space, Lab};
+use crate::lchab::Lchab;
+use crate::rgb::Rgb;
+use crate::white_point::D65;
+use crate::xyz::Xyz;
+use angle::Angle;
+use std::error;
+use std::fmt;
+
+/// An error returned when two buffers that must be compared pixel by pixel differ in length
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct LengthMismatchError {
+    /// The length of the first buffer
+    pub left: usize,
+    /// The length of the second buffer
+    pub right: usize,
+}
+
+impl fmt::Display for LengthMismatchError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "buffer lengths differ: {} and {} pixels",
+            self.left, self.right
+        )
+    }
+}
+
+impl error::Error for LengthMismatchError {}
+
+/// Build a histogram of CIE lightness ($`L^*`$) from a buffer of 8-bit pixels
+///
+/// Each pixel is interpreted as being in `space` with the space's own encoding and converted to
+/// $`L^*`$ relative to the space's white point. The range $`[0, 100]`$ is split into `bins` equal
+/// buckets, and the returned vector holds the number of pixels falling in each. A pixel with
+/// $`L^* = 100`$ is counted in the last bucket.
+///
+/// Because $`L^*`$ is perceptually uniform, the histogram reflects how bright an image looks
+/// rather than how much light it emits, making it a good basis for auto-exposure.
+///
+/// If `bins` is zero, an empty vector is returned.
+pub fn lstar_histogram<T, S>(pixels: &[Rgb<u8>], bins: usize, space: &S) -> Vec<u32>
+where
+    T: FreeChannelScalar + PosNormalChannelScalar + ChannelFormatCast<f64>,
+    f64: ChannelFormatCast<T>,
+    u8: ChannelFormatCast<T>,
+    S: ConvertToXyz<T, Rgb<T>, <S as ColorSpace<T>>::Encoding, OutputColor = Xyz<T>>,
+{
+    let mut histogram = vec![0; bins];
+    if bins == 0 {
+        return histogram;
+    }
+
+    let white_y = space.white_point().y();
+    let encoding = space.encoding();
+    let bin_count: T = num_traits::cast(bins).unwrap();
+    let hundred: T = num_traits::cast(100.0).unwrap();
+
+    for pixel in pixels {
+        let color = EncodedColor::new(pixel.color_cast::<T>(), encoding.clone());
+        let relative_y = space.convert_to_xyz(&color).y() / white_y;
+        let lightness = Lab::<T, D65>::lightness_from_relative_y(relative_y);
+
+        let bin = num_traits::cast::<_, usize>((lightness / hundred * bin_count).floor())
+            .unwrap_or(0)
+            .min(bins - 1);
+        histogram[bin] += 1;
+    }
+
+    histogram
+}
+
+/// Build a per-pixel map of the CIEDE2000 color difference between two buffers of 8-bit pixels
+///
+/// Corresponding pixels of `a` and `b` are interpreted as being in `space` with the space's own
+/// encoding, converted to CIELAB relative to the space's white point, and compared with
+/// [`Lab::ciede2000`](../struct.Lab.html#method.ciede2000). The result holds one $`\Delta E_{00}`$
+/// per pixel, in the order of the buffers, and can be used directly as a difference heatmap.
+///
+/// An error is returned if `a` and `b` are not the same length.
+pub fn delta_e_map<T, S>(
+    a: &[Rgb<u8>],
+    b: &[Rgb<u8>],
+    space: &S,
+) -> Result<Vec<f64>, LengthMismatchError>
+where
+    T: FreeChannelScalar + PosNormalChannelScalar + ChannelFormatCast<f64>,
+    f64: ChannelFormatCast<T>,
+    u8: ChannelFormatCast<T>,
+    S: ConvertToXyz<T, Rgb<T>, <S as ColorSpace<T>>::Encoding, OutputColor = Xyz<T>>,
+{
+    if a.len() != b.len() {
+        return Err(LengthMismatchError {
+            left: a.len(),
+            right: b.len(),
+        });
+    }
+
+    let to_lab = |pixel: &Rgb<u8>| rgb_to_lab_in_space(&pixel.color_cast::<T>(), space);
+
+    Ok(a.iter()
+        .zip(b.iter())
+        .map(|(pa, pb)| num_traits::cast(to_lab(pa).ciede2000(&to_lab(pb))).unwrap())
+        .collect())
+}
+
+/// Find the perceptual center of a group of 8-bit colors, as the mean of their CIELAB values
+///
+/// Each color is interpreted as being in `space` with the space's own encoding and converted to
+/// CIELAB relative to the space's white point. The mean Lab value is converted back into `space`,
+/// reducing its chroma if needed to bring it into gamut as in
+/// [`Lchab::clip_with_delta`](../struct.Lchab.html#method.clip_with_delta), and rounded to 8 bits.
+///
+/// Averaging the encoded channels instead weighs the colors by their code values rather than
+/// how they look. For saturated colors that differ in lightness, such as red and yellow, the
+/// channel mean comes out noticeably darker than the color that looks halfway between them.
+///
+/// Panics:
+/// =======
+/// Panics if `colors` is empty.
+pub fn perceptual_centroid<T, S>(colors: &[Rgb<u8>], space: &S) -> Rgb<u8>
+where
+    T: FreeChannelScalar
+        + PosNormalChannelScalar
+        + ChannelFormatCast<f64>
+        + ChannelFormatCast<u8>
+        + num_traits::Float,
+    f64: ChannelFormatCast<T>,
+    u8: ChannelFormatCast<T>,
+    S: ConvertToXyz<T, Rgb<T>, <S as ColorSpace<T>>::Encoding, OutputColor = Xyz<T>>,
+{
+    if colors.is_empty() {
+        panic!("Cannot find the centroid of an empty set of colors");
+    }
+
+    let zero = T::zero();
+    let (l, a, b) = colors.iter().fold((zero, zero, zero), |(l, a, b), pixel| {
+        let lab = rgb_to_lab_in_space(&pixel.color_cast::<T>(), space);
+        (l + lab.L(), a + lab.a(), b + lab.b())
+    });
+
+    let count: T = num_traits::cast(colors.len()).unwrap();
+    let mean = Lab::<T, D65>::new(l / count, a / count, b / count);
+    let lch: Lchab<T, D65> = FromColor::from_color(&mean);
+    lch.clip_with_delta(space).0.color_cast()
+}
+
+/// Estimate per-channel gains that white balance `pixels` using the gray world assumption
+///
+/// The gray world assumption is that the colors of a typical scene average out to a neutral gray,
+/// so any tint of the average comes from the illuminant. The pixels are taken to be linearly
+/// encoded in `space`, and the returned gains scale the red, green and blue channels so that their
+/// mean becomes the neutral of the same luminance, with the white point's chromaticity. Apply
+/// them with [`apply_white_balance_buffer`](fn.apply_white_balance_buffer.html).
+///
+/// The gray world estimate is easily thrown off by images dominated by one color, such as a
+/// landscape of grass. A channel with a mean of zero is left with a gain of one, as are all
+/// channels of an empty buffer.
+pub fn auto_white_balance<T, S>(pixels: &[Rgb<f32>], space: &S) -> Rgb<f32>
+where
+    T: FreeChannelScalar,
+    S: ColorSpace<T>,
+{
+    if pixels.is_empty() {
+        return Rgb::new(1.0, 1.0, 1.0);
+    }
+
+    let mut sum = [0.0f64; 3];
+    for pixel in pixels {
+        sum[0] += f64::from(pixel.red());
+        sum[1] += f64::from(pixel.green());
+        sum[2] += f64::from(pixel.blue());
+    }
+    let count = pixels.len() as f64;
+    let mean = [sum[0] / count, sum[1] / count, sum[2] / count];
+
+    // The white point is linear (1, 1, 1), so the Y row scaled by the white's Y gives the weights
+    // of a relative luminance
+    let transform = space.get_xyz_transform();
+    let white_y: f64 = num_traits::cast(space.white_point().y()).unwrap();
+    let luminance = transform.as_slice()[3..6]
+        .iter()
+        .zip(mean.iter())
+        .map(|(&w, m)| num_traits::cast::<_, f64>(w).unwrap() / white_y * m)
+        .sum::<f64>();
+
+    let gain = |m: f64| if m > 0.0 { (luminance / m) as f32 } else { 1.0 };
+    Rgb::new(gain(mean[0]), gain(mean[1]), gain(mean[2]))
+}
+
+/// Scale each channel of every pixel in `pixels` by the matching channel of `gains`
+///
+/// This applies a white balance estimate, such as the one from
+/// [`auto_white_balance`](fn.auto_white_balance.html), in place. As with that estimate, the
+/// pixels should be linearly encoded; scaling gamma encoded values distorts the correction. The
+/// results are not clamped, so bright pixels can end up above one.
+pub fn apply_white_balance_buffer(pixels: &mut [Rgb<f32>], gains: &Rgb<f32>) {
+    for pixel in pixels.iter_mut() {
+        *pixel = Rgb::new(
+            pixel.red() * gains.red(),
+            pixel.green() * gains.green(),
+            pixel.blue() * gains.blue(),
+        );
+    }
+}
+
+/// Remove slowly varying lighting from an image with single-scale retinex
+///
+/// `pixels` is a `width` by `height` image of linear colors stored row by row. Each channel is
+/// taken to the log domain, where a pixel's local average, a Gaussian blur with standard deviation
+/// `sigma` pixels, is subtracted from it. This divides each pixel by the geometric mean of its
+/// neighborhood, so shading and tinted lighting that vary over distances much larger than `sigma`
+/// cancel out, while detail at smaller scales is kept. The mean log value of each channel over the
+/// whole image is then added back, so the output keeps the overall brightness and color of the
+/// input instead of ending up centered on one.
+///
+/// Pixels outside the image are taken to repeat the nearest edge pixel. A small offset is added
+/// before taking the log so that black pixels stay finite, and negative channels are treated as
+/// zero. The results are not clamped. The blur kernel is cut off at three standard deviations,
+/// or at the size of the image if that is smaller. If `sigma` is not positive, or is not finite,
+/// `pixels` is left unchanged; an infinitely wide blur would average the whole image, which the
+/// global mean cancels out anyway.
+///
+/// # Panics
+/// Panics if `pixels.len()` is not `width * height`.
+pub fn retinex_normalize(pixels: &mut [Rgb<f32>], width: usize, height: usize, sigma: f32) {
+    assert_eq!(
+        pixels.len(),
+        width * height,
+        "buffer of {} pixels is not a {}x{} image",
+        pixels.len(),
+        width,
+        height
+    );
+    if pixels.is_empty() || !sigma.is_finite() || sigma <= 0.0 {
+        return;
+    }
+
+    const OFFSET: f32 = 1e-3;
+    let to_log = |c: f32| (c.max(0.0) + OFFSET).ln();
+    let logs: Vec<[f32; 3]> = pixels
+        .iter()
+        .map(|p| [to_log(p.red()), to_log(p.green()), to_log(p.blue())])
+        .collect();
+
+    let mut mean = [0.0f64; 3];
+    for log in logs.iter() {
+        for c in 0..3 {
+            mean[c] += f64::from(log[c]);
+        }
+    }
+    let count = logs.len() as f64;
+    let mean = [
+        (mean[0] / count) as f32,
+        (mean[1] / count) as f32,
+        (mean[2] / count) as f32,
+    ];
+
+    let radius = ((3.0 * sigma).ceil() as usize).min(width.max(height));
+    let mut kernel: Vec<f32> = (0..=radius)
+        .map(|i| (-((i * i) as f32) / (2.0 * sigma * sigma)).exp())
+        .collect();
+    let total = kernel[0] + 2.0 * kernel[1..].iter().sum::<f32>();
+    for weight in kernel.iter_mut() {
+        *weight /= total;
+    }
+
+    // The Gaussian is separable, so blur the rows and then the columns
+    let rows = blur_pass(&logs, &kernel, width, height, 1, width);
+    let local = blur_pass(&rows, &kernel, height, width, width, 1);
+
+    for ((pixel, log), local) in pixels.iter_mut().zip(logs.iter()).zip(local.iter()) {
+        let channel = |c: usize| (log[c] - local[c] + mean[c]).exp() - OFFSET;
+        *pixel = Rgb::new(channel(0), channel(1), channel(2));
+    }
+}
+
+/// Blur `len` pixels long lines, `count` of them, with a symmetric one sided `kernel`
+///
+/// Consecutive pixels of a line are `step` apart in `values` and consecutive lines `stride` apart.
+fn blur_pass(
+    values: &[[f32; 3]],
+    kernel: &[f32],
+    len: usize,
+    count: usize,
+    step: usize,
+    stride: usize,
+) -> Vec<[f32; 3]> {
+    let mut out = vec![[0.0f32; 3]; values.len()];
+    let radius = kernel.len() as isize - 1;
+    for line in 0..count {
+        let at = |i: isize| line * stride + (i.clamp(0, len as isize - 1) as usize) * step;
+        for i in 0..len as isize {
+            let mut sum = [0.0f32; 3];
+            for k in -radius..=radius {
+                let weight = kernel[k.unsigned_abs()];
+                let value = &values[at(i + k)];
+                for c in 0..3 {
+                    sum[c] += weight * value[c];
+                }
+            }
+            out[at(i)] = sum;
+        }
+    }
+    out
+}
+
+/// Compute the mean of a set of hues, taking the wrap-around of angles into account
+///
+/// Each hue is treated as a unit vector on the color wheel, and the result is the angle of the sum
+/// of those vectors, normalized into the range of `A`. Unlike the arithmetic mean, this gives
+/// a mean of 0° for 350° and 10° rather than 180°.
+///
+/// If `hues` is empty, or the hues are spread evenly enough that the vectors cancel out, there is no
+/// meaningful mean and the result is an angle of zero. The vectors are taken to cancel when their
+/// mean length is below the square root of the scalar's epsilon, which absorbs rounding in the sums.
+pub fn circular_mean_hue<A>(hues: &[A]) -> A
+where
+    A: Angle,
+    A::Scalar: num_traits::Float,
+{
+    let zero: A::Scalar = num_traits::cast(0.0).unwrap();
+    let (sin_sum, cos_sum) = hues.iter().fold((zero, zero), |(sin_sum, cos_sum), hue| {
+        (sin_sum + hue.clone().sin(), cos_sum + hue.clone().cos())
+    });
+    let count: A::Scalar = num_traits::cast(hues.len()).unwrap();
+    let length = num_traits::Float::hypot(sin_sum, cos_sum);
+    let tolerance = num_traits::Float::sqrt(<A::Scalar as num_traits::Float>::epsilon());
+    if hues.is_empty() || length / count < tolerance {
+        return A::new(zero);
+    }
+    let mean = A::atan2(sin_sum, cos_sum).normalize();
+    // A tiny negative angle can normalize to exactly one full period after rounding
+    if mean.scalar() >= A::period() {
+        A::new(zero)
+    } else {
+        mean
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::color_space::named::SRgb;
+    use crate::encoding::EncodableColor;
+    use approx::*;
+
+    #[test]
+    fn test_lstar_histogram() {
+        let space = SRgb::<f64>::new();
+        // Black, L*=0; middle gray (#777777), L*~=50; white, L*=100
+        let mut pixels = vec![Rgb::new(0u8, 0, 0); 5];
+        pixels.extend(vec![Rgb::new(0x77, 0x77, 0x77); 3]);
+        pixels.extend(vec![Rgb::new(255, 255, 255); 2]);
+
+        let hist = lstar_histogram(&pixels, 4, &space);
+        assert_eq!(hist, vec![5, 0, 3, 2]);
+
+        let hist = lstar_histogram(&pixels, 1, &space);
+        assert_eq!(hist, vec![10]);
+
+        let hist = lstar_histogram(&pixels, 10, &space);
+        assert_eq!(hist, vec![5, 0, 0, 0, 0, 3, 0, 0, 0, 2]);
+
+        assert!(lstar_histogram(&pixels, 0, &space).is_empty());
+        assert_eq!(lstar_histogram(&[], 3, &space), vec![0, 0, 0]);
+    }
+
+    #[test]
+    fn test_circular_mean_hue() {
+        use angle::{Deg, Turns};
+        use approx::*;
+
+        assert_relative_eq!(
+            circular_mean_hue(&[Deg(350.0), Deg(10.0)]),
+            Deg(0.0),
+            epsilon = 1e-10
+        );
+        assert_relative_eq!(
+            circular_mean_hue(&[Deg(30.0), Deg(90.0)]),
+            Deg(60.0),
+            epsilon = 1e-10
+        );
+        assert_relative_eq!(
+            circular_mean_hue(&[Deg(340.0f32), Deg(350.0), Deg(0.0)]),
+            Deg(350.0),
+            epsilon = 1e-3
+        );
+        assert_relative_eq!(
+            circular_mean_hue(&[Turns(0.9), Turns(0.2)]),
+            Turns(0.05),
+            epsilon = 1e-10
+        );
+        assert_relative_eq!(
+            circular_mean_hue(&[Deg(123.0)]),
+            Deg(123.0),
+            epsilon = 1e-10
+        );
+        assert_eq!(circular_mean_hue::<Deg<f64>>(&[]), Deg(0.0));
+        assert_eq!(
+            circular_mean_hue(&[Deg(0.0), Deg(120.0), Deg(240.0)]),
+            Deg(0.0)
+        );
+        assert_eq!(
+            circular_mean_hue(&[Deg(45.0f32), Deg(165.0), Deg(285.0)]),
+            Deg(0.0)
+        );
+        assert_eq!(circular_mean_hue(&[Turns(0.1), Turns(0.6)]), Turns(0.0));
+    }
+
+    #[test]
+    fn test_lstar_histogram_ramp() {
+        let space = SRgb::<f32>::new();
+        let pixels: Vec<_> = (0..=255u8).map(|v| Rgb::new(v, v, v)).collect();
+        let hist = lstar_histogram(&pixels, 2, &space);
+        // #777777 is the first gray at or above L* = 50
+        assert_eq!(hist, vec![0x77, 256 - 0x77]);
+    }
+
+    #[test]
+    fn test_delta_e_map() {
+        let space = SRgb::<f64>::new();
+        let a = vec![
+            Rgb::new(0u8, 0, 0),
+            Rgb::new(200, 40, 40),
+            Rgb::new(30, 120, 220),
+            Rgb::new(255, 255, 255),
+        ];
+
+        let same = delta_e_map(&a, &a, &space).unwrap();
+        assert_eq!(same, vec![0.0; 4]);
+
+        let b: Vec<_> = a
+            .iter()
+            .map(|p| Rgb::new(p.red().saturating_add(10), p.green(), p.blue()))
+            .collect();
+        let shifted = delta_e_map(&a, &b, &space).unwrap();
+        assert_eq!(shifted.len(), 4);
+        assert!(shifted[..3].iter().all(|&d| d > 0.5));
+        assert_eq!(shifted[3], 0.0);
+        assert_eq!(delta_e_map(&b, &a, &space).unwrap(), shifted);
+
+        assert!(delta_e_map(&[], &[], &space).unwrap().is_empty());
+        assert_eq!(
+            delta_e_map(&a, &b[..2], &space),
+            Err(LengthMismatchError { left: 4, right: 2 })
+        );
+    }
+
+    #[test]
+    fn test_perceptual_centroid() {
+        let space = SRgb::<f64>::new();
+        let to_lab = |c: &Rgb<u8>| {
+            let xyz = space.convert_to_xyz(&c.color_cast::<f64>().srgb_encoded());
+            Lab::from_xyz(&xyz, D65)
+        };
+        let red = Rgb::new(255u8, 0, 0);
+        let yellow = Rgb::new(255u8, 255, 0);
+        let centroid = perceptual_centroid(&[red, yellow], &space);
+        let rgb_mean = Rgb::new(255u8, 128, 0);
+
+        // Halfway in lightness, L* of about 53 and 97
+        let mid_lightness = (to_lab(&red).L() + to_lab(&yellow).L()) / 2.0;
+        assert_relative_eq!(to_lab(&centroid).L(), mid_lightness, epsilon = 0.5);
+        assert!(to_lab(&rgb_mean).L() < mid_lightness - 7.0);
+        assert_eq!(centroid.red(), 255);
+        assert!(centroid.green() > rgb_mean.green() + 30);
+        // Both lie between red and yellow in hue
+        let hue = |c: &Rgb<u8>| to_lab(c).b().atan2(to_lab(c).a());
+        assert!(hue(&centroid) > hue(&red) && hue(&centroid) < hue(&yellow));
+
+        let teal = Rgb::new(20u8, 150, 140);
+        assert_eq!(perceptual_centroid(&[teal], &space), teal);
+        assert_eq!(perceptual_centroid(&[teal, teal, teal], &space), teal);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_perceptual_centroid_empty() {
+        perceptual_centroid(&[], &SRgb::<f64>::new());
+    }
+
+    #[test]
+    fn test_auto_white_balance() {
+        let space = SRgb::<f64>::new();
+        let mut state = 0x2545f491u32;
+        let mut next = || {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            (state % 1000) as f32 / 1000.0
+        };
+        let scene: Vec<_> = (0..500).map(|_| Rgb::new(next(), next(), next())).collect();
+        let mut tinted: Vec<_> = scene
+            .iter()
+            .map(|p| Rgb::new(p.red() * 1.3, p.green() * 0.95, p.blue() * 0.6))
+            .collect();
+
+        let gains = auto_white_balance(&tinted, &space);
+        assert!(gains.red() < gains.green() && gains.green() < gains.blue());
+        apply_white_balance_buffer(&mut tinted, &gains);
+
+        let n = tinted.len() as f32;
+        let mean = |f: fn(&Rgb<f32>) -> f32| tinted.iter().map(f).sum::<f32>() / n;
+        let (r, g, b) = (mean(Rgb::red), mean(Rgb::green), mean(Rgb::blue));
+        assert!((r - g).abs() < 1e-4);
+        assert!((r - b).abs() < 1e-4);
+        // Correcting a neutral average needs no gain
+        let neutral = auto_white_balance(&tinted, &space);
+        assert!((neutral.red() - 1.0).abs() < 1e-4);
+        assert!((neutral.green() - 1.0).abs() < 1e-4);
+        assert!((neutral.blue() - 1.0).abs() < 1e-4);
+
+        assert_eq!(auto_white_balance(&[], &space), Rgb::new(1.0, 1.0, 1.0));
+        let red = [Rgb::new(0.5, 0.0, 0.0f32)];
+        assert_eq!(auto_white_balance(&red, &space).green(), 1.0);
+    }
+
+    #[test]
+    fn test_retinex_normalize() {
+        let (width, height) = (64, 12);
+        let base = Rgb::new(0.6f32, 0.4, 0.2);
+        // Light falling off from the right to the left, plus a little texture
+        let mut image: Vec<_> = (0..width * height)
+            .map(|i| {
+                let (x, y) = (i % width, i / width);
+                let shade = 0.25 + 0.75 * x as f32 / (width - 1) as f32;
+                let texture = if (x + y) % 2 == 0 { 1.05 } else { 0.95 };
+                Rgb::new(
+                    base.red() * shade * texture,
+                    base.green() * shade * texture,
+                    base.blue() * shade * texture,
+                )
+            })
+            .collect();
+        let column_mean = |image: &[Rgb<f32>], x: usize| {
+            (0..height)
+                .map(|y| image[y * width + x].green())
+                .sum::<f32>()
+                / height as f32
+        };
+        let before = column_mean(&image, width - 1) / column_mean(&image, 0);
+        assert!(before > 3.5);
+
+        retinex_normalize(&mut image, width, height, 6.0);
+        let after = column_mean(&image, width - 1) / column_mean(&image, 0);
+        assert!(after < 1.5, "{}", after);
+        // Away from the edges the falloff is removed almost entirely
+        let inner = column_mean(&image, width - 20) / column_mean(&image, 20);
+        assert!((inner - 1.0).abs() < 0.02, "{}", inner);
+        // The shading is the same in every channel, so the hue is kept
+        for pixel in image.iter() {
+            assert!((pixel.red() / pixel.green() - 1.5).abs() < 0.01);
+            assert!((pixel.green() / pixel.blue() - 2.0).abs() < 0.02);
+        }
+
+        let mut flat = vec![base; 9];
+        retinex_normalize(&mut flat, 3, 3, 2.0);
+        for pixel in flat.iter() {
+            assert!((pixel.red() - 0.6).abs() < 1e-4);
+            assert!((pixel.blue() - 0.2).abs() < 1e-4);
+        }
+        retinex_normalize(&mut [], 0, 5, 2.0);
+
+        let ramp: Vec<_> = (0..12)
+            .map(|i| Rgb::new(i as f32 / 11.0, 0.5, 0.25))
+            .collect();
+        for &sigma in [0.0, -1.0, f32::NAN, f32::INFINITY].iter() {
+            let mut unchanged = ramp.clone();
+            retinex_normalize(&mut unchanged, 4, 3, sigma);
+            assert_eq!(unchanged, ramp);
+        }
+        // A huge sigma must not size the kernel by itself
+        let mut wide = ramp.clone();
+        retinex_normalize(&mut wide, 4, 3, 1e30);
+        assert!(wide.iter().all(|pixel| pixel.red().is_finite()));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_retinex_normalize_size_mismatch() {
+        retinex_normalize(&mut [Rgb::new(0.5, 0.5, 0.5)], 2, 2, 1.0);
+    }
+}
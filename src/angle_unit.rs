@@ -0,0 +1,50 @@
+//! Defines `AngleUnit` for choosing the unit of a scalar hue at runtime
+
+use angle::{Deg, FromAngle, Rad, Turns};
+
+/// A unit that a scalar angle can be given in
+///
+/// The hue channels of the polar color models are typed by their unit, such as `Deg<T>`. When
+/// the unit is only known at runtime, for example when it is read from a file, `AngleUnit` can
+/// carry it alongside a plain number and convert the pair into any angle type.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AngleUnit {
+    /// Degrees, with 360 in a turn
+    Degrees,
+    /// Radians, with $`2\pi`$ in a turn
+    Radians,
+    /// Turns, with one in a turn
+    Turns,
+}
+
+impl AngleUnit {
+    /// Convert `value`, given in this unit, into the angle type `A`
+    pub fn to_angle<T, A>(self, value: T) -> A
+    where
+        T: num_traits::Float,
+        A: FromAngle<Deg<T>> + FromAngle<Rad<T>> + FromAngle<Turns<T>>,
+    {
+        match self {
+            AngleUnit::Degrees => A::from_angle(Deg(value)),
+            AngleUnit::Radians => A::from_angle(Rad(value)),
+            AngleUnit::Turns => A::from_angle(Turns(value)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use approx::*;
+
+    #[test]
+    fn test_to_angle() {
+        let deg: Deg<f64> = AngleUnit::Radians.to_angle(std::f64::consts::PI);
+        assert_relative_eq!(deg, Deg(180.0), epsilon = 1e-12);
+        let deg: Deg<f64> = AngleUnit::Degrees.to_angle(45.0);
+        assert_eq!(deg, Deg(45.0));
+        let rad: Rad<f64> = AngleUnit::Turns.to_angle(0.5);
+        assert_relative_eq!(rad, Rad(std::f64::consts::PI), epsilon = 1e-12);
+    }
+}
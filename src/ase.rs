@@ -0,0 +1,292 @@
+//! Reading and writing Adobe Swatch Exchange (`.ase`) palettes
+//!
+//! This module is only available with the `ase` feature enabled.
+//!
+//! An ASE file is a big-endian binary file made of a header followed by a list of blocks. Each
+//! color block holds a UTF-16 name, a color model and the channel values as 32-bit floats. Swatch
+//! groups are flattened when reading, and the group names are discarded.
+
+use crate::color::{Bounded, Color};
+use crate::color_space::named::SRgb;
+use crate::color_space::ConvertFromXyz;
+use crate::encoding::{EncodableColor, SrgbEncoding};
+use crate::lab::Lab;
+use crate::lms::bradford_adaptation;
+use crate::rgb::Rgb;
+use crate::white_point::{WhitePoint, D50, D65};
+use crate::xyz::Xyz;
+use std::io;
+use std::io::{Read, Write};
+
+const SIGNATURE: &[u8; 4] = b"ASEF";
+const VERSION: (u16, u16) = (1, 0);
+
+const BLOCK_GROUP_START: u16 = 0xC001;
+const BLOCK_GROUP_END: u16 = 0xC002;
+const BLOCK_COLOR: u16 = 0x0001;
+
+const COLOR_TYPE_NORMAL: u16 = 2;
+
+/// Read the colors from an ASE palette
+///
+/// Returns each swatch's name along with its color as sRgb encoded `Rgb<f32>`. Colors are
+/// converted from the model they are stored in:
+///
+/// * `RGB` values are taken to be sRgb encoded and used directly.
+/// * `CMYK` values are converted naively with $`R = (1 - C)(1 - K)`$ and likewise for green and
+///   blue, as ASE files do not carry a color profile.
+/// * `LAB` values are interpreted with a D50 white point, with $`L^*`$ stored in $`[0, 1]`$. They
+///   are adapted to D65 with the Bradford transform and clamped into the sRgb gamut.
+/// * `Gray` values are broadcast to all three channels.
+///
+/// Errors of kind `InvalidData` are returned for malformed files or unknown color models.
+pub fn read_ase<R: Read>(mut reader: R) -> io::Result<Vec<(String, Rgb<f32>)>> {
+    let mut signature = [0u8; 4];
+    reader.read_exact(&mut signature)?;
+    if &signature != SIGNATURE {
+        return Err(invalid_data("missing ASEF signature"));
+    }
+    let _major = read_u16(&mut reader)?;
+    let _minor = read_u16(&mut reader)?;
+    let num_blocks = read_u32(&mut reader)?;
+
+    let mut colors = Vec::new();
+    for _ in 0..num_blocks {
+        let block_type = read_u16(&mut reader)?;
+        let block_len = read_u32(&mut reader)?;
+        // The length comes from the file, so read what is there rather than allocating it upfront
+        let mut block = Vec::new();
+        reader
+            .by_ref()
+            .take(u64::from(block_len))
+            .read_to_end(&mut block)?;
+        if block.len() as u64 != u64::from(block_len) {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "truncated block",
+            ));
+        }
+
+        match block_type {
+            BLOCK_COLOR => colors.push(parse_color_block(&block)?),
+            BLOCK_GROUP_START | BLOCK_GROUP_END => {}
+            _ => return Err(invalid_data("unknown block type")),
+        }
+    }
+
+    Ok(colors)
+}
+
+/// Write a palette as an ASE file
+///
+/// Each color is written as a normal (non-spot) `RGB` swatch with the given name. The colors are expected to
+/// be sRgb encoded, and are written without any conversion.
+pub fn write_ase<W: Write>(mut writer: W, colors: &[(String, Rgb<f32>)]) -> io::Result<()> {
+    if colors.len() > u32::MAX as usize {
+        return Err(invalid_input("too many colors"));
+    }
+    writer.write_all(SIGNATURE)?;
+    writer.write_all(&VERSION.0.to_be_bytes())?;
+    writer.write_all(&VERSION.1.to_be_bytes())?;
+    writer.write_all(&(colors.len() as u32).to_be_bytes())?;
+
+    for (name, color) in colors {
+        let mut block = Vec::new();
+        write_name(&mut block, name)?;
+        block.extend_from_slice(b"RGB ");
+        for channel in [color.red(), color.green(), color.blue()].iter() {
+            block.extend_from_slice(&channel.to_bits().to_be_bytes());
+        }
+        block.extend_from_slice(&COLOR_TYPE_NORMAL.to_be_bytes());
+
+        writer.write_all(&BLOCK_COLOR.to_be_bytes())?;
+        writer.write_all(&(block.len() as u32).to_be_bytes())?;
+        writer.write_all(&block)?;
+    }
+
+    Ok(())
+}
+
+fn parse_color_block(mut block: &[u8]) -> io::Result<(String, Rgb<f32>)> {
+    let name = read_name(&mut block)?;
+    let mut model = [0u8; 4];
+    block.read_exact(&mut model)?;
+
+    let color = match &model {
+        b"RGB " => {
+            let (r, g, b) = read_f32x3(&mut block)?;
+            Rgb::new(r, g, b)
+        }
+        b"CMYK" => {
+            let (c, m, y) = read_f32x3(&mut block)?;
+            let k = 1.0 - read_f32(&mut block)?;
+            Rgb::new((1.0 - c) * k, (1.0 - m) * k, (1.0 - y) * k)
+        }
+        b"LAB " => {
+            let (l, a, b) = read_f32x3(&mut block)?;
+            lab_d50_to_srgb(Lab::new(l * 100.0, a, b))
+        }
+        b"Gray" => {
+            let v = read_f32(&mut block)?;
+            Rgb::new(v, v, v)
+        }
+        _ => return Err(invalid_data("unknown color model")),
+    };
+    let _color_type = read_u16(&mut block)?;
+
+    Ok((name, color))
+}
+
+fn lab_d50_to_srgb(lab: Lab<f32, D50>) -> Rgb<f32> {
+    let adaptation = bradford_adaptation::<f32>(&D50.get_xyz(), &D65.get_xyz());
+    let xyz = lab.to_xyz();
+    let (x, y, z) = adaptation.transform_vector(xyz.to_tuple());
+    let linear = SRgb::new().convert_from_xyz_raw(&Xyz::new(x, y, z));
+    linear
+        .normalize()
+        .linear()
+        .encode(SrgbEncoding::new())
+        .strip_encoding()
+}
+
+fn read_name<R: Read>(reader: &mut R) -> io::Result<String> {
+    let len = read_u16(reader)?;
+    let mut units = Vec::with_capacity(len as usize);
+    for _ in 0..len {
+        units.push(read_u16(reader)?);
+    }
+    if units.last() == Some(&0) {
+        units.pop();
+    }
+    String::from_utf16(&units).map_err(|_| invalid_data("invalid UTF-16 in name"))
+}
+
+fn write_name(block: &mut Vec<u8>, name: &str) -> io::Result<()> {
+    let units: Vec<u16> = name.encode_utf16().chain(Some(0)).collect();
+    if units.len() > u16::MAX as usize {
+        return Err(invalid_input("color name is too long"));
+    }
+    block.extend_from_slice(&(units.len() as u16).to_be_bytes());
+    for unit in units {
+        block.extend_from_slice(&unit.to_be_bytes());
+    }
+    Ok(())
+}
+
+fn read_u16<R: Read>(reader: &mut R) -> io::Result<u16> {
+    let mut buf = [0u8; 2];
+    reader.read_exact(&mut buf)?;
+    Ok(u16::from_be_bytes(buf))
+}
+
+fn read_u32<R: Read>(reader: &mut R) -> io::Result<u32> {
+    let mut buf = [0u8; 4];
+    reader.read_exact(&mut buf)?;
+    Ok(u32::from_be_bytes(buf))
+}
+
+fn read_f32<R: Read>(reader: &mut R) -> io::Result<f32> {
+    Ok(f32::from_bits(read_u32(reader)?))
+}
+
+fn read_f32x3<R: Read>(reader: &mut R) -> io::Result<(f32, f32, f32)> {
+    Ok((read_f32(reader)?, read_f32(reader)?, read_f32(reader)?))
+}
+
+fn invalid_data(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+fn invalid_input(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, msg)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use approx::*;
+
+    fn color_block(name: &str, model: &[u8; 4], values: &[f32]) -> Vec<u8> {
+        let mut block = Vec::new();
+        write_name(&mut block, name).unwrap();
+        block.extend_from_slice(model);
+        for v in values {
+            block.extend_from_slice(&v.to_bits().to_be_bytes());
+        }
+        block.extend_from_slice(&COLOR_TYPE_NORMAL.to_be_bytes());
+
+        let mut out = BLOCK_COLOR.to_be_bytes().to_vec();
+        out.extend_from_slice(&(block.len() as u32).to_be_bytes());
+        out.extend(block);
+        out
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let palette = vec![
+            ("Red".to_string(), Rgb::new(1.0, 0.0, 0.0)),
+            ("Sky blue".to_string(), Rgb::new(0.529, 0.808, 0.922)),
+            ("Gris clair ☁".to_string(), Rgb::new(0.8, 0.8, 0.8)),
+        ];
+        let mut buf = Vec::new();
+        write_ase(&mut buf, &palette).unwrap();
+        assert_eq!(&buf[0..4], b"ASEF");
+
+        let read = read_ase(buf.as_slice()).unwrap();
+        assert_eq!(read, palette);
+
+        let mut empty = Vec::new();
+        write_ase(&mut empty, &[]).unwrap();
+        assert_eq!(read_ase(empty.as_slice()).unwrap(), vec![]);
+    }
+
+    #[test]
+    fn test_read_models() {
+        let mut file = b"ASEF\x00\x01\x00\x00\x00\x00\x00\x06".to_vec();
+        file.extend_from_slice(&BLOCK_GROUP_START.to_be_bytes());
+        let mut group = Vec::new();
+        write_name(&mut group, "Group").unwrap();
+        file.extend_from_slice(&(group.len() as u32).to_be_bytes());
+        file.extend(group);
+        file.extend(color_block("cmyk", b"CMYK", &[0.0, 1.0, 0.5, 0.2]));
+        file.extend(color_block("gray", b"Gray", &[0.25]));
+        file.extend(color_block("white", b"LAB ", &[1.0, 0.0, 0.0]));
+        file.extend(color_block("lab", b"LAB ", &[0.5, 20.0, -30.0]));
+        file.extend_from_slice(&BLOCK_GROUP_END.to_be_bytes());
+        file.extend_from_slice(&0u32.to_be_bytes());
+
+        let colors = read_ase(file.as_slice()).unwrap();
+        let names: Vec<_> = colors.iter().map(|c| c.0.as_str()).collect();
+        assert_eq!(names, vec!["cmyk", "gray", "white", "lab"]);
+        assert_relative_eq!(colors[0].1, Rgb::new(0.8, 0.0, 0.4), epsilon = 1e-6);
+        assert_relative_eq!(colors[1].1, Rgb::new(0.25, 0.25, 0.25));
+        assert_relative_eq!(colors[2].1, Rgb::new(1.0, 1.0, 1.0), epsilon = 1e-3);
+        assert_relative_eq!(
+            colors[3].1,
+            Rgb::new(0.5211, 0.4237, 0.6685),
+            epsilon = 1e-3
+        );
+    }
+
+    #[test]
+    fn test_read_errors() {
+        let err = read_ase(&b"ASEX\x00\x01\x00\x00\x00\x00\x00\x00"[..]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+        let mut file = b"ASEF\x00\x01\x00\x00\x00\x00\x00\x01".to_vec();
+        file.extend(color_block("hsb", b"HSB ", &[0.0, 0.0, 0.0]));
+        let err = read_ase(file.as_slice()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+        let err = read_ase(&b"ASEF\x00\x01\x00\x00\x00\x00\x00\x01"[..]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+
+        // A block claiming to be 4 GiB long must not be allocated before it is read
+        let mut file = b"ASEF\x00\x01\x00\x00\x00\x00\x00\x01".to_vec();
+        file.extend_from_slice(&BLOCK_COLOR.to_be_bytes());
+        file.extend_from_slice(&u32::MAX.to_be_bytes());
+        file.extend_from_slice(&[0u8; 16]);
+        let err = read_ase(file.as_slice()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+    }
+}
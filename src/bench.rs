@@ -0,0 +1,53 @@
+//! Helpers for measuring the throughput of color conversions
+//!
+//! This module is only available with the `bench` feature enabled.
+//!
+//! The helpers here only time the conversions themselves, and leave warm up, repetition and
+//! statistics to the caller, so they can be dropped into a `criterion` or `bencher` setup, or used
+//! for ad hoc regression checks.
+
+use crate::convert::FromColor;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// Time converting every color in `samples` from `C` to `D` and back, `iters` times over
+///
+/// Returns the total elapsed time, so the time per round trip is the result divided by
+/// `samples.len() * iters`. The converted colors are passed through `std::hint::black_box` so
+/// that the optimizer can not remove the work being measured.
+pub fn bench_roundtrip<C, D>(samples: &[C], iters: usize) -> Duration
+where
+    C: FromColor<D>,
+    D: FromColor<C>,
+{
+    let start = Instant::now();
+    for _ in 0..iters {
+        for sample in samples.iter() {
+            let converted = D::from_color(black_box(sample));
+            black_box(C::from_color(&converted));
+        }
+    }
+    start.elapsed()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::hsv::Hsv;
+    use crate::rgb::Rgb;
+    use angle::Deg;
+
+    #[test]
+    fn test_bench_roundtrip() {
+        let samples: Vec<Rgb<f32>> = (0..64)
+            .map(|i| {
+                let t = i as f32 / 63.0;
+                Rgb::new(t, 1.0 - t, (t * 3.0).fract())
+            })
+            .collect();
+        let elapsed = bench_roundtrip::<_, Hsv<f32, Deg<f32>>>(&samples, 100);
+        assert!(elapsed > Duration::from_secs(0));
+        // Nothing to convert is not an error
+        bench_roundtrip::<Rgb<f32>, Hsv<f32, Deg<f32>>>(&[], 10);
+    }
+}
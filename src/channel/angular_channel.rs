@@ -4,7 +4,7 @@ use angle::Angle;
 #[cfg(feature = "approx")]
 use approx;
 
-use crate::channel::{ChannelCast, ChannelFormatCast, ColorChannel};
+use crate::channel::{debug_check_angle, ChannelCast, ChannelFormatCast, ColorChannel};
 use crate::color;
 use crate::color::Lerp;
 use num_traits;
@@ -62,7 +62,9 @@ where
         self.0.scalar()
     }
     fn from_scalar(value: T::Scalar) -> Self {
-        AngularChannel(T::new(value))
+        let value = T::new(value);
+        debug_check_angle(&value);
+        AngularChannel(value)
     }
     fn new(value: T) -> Self {
         AngularChannel(value)
@@ -4,6 +4,7 @@
 
 use super::cast::ChannelFormatCast;
 use super::scalar::{NormalChannelScalar, PosNormalChannelScalar};
+use super::strict::debug_check_bounded;
 use super::traits::ColorChannel;
 use super::ChannelCast;
 use crate::color;
@@ -36,6 +37,13 @@ impl<T> PosNormalBoundedChannel<T> {
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct NormalBoundedChannel<T>(pub T);
 
+impl<T> NormalBoundedChannel<T> {
+    /// Construct a new `NormalBoundedChannel`
+    pub const fn new_const(val: T) -> Self {
+        NormalBoundedChannel(val)
+    }
+}
+
 macro_rules! impl_bounded_channel_type {
     ($name:ident, $scalar_type:ident, $tag:ident) => {
         impl<T> ColorChannel for $name<T>
@@ -63,6 +71,7 @@ macro_rules! impl_bounded_channel_type {
                 self.0.clone()
             }
             fn from_scalar(value: T) -> Self {
+                debug_check_bounded::<Self>(&value);
                 $name(value)
             }
             fn new(value: T) -> Self {
@@ -3,10 +3,14 @@
 
 use angle;
 use angle::Angle;
+#[cfg(feature = "half")]
+use half::f16;
 use num_traits;
 
 /// Convert between channel formats
 pub trait ChannelFormatCast<Out>: Sized {
+    /// Whether every value survives a round trip through `Out` and back unchanged
+    const LOSSLESS: bool = false;
     /// Cast the channel scalar type
     fn cast(self) -> Out;
     /// Cast the channel scalar type, optionally rescaling the range
@@ -15,6 +19,25 @@ pub trait ChannelFormatCast<Out>: Sized {
     }
 }
 
+/// Returns whether casting a channel scalar from `From` to `To` is lossless
+///
+/// A cast is lossless if every value of `From` can be cast to `To` and back again without
+/// change. Since `color_cast` casts each channel independently, this also tells whether
+/// `color_cast` between two colors with those scalar types is lossless.
+///
+/// ```
+/// use prisma::channel::is_lossless;
+///
+/// assert!(is_lossless::<u8, f32>());
+/// assert!(!is_lossless::<f32, u8>());
+/// ```
+pub const fn is_lossless<From, To>() -> bool
+where
+    From: ChannelFormatCast<To>,
+{
+    <From as ChannelFormatCast<To>>::LOSSLESS
+}
+
 macro_rules! impl_cast_with_rescale_flt_to_int {
     ($from:ty, $to:ty) => {
         /// Cast the channel scalar, optionally rescaling the range
@@ -38,32 +61,38 @@ macro_rules! impl_cast_with_rescale_int_to_flt {
 }
 
 impl ChannelFormatCast<u8> for u8 {
+    const LOSSLESS: bool = true;
     fn cast(self) -> u8 {
         self
     }
 }
 impl ChannelFormatCast<u16> for u8 {
+    const LOSSLESS: bool = true;
     fn cast(self) -> u16 {
         (self as u16) * 0x0101_u16
     }
 }
 impl ChannelFormatCast<u32> for u8 {
+    const LOSSLESS: bool = true;
     fn cast(self) -> u32 {
         (self as u32) * 0x01010101_u32
     }
 }
 impl ChannelFormatCast<u64> for u8 {
+    const LOSSLESS: bool = true;
     fn cast(self) -> u64 {
         (self as u64) * 0x0101010101010101_u64
     }
 }
 impl ChannelFormatCast<f32> for u8 {
+    const LOSSLESS: bool = true;
     fn cast(self) -> f32 {
         (self as f32) / (0xFF as f32)
     }
     impl_cast_with_rescale_int_to_flt!(u8, f32);
 }
 impl ChannelFormatCast<f64> for u8 {
+    const LOSSLESS: bool = true;
     fn cast(self) -> f64 {
         (self as f64) / (0xFF as f64)
     }
@@ -76,27 +105,32 @@ impl ChannelFormatCast<u8> for u16 {
     }
 }
 impl ChannelFormatCast<u16> for u16 {
+    const LOSSLESS: bool = true;
     fn cast(self) -> u16 {
         self
     }
 }
 impl ChannelFormatCast<u32> for u16 {
+    const LOSSLESS: bool = true;
     fn cast(self) -> u32 {
         (self as u32) * 0x00010001_u32
     }
 }
 impl ChannelFormatCast<u64> for u16 {
+    const LOSSLESS: bool = true;
     fn cast(self) -> u64 {
         (self as u64) * 0x0001000100010001_u64
     }
 }
 impl ChannelFormatCast<f32> for u16 {
+    const LOSSLESS: bool = true;
     fn cast(self) -> f32 {
         (self as f32) / (0xFFFF as f32)
     }
     impl_cast_with_rescale_int_to_flt!(u16, f32);
 }
 impl ChannelFormatCast<f64> for u16 {
+    const LOSSLESS: bool = true;
     fn cast(self) -> f64 {
         (self as f64) / (0xFFFF as f64)
     }
@@ -114,11 +148,13 @@ impl ChannelFormatCast<u16> for u32 {
     }
 }
 impl ChannelFormatCast<u32> for u32 {
+    const LOSSLESS: bool = true;
     fn cast(self) -> u32 {
         self
     }
 }
 impl ChannelFormatCast<u64> for u32 {
+    const LOSSLESS: bool = true;
     fn cast(self) -> u64 {
         (self as u64) * 0x0000000100000001_u64
     }
@@ -130,6 +166,7 @@ impl ChannelFormatCast<f32> for u32 {
     impl_cast_with_rescale_int_to_flt!(u32, f32);
 }
 impl ChannelFormatCast<f64> for u32 {
+    const LOSSLESS: bool = true;
     fn cast(self) -> f64 {
         (self as f64) / (0xFFFFFFFF_u32 as f64)
     }
@@ -152,6 +189,7 @@ impl ChannelFormatCast<u32> for u64 {
     }
 }
 impl ChannelFormatCast<u64> for u64 {
+    const LOSSLESS: bool = true;
     fn cast(self) -> u64 {
         self
     }
@@ -196,11 +234,13 @@ impl ChannelFormatCast<u64> for f32 {
     impl_cast_with_rescale_flt_to_int!(f32, u64);
 }
 impl ChannelFormatCast<f32> for f32 {
+    const LOSSLESS: bool = true;
     fn cast(self) -> f32 {
         self
     }
 }
 impl ChannelFormatCast<f64> for f32 {
+    const LOSSLESS: bool = true;
     fn cast(self) -> f64 {
         self as f64
     }
@@ -238,6 +278,7 @@ impl ChannelFormatCast<f32> for f64 {
     }
 }
 impl ChannelFormatCast<f64> for f64 {
+    const LOSSLESS: bool = true;
     fn cast(self) -> f64 {
         self
     }
@@ -265,3 +306,97 @@ impl_channel_format_cast_for_angle!(Rad);
 impl_channel_format_cast_for_angle!(Turns);
 impl_channel_format_cast_for_angle!(ArcMinutes);
 impl_channel_format_cast_for_angle!(ArcSeconds);
+
+// Half-precision floats are cast by way of `f32`, which can represent every `f16` value exactly.
+#[cfg(feature = "half")]
+macro_rules! impl_cast_for_f16 {
+    ($($other:ty => $lossless:expr),*) => {
+        $(
+            impl ChannelFormatCast<$other> for f16 {
+                const LOSSLESS: bool = $lossless;
+                fn cast(self) -> $other {
+                    self.to_f32().cast()
+                }
+                fn cast_with_rescale(self, min: f64, max: f64) -> $other {
+                    self.to_f32().cast_with_rescale(min, max)
+                }
+            }
+            impl ChannelFormatCast<f16> for $other {
+                fn cast(self) -> f16 {
+                    f16::from_f32(self.cast())
+                }
+                fn cast_with_rescale(self, min: f64, max: f64) -> f16 {
+                    f16::from_f32(self.cast_with_rescale(min, max))
+                }
+            }
+        )*
+    };
+}
+
+#[cfg(feature = "half")]
+impl_cast_for_f16!(u8 => false, u16 => false, u32 => false, u64 => false, f32 => true, f64 => true);
+
+#[cfg(feature = "half")]
+impl ChannelFormatCast<f16> for f16 {
+    const LOSSLESS: bool = true;
+    fn cast(self) -> f16 {
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rgb::Rgb;
+    #[cfg(feature = "half")]
+    use crate::Bounded;
+    #[cfg(feature = "half")]
+    use approx::*;
+
+    #[test]
+    fn test_is_lossless() {
+        assert!(is_lossless::<u8, u8>());
+        assert!(is_lossless::<u8, f32>());
+        assert!(is_lossless::<u16, f64>());
+        assert!(is_lossless::<f32, f64>());
+        assert!(!is_lossless::<f32, u8>());
+        assert!(!is_lossless::<u16, u8>());
+        assert!(!is_lossless::<f64, f32>());
+        assert!(!is_lossless::<u64, f64>());
+
+        for i in 0..=255u8 {
+            let flt: f32 = i.cast();
+            assert_eq!(ChannelFormatCast::<u8>::cast(flt), i);
+        }
+        for i in 0..=0xFFFFu16 {
+            let flt: f32 = i.cast();
+            assert_eq!(ChannelFormatCast::<u16>::cast(flt), i);
+        }
+
+        let c = Rgb::new(12u8, 200, 255);
+        assert_eq!(c.color_cast::<f32>().color_cast::<u8>(), c);
+        let c = Rgb::new(0.3f32, 0.5, 0.7);
+        assert!(c.color_cast::<u8>().color_cast::<f32>() != c);
+    }
+
+    #[cfg(feature = "half")]
+    #[test]
+    fn test_f16() {
+        assert!(is_lossless::<f16, f32>());
+        assert!(!is_lossless::<f32, f16>());
+
+        let c = Rgb::new(f16::from_f32(0.25), f16::from_f32(0.5), f16::ONE);
+        assert_eq!(c.color_cast::<f32>(), Rgb::new(0.25f32, 0.5, 1.0));
+        assert_eq!(c.color_cast::<f32>().color_cast::<f16>(), c);
+        assert_eq!(c.color_cast::<f64>().color_cast::<f16>(), c);
+
+        let c = Rgb::new(0.3f32, 0.123, 0.9876);
+        let half: Rgb<f16> = c.color_cast();
+        let back: Rgb<f32> = half.color_cast();
+        assert_relative_eq!(back, c, epsilon = 1e-3);
+        assert!(half.is_normalized());
+
+        let c = Rgb::new(10u8, 128, 255);
+        assert_eq!(c.color_cast::<f16>().color_cast::<u8>(), c);
+    }
+}
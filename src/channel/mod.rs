@@ -43,20 +43,35 @@
 //! * [PosNormalBoundedChannel](bounded_channel/struct.PosNormalBoundedChannel.html)
 //! * [FreeChannel](free_channel/struct.FreeChannel.html)
 //! * [PosFreeChannel](free_channel/struct.PosFreeChannel.html)
+//!
+//! ## Strict mode:
+//!
+//! Colors may hold out of range channel values, such as a negative Rgb channel produced by a gamut
+//! conversion, and most operations will pass them through. To catch such values where they are not
+//! expected, enable the `strict` feature. Colors built with their `new_checked` constructors,
+//! channels created with `ColorChannel::from_scalar`, and values set through a color's channel
+//! setters are then checked: bounded channels must lie within their bounds and angular channels
+//! must be finite, or the call panics in builds with debug assertions. The `new` constructors stay
+//! unchecked so that they remain `const` whether or not the feature is enabled, and conversions,
+//! channel casts, `ColorChannel::new` and the `*_mut` accessors carry values through unchanged.
+//! Release builds, and builds without the feature, do no checking at all.
 
 pub mod angular_channel;
 pub mod bounded_channel;
 pub mod cast;
 pub mod free_channel;
 pub mod scalar;
+mod strict;
 pub mod traits;
 
 pub use self::angular_channel::AngularChannel;
 pub use self::bounded_channel::{NormalBoundedChannel, PosNormalBoundedChannel};
-pub use self::cast::ChannelFormatCast;
+pub use self::cast::{is_lossless, ChannelFormatCast};
 pub use self::free_channel::{FreeChannel, PosFreeChannel};
 pub use self::scalar::{
     AngularChannelScalar, BoundedChannelScalar, FreeChannelScalar, NormalChannelScalar,
     PosNormalChannelScalar,
 };
 pub use self::traits::{ChannelCast, ColorChannel};
+
+pub(crate) use self::strict::{debug_check_angle, debug_check_bounded};
@@ -5,6 +5,8 @@
 use crate::color;
 use angle;
 use angle::*;
+#[cfg(feature = "half")]
+use half::f16;
 use num_traits::{cast, Float, NumCast, PrimInt, Zero};
 use std::ops;
 
@@ -13,6 +15,8 @@ pub trait FreeChannelScalar: Clone + Float + Default {}
 
 impl FreeChannelScalar for f32 {}
 impl FreeChannelScalar for f64 {}
+#[cfg(feature = "half")]
+impl FreeChannelScalar for f16 {}
 
 /// A scalar with an upper and lower bound
 pub trait BoundedChannelScalar:
@@ -31,6 +35,8 @@ impl BoundedChannelScalar for u16 {}
 impl BoundedChannelScalar for u32 {}
 impl BoundedChannelScalar for f32 {}
 impl BoundedChannelScalar for f64 {}
+#[cfg(feature = "half")]
+impl BoundedChannelScalar for f16 {}
 
 /// A scalar for periodic, angular channels
 pub trait AngularChannelScalar:
@@ -270,3 +276,62 @@ impl_normal_bounded_channel_traits_int!(u16);
 impl_normal_bounded_channel_traits_int!(u32);
 impl_normal_bounded_channel_traits_float!(f32);
 impl_normal_bounded_channel_traits_float!(f64);
+
+#[cfg(feature = "half")]
+impl PosNormalChannelScalar for f16 {
+    #[inline]
+    fn min_bound() -> Self {
+        f16::ZERO
+    }
+    #[inline]
+    fn max_bound() -> Self {
+        f16::ONE
+    }
+    #[inline]
+    fn is_normalized(&self) -> bool {
+        *self >= f16::ZERO && *self <= f16::ONE
+    }
+    #[inline]
+    fn normalize(self) -> Self {
+        if self > f16::ONE {
+            f16::ONE
+        } else if self < f16::ZERO {
+            f16::ZERO
+        } else {
+            self
+        }
+    }
+}
+#[cfg(feature = "half")]
+impl NormalChannelScalar for f16 {
+    #[inline]
+    fn min_bound() -> Self {
+        f16::NEG_ONE
+    }
+    #[inline]
+    fn max_bound() -> Self {
+        f16::ONE
+    }
+    #[inline]
+    fn is_normalized(&self) -> bool {
+        *self >= f16::NEG_ONE && *self <= f16::ONE
+    }
+    #[inline]
+    fn normalize(self) -> Self {
+        if self > f16::ONE {
+            f16::ONE
+        } else if self < f16::NEG_ONE {
+            f16::NEG_ONE
+        } else {
+            self
+        }
+    }
+}
+#[cfg(feature = "half")]
+impl color::Lerp for f16 {
+    type Position = f16;
+    #[inline]
+    fn lerp(&self, right: &Self, pos: Self::Position) -> Self {
+        lerp_flat(self, right, pos)
+    }
+}
@@ -0,0 +1,117 @@
+//! Debug checks of channel values, enabled by the `strict` feature
+//!
+//! Each check is a `debug_assert!` that is only evaluated when the `strict` feature is enabled,
+//! so it costs nothing in release builds or when the feature is off.
+
+use super::traits::ColorChannel;
+use angle::Angle;
+use num_traits::Float;
+use std::any;
+
+/// Check that `value` lies within the bounds of the bounded channel `C`
+#[inline]
+pub(crate) fn debug_check_bounded<C>(value: &C::Format)
+where
+    C: ColorChannel,
+    C::Format: PartialOrd,
+{
+    debug_assert!(
+        !cfg!(feature = "strict") || (*value >= C::min_bound() && *value <= C::max_bound()),
+        "strict: {} value is out of range",
+        any::type_name::<C>()
+    );
+}
+
+/// Check that the angle `value` is finite
+#[inline]
+pub(crate) fn debug_check_angle<A>(value: &A)
+where
+    A: Angle,
+{
+    debug_assert!(
+        !cfg!(feature = "strict") || value.scalar().is_finite(),
+        "strict: {} angle is not finite",
+        any::type_name::<A>()
+    );
+}
+
+#[cfg(all(test, feature = "strict", debug_assertions))]
+mod test {
+    use crate::channel::{
+        AngularChannel, ColorChannel, NormalBoundedChannel, PosNormalBoundedChannel,
+    };
+    use crate::{Hsv, Rgb, YCoCg};
+    use angle::Deg;
+
+    #[test]
+    fn test_in_range_values() {
+        let _ = <PosNormalBoundedChannel<f32> as ColorChannel>::from_scalar(1.0);
+        let _ = <NormalBoundedChannel<f32> as ColorChannel>::from_scalar(-1.0);
+        let _ = <AngularChannel<Deg<f32>> as ColorChannel>::from_scalar(400.0);
+        let mut rgb = Rgb::new(0.0, 0.5, 1.0);
+        rgb.set_red(1.0);
+        assert_eq!(rgb.red(), 1.0);
+    }
+
+    #[test]
+    fn test_new_is_unchecked() {
+        let chan = <PosNormalBoundedChannel<f32> as ColorChannel>::new(1.5);
+        assert_eq!(chan.value(), 1.5);
+        let rgb: Rgb<f32> = Rgb::new(1.5f64, -0.5, 0.5).color_cast();
+        assert_eq!(rgb.red(), 1.5);
+        const HSV: Hsv<f32> = Hsv::new(Deg(f32::NAN), 1.5, 0.5);
+        assert_eq!(HSV.saturation(), 1.5);
+    }
+
+    #[test]
+    fn test_new_checked() {
+        let rgb = Rgb::new_checked(0.0, 0.5, 1.0);
+        assert_eq!(rgb, Rgb::new(0.0, 0.5, 1.0));
+        let ycocg = YCoCg::new_checked(0.5f32, -1.0, 1.0);
+        assert_eq!(ycocg.co(), -1.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "strict")]
+    fn test_out_of_range_new_checked() {
+        let _ = Rgb::new_checked(0.2f32, 1.5, 0.6);
+    }
+
+    #[test]
+    #[should_panic(expected = "strict")]
+    fn test_out_of_range_normal_new_checked() {
+        let _ = YCoCg::new_checked(0.5f64, -1.25, 0.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "strict")]
+    fn test_non_finite_new_checked() {
+        let _ = Hsv::new_checked(Deg(f32::INFINITY), 0.5, 0.5);
+    }
+
+    #[test]
+    #[should_panic(expected = "strict")]
+    fn test_out_of_range_bounded_channel() {
+        let _ = <PosNormalBoundedChannel<f32> as ColorChannel>::from_scalar(1.5);
+    }
+
+    #[test]
+    #[should_panic(expected = "strict")]
+    fn test_out_of_range_normal_channel() {
+        let _ = <NormalBoundedChannel<f64> as ColorChannel>::from_scalar(-1.5);
+    }
+
+    #[test]
+    #[should_panic(expected = "strict")]
+    fn test_out_of_range_setter() {
+        let mut rgb = Rgb::new(0.2f32, 0.4, 0.6);
+        rgb.set_green(-0.1);
+    }
+
+    #[test]
+    #[should_panic(expected = "strict")]
+    fn test_non_finite_angle() {
+        let mut hsv = Hsv::new(Deg(120.0f32), 0.5, 0.5);
+        hsv.set_hue(Deg(f32::NAN));
+    }
+}
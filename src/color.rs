@@ -32,6 +32,22 @@ pub trait PolarColor: Color {
     type Cartesian;
 }
 
+/// A color with a channel that carries its achromatic brightness
+///
+/// Models describe brightness in different ways: value in `Hsv`, lightness in `Hsl`, intensity
+/// in `Hsi`, $`L^*`$ in `Lab` and `Luv`, and luma in `YCbCr`. `Lightness` gives generic code a
+/// single way to read and adjust that channel. The values are only comparable between colors of
+/// the same model.
+pub trait Lightness: Color {
+    /// The scalar type of the lightness channel
+    type LightnessFormat;
+
+    /// Return the value of the lightness channel
+    fn lightness(&self) -> Self::LightnessFormat;
+    /// Return `self` with the lightness channel set to `lightness`, keeping the other channels
+    fn with_lightness(self, lightness: Self::LightnessFormat) -> Self;
+}
+
 /// A color that can be represented as a slice without any conversion
 pub trait Flatten: HomogeneousColor {
     /// Return `Self` constructed from `values`
@@ -40,6 +56,14 @@ pub trait Flatten: HomogeneousColor {
     fn as_slice(&self) -> &[Self::ChannelFormat];
 }
 
+/// A color whose channels can be modified in place through a slice
+pub trait FlattenMut: Flatten {
+    /// Return a mutable slice representation of `Self`
+    ///
+    /// Writing through the slice sets the channels directly, without any checks.
+    fn as_slice_mut(&mut self) -> &mut [Self::ChannelFormat];
+}
+
 /// A color only having one type of channel
 pub trait HomogeneousColor: Color {
     /// The scalar type of each channel
@@ -70,6 +94,18 @@ pub trait Lerp {
     ///
     /// $`pos = 0`$ would return `self` while $`pos = 1`$ would return right.
     fn lerp(&self, right: &Self, pos: Self::Position) -> Self;
+
+    /// Interpolate between `self` and `right` with `pos` remapped by an easing function
+    ///
+    /// This is equivalent to `self.lerp(right, easing(pos))`. The [`easing`](easing/index.html)
+    /// module provides a number of common easing curves.
+    fn lerp_eased<F>(&self, right: &Self, pos: Self::Position, easing: F) -> Self
+    where
+        Self: Sized,
+        F: Fn(Self::Position) -> Self::Position,
+    {
+        self.lerp(right, easing(pos))
+    }
 }
 
 /// A value that can be inverted
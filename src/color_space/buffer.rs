@@ -0,0 +1,127 @@
+//! Bulk conversion of pixel buffers between color spaces
+
+use crate::channel::{ChannelFormatCast, FreeChannelScalar, PosNormalChannelScalar};
+use crate::color::Bounded;
+use crate::color_space::ColorSpace;
+use crate::encoding::{ChannelDecoder, EncodableColor};
+use crate::linalg::Matrix3;
+use crate::lms::bradford_adaptation;
+use crate::rgb::Rgb;
+use num_traits;
+
+/// Convert a buffer of 8-bit pixels from one color space to another
+///
+/// Each pixel is decoded with the encoding of `from`, transformed into `to` in linear light and
+/// re-encoded with the encoding of `to`. The RGB to RGB transform is composed into a single matrix
+/// once for the whole buffer: it moves through XYZ and, if the white points of the two spaces
+/// differ, chromatically adapts from one to the other with a von Kries transform in the Bradford
+/// `LMS` space. Decoding is done through a 256 entry lookup table, so the per-pixel cost is one
+/// matrix multiply and an encode.
+///
+/// Colors outside the gamut of `to` have their channels clamped.
+pub fn convert_buffer<T, S1, S2>(src: &[Rgb<u8>], from: &S1, to: &S2) -> Vec<Rgb<u8>>
+where
+    T: FreeChannelScalar + PosNormalChannelScalar + num_traits::Float + ChannelFormatCast<f64>,
+    f64: ChannelFormatCast<T>,
+    u8: ChannelFormatCast<T>,
+    T: ChannelFormatCast<u8>,
+    S1: ColorSpace<T>,
+    S2: ColorSpace<T>,
+{
+    let transform = rgb_to_rgb_transform(from, to);
+
+    let decoder = from.encoding();
+    let linear: Vec<T> = (0..=u8::MAX)
+        .map(|value| decoder.decode_channel(value.cast()))
+        .collect();
+
+    let encoding = to.encoding();
+    src.iter()
+        .map(|pixel| {
+            let rgb = (
+                linear[pixel.red() as usize],
+                linear[pixel.green() as usize],
+                linear[pixel.blue() as usize],
+            );
+            let (r, g, b) = transform.transform_vector(rgb);
+            Rgb::new(r, g, b)
+                .normalize()
+                .linear()
+                .encode(encoding.clone())
+                .strip_encoding()
+                .color_cast()
+        })
+        .collect()
+}
+
+fn rgb_to_rgb_transform<T, S1, S2>(from: &S1, to: &S2) -> Matrix3<T>
+where
+    T: FreeChannelScalar + num_traits::Float,
+    S1: ColorSpace<T>,
+    S2: ColorSpace<T>,
+{
+    let adaptation = bradford_adaptation(&from.white_point(), &to.white_point());
+
+    to.get_inverse_xyz_transform() * adaptation * from.get_xyz_transform()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::color_space::named::{DciP3, DisplayP3, SRgb};
+    use crate::color_space::ConvertFromXyz;
+
+    fn test_buffer() -> Vec<Rgb<u8>> {
+        let mut buffer = Vec::new();
+        for r in (0..=255).step_by(51) {
+            for g in (0..=255).step_by(51) {
+                for b in (0..=255).step_by(51) {
+                    buffer.push(Rgb::new(r as u8, g as u8, b as u8));
+                }
+            }
+        }
+        buffer
+    }
+
+    #[test]
+    fn test_convert_buffer() {
+        let srgb = SRgb::<f64>::new();
+        let p3 = DisplayP3::<f64>::new();
+
+        let src = test_buffer();
+        let in_p3 = convert_buffer(&src, &srgb, &p3);
+        assert_eq!(in_p3.len(), src.len());
+        // Both trips quantize to 8 bits, which costs up to two steps in dark, saturated colors
+        let back = convert_buffer(&in_p3, &p3, &srgb);
+        for (orig, round_trip) in src.iter().zip(back.iter()) {
+            assert!(orig.red().abs_diff(round_trip.red()) <= 2);
+            assert!(orig.green().abs_diff(round_trip.green()) <= 2);
+            assert!(orig.blue().abs_diff(round_trip.blue()) <= 2);
+        }
+
+        let pixel = Rgb::new(200u8, 60, 30);
+        let xyz = srgb.convert_rgb_to_xyz(&pixel.color_cast(), &srgb.encoding());
+        let expected: Rgb<u8> = p3
+            .convert_from_xyz(&xyz)
+            .strip_space()
+            .strip_encoding()
+            .color_cast();
+        assert_eq!(convert_buffer(&[pixel], &srgb, &p3), vec![expected]);
+
+        assert_eq!(convert_buffer(&src, &srgb, &srgb), src);
+        assert!(convert_buffer(&[], &srgb, &p3).is_empty());
+    }
+
+    #[test]
+    fn test_convert_buffer_adapts_white() {
+        let srgb = SRgb::<f64>::new();
+        let dci = DciP3::<f64>::new();
+
+        let white = Rgb::new(255u8, 255, 255);
+        assert_eq!(convert_buffer(&[white], &srgb, &dci), vec![white]);
+        let gray = Rgb::new(128u8, 128, 128);
+        let dci_gray = convert_buffer(&[gray], &srgb, &dci)[0];
+        assert_eq!(dci_gray.red(), dci_gray.green());
+        assert_eq!(dci_gray.green(), dci_gray.blue());
+    }
+}
@@ -4,12 +4,14 @@ use std::sync::Arc;
 use crate::alpha::{Rgba, Xyza};
 use crate::channel::{ChannelFormatCast, FreeChannelScalar, PosNormalChannelScalar};
 use crate::color::Color;
+use crate::convert::FromColor;
 use crate::encoding::{
     ChannelDecoder, ChannelEncoder, ColorEncoding, EncodableColor, EncodedColor, LinearEncoding,
     TranscodableColor,
 };
 use crate::linalg::Matrix3;
 use crate::rgb::Rgb;
+use crate::xyy::XyY;
 use crate::xyz::Xyz;
 use num_traits;
 
@@ -42,6 +44,37 @@ pub trait ColorSpace<T>: Clone {
 
     /// Apply the forward transform to a 3-vector
     fn apply_transform(&self, vec: (T, T, T)) -> (T, T, T);
+
+    /// Returns the colorimetry of the color space: its red, green and blue primaries and its white point
+    ///
+    /// Together with an encoding, these fully describe a color space. They can be turned back into
+    /// one with [`EncodedColorSpace::from_primaries_and_white`](struct.EncodedColorSpace.html#method.from_primaries_and_white).
+    fn primaries_and_white(&self) -> ([RgbPrimary<T>; 3], XyY<T>)
+    where
+        T: num_traits::Float + FreeChannelScalar + PosNormalChannelScalar,
+    {
+        let primaries = [
+            self.red_primary(),
+            self.green_primary(),
+            self.blue_primary(),
+        ];
+        (primaries, XyY::from_color(&self.white_point()))
+    }
+
+    /// Convert a bare `Rgb` color into XYZ, decoding it with `encoding`
+    ///
+    /// This gives the same result as calling `convert_to_xyz` with `rgb` wrapped in an
+    /// `EncodedColor`, for when the encoding is known separately from the color.
+    fn convert_rgb_to_xyz<E>(&self, rgb: &Rgb<T>, encoding: &E) -> Xyz<T>
+    where
+        T: num_traits::Float + FreeChannelScalar + PosNormalChannelScalar + ChannelFormatCast<f64>,
+        f64: ChannelFormatCast<T>,
+        E: ColorEncoding,
+    {
+        let linear_color = rgb.clone().decode_color(encoding);
+        let (x, y, z) = self.apply_transform(linear_color.to_tuple());
+        Xyz::new(x, y, z)
+    }
 }
 
 /// An object that can convert a color into XYZ
@@ -139,6 +172,19 @@ where
         }
     }
 
+    /// Construct a new `EncodedColorSpace` from red, green and blue primaries, a white point in xyY
+    /// and an encoding
+    ///
+    /// This is the inverse of [`ColorSpace::primaries_and_white`](trait.ColorSpace.html#method.primaries_and_white).
+    pub fn from_primaries_and_white(
+        primaries: [RgbPrimary<T>; 3],
+        white: XyY<T>,
+        encoding: E,
+    ) -> Self {
+        let [red, green, blue] = primaries;
+        EncodedColorSpace::new(red, green, blue, Xyz::from_color(&white), encoding)
+    }
+
     /// Construct a new `EncodedColorSpace` from primaries, a white point and an encoding as well as transformation matrices
     ///
     /// This does not verify the correctness of the transformation matricies, so only use it if you are positive.
@@ -499,11 +545,11 @@ mod test {
     use crate::color::*;
     use crate::color_space::named::*;
     use crate::color_space::primary::RgbPrimary;
-    use crate::color_space::WithColorSpace;
+    use crate::color_space::{UnitColorSpace, WithColorSpace};
     use crate::encoding::*;
     use crate::linalg::Matrix3;
     use crate::rgb::Rgb;
-    use crate::white_point::{WhitePoint, D65};
+    use crate::white_point::{WhitePoint, D65, DCI};
     use crate::xyz::Xyz;
     use approx::*;
 
@@ -520,6 +566,65 @@ mod test {
         assert_eq!(rgb, rgb2.strip_space());
     }
 
+    #[test]
+    fn test_convert_rgb_to_xyz() {
+        let srgb = SRgb::new();
+        let colors = [
+            Rgb::new(0.0, 0.0, 0.0),
+            Rgb::new(1.0, 1.0, 1.0),
+            Rgb::new(0.25, 0.55, 0.89),
+            Rgb::new(-0.3, 1.2, 0.8),
+        ];
+        for rgb in colors.iter() {
+            assert_relative_eq!(
+                srgb.convert_rgb_to_xyz(rgb, &SrgbEncoding::new()),
+                srgb.convert_to_xyz(&rgb.encoded_as(SrgbEncoding::new())),
+                epsilon = 1e-12
+            );
+            assert_relative_eq!(
+                srgb.convert_rgb_to_xyz(rgb, &LinearEncoding::new()),
+                srgb.convert_to_xyz(&rgb.encoded_as(LinearEncoding::new())),
+                epsilon = 1e-12
+            );
+        }
+        assert_relative_eq!(
+            srgb.convert_rgb_to_xyz(&Rgb::new(0.25, 0.55, 0.89), &SrgbEncoding::new()),
+            Xyz::new(0.253659, 0.254514, 0.761978),
+            epsilon = 1e-6
+        );
+    }
+
+    #[test]
+    fn test_primaries_and_white() {
+        let srgb = SRgb::<f64>::new();
+        let (primaries, white) = srgb.primaries_and_white();
+        assert_eq!(primaries[0], srgb.red_primary());
+        assert_eq!(primaries[1], srgb.green_primary());
+        assert_eq!(primaries[2], srgb.blue_primary());
+        assert_relative_eq!(white.x(), 0.312727, epsilon = 1e-6);
+        assert_relative_eq!(white.y(), 0.329023, epsilon = 1e-6);
+        assert_relative_eq!(white.Y(), 1.0);
+
+        let space =
+            EncodedColorSpace::from_primaries_and_white(primaries.clone(), white, SrgbEncoding);
+        assert_relative_eq!(space.white_point(), srgb.white_point(), epsilon = 1e-12);
+        assert_relative_eq!(
+            space.get_xyz_transform(),
+            srgb.get_xyz_transform(),
+            epsilon = 1e-12
+        );
+        let (primaries2, white2) = space.primaries_and_white();
+        assert_eq!(primaries2, primaries);
+        assert_relative_eq!(white2, white, epsilon = 1e-12);
+
+        let rgb = Rgb::new(0.25, 0.5, 0.75).srgb_encoded();
+        assert_relative_eq!(
+            space.convert_to_xyz(&rgb),
+            srgb.convert_to_xyz(&rgb),
+            epsilon = 1e-12
+        );
+    }
+
     #[test]
     fn test_to_xyz() {
         let linear_srgb = LinearColorSpace::new_linear_color_space(
@@ -662,4 +767,63 @@ mod test {
             epsilon = 1e-4
         );
     }
+
+    #[test]
+    fn test_p3_spaces() {
+        let dci = DciP3::<f64>::new();
+        let display = DisplayP3::<f64>::new();
+        assert_eq!(dci.red_primary(), display.red_primary());
+        assert_eq!(dci.blue_primary(), display.blue_primary());
+        assert_eq!(dci.encoding(), GammaEncoding::new(2.6));
+        assert_eq!(display.encoding(), SrgbEncoding::new());
+
+        // SMPTE RP 431-2
+        assert_relative_eq!(
+            dci.get_xyz_transform(),
+            Matrix3::new([
+                0.4451698, 0.2771344, 0.1722827, 0.2094917, 0.7215953, 0.0689131, 0.0, 0.0470606,
+                0.9073554
+            ]),
+            epsilon = 1e-6
+        );
+        assert_relative_eq!(
+            display.get_xyz_transform(),
+            Matrix3::new([
+                0.4865709, 0.2656677, 0.1982173, 0.2289746, 0.6917385, 0.0792869, 0.0, 0.0451134,
+                1.0439444
+            ]),
+            epsilon = 5e-4
+        );
+        // The precomputed matrices agree with the ones derived from the primaries
+        let built = DciP3::<f64>::build_color_space_instance();
+        assert_relative_eq!(
+            built.get_xyz_transform(),
+            dci.get_xyz_transform(),
+            epsilon = 1e-6
+        );
+        let built = DisplayP3::<f64>::build_color_space_instance();
+        assert_relative_eq!(
+            built.get_xyz_transform(),
+            display.get_xyz_transform(),
+            epsilon = 1e-6
+        );
+
+        let white = Rgb::new(1.0, 1.0, 1.0);
+        let dci_white = dci.convert_rgb_to_xyz(&white, &dci.encoding());
+        let display_white = display.convert_rgb_to_xyz(&white, &display.encoding());
+        assert_relative_eq!(dci_white, DCI.get_xyz(), epsilon = 1e-6);
+        assert_relative_eq!(display_white, D65.get_xyz(), epsilon = 1e-6);
+        assert!((dci_white.z() - display_white.z()).abs() > 0.1);
+        let round_trip = dci
+            .get_inverse_xyz_transform()
+            .transform_vector(dci_white.to_tuple());
+        assert_relative_eq!(Rgb::from_tuple(round_trip), white, epsilon = 1e-9);
+
+        // The curves differ as well, so mid gray is darker in DCI-P3
+        let gray = Rgb::new(0.5, 0.5, 0.5);
+        let dci_y = dci.convert_rgb_to_xyz(&gray, &dci.encoding()).y();
+        let display_y = display.convert_rgb_to_xyz(&gray, &display.encoding()).y();
+        assert_relative_eq!(dci_y, 0.5f64.powf(2.6), epsilon = 1e-9);
+        assert!(dci_y < display_y - 0.03);
+    }
 }
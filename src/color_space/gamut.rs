@@ -0,0 +1,175 @@
+//! Functions for comparing and tracing the gamuts of color spaces
+
+use crate::channel::{ChannelFormatCast, FreeChannelScalar, PosNormalChannelScalar};
+use crate::color::Color;
+use crate::color_space::{ColorSpace, RgbPrimary};
+use crate::encoding::TranscodableColor;
+use crate::lchab::Lchab;
+use crate::rgb::Rgb;
+use crate::white_point::UnitWhitePoint;
+use angle::Deg;
+use num_traits;
+
+/// Compute the fraction of a reference color space's gamut covered by another color space
+///
+/// The gamut of an Rgb color space is the triangle formed by its primaries. The coverage is
+/// the ratio of the area of `inner`'s triangle to that of `reference`'s triangle, measured in the
+/// CIE 1976 $`u'v'`$ chromaticity plane. $`u'v'`$ is used rather than $`xy`$ as it is
+/// much closer to perceptually uniform, so equal areas represent similar numbers of
+/// distinguishable chromaticities.
+///
+/// Note that this is a ratio of areas, not of the intersection of the two triangles. If `inner`
+/// has a larger gamut than `reference`, the result will be greater than one.
+pub fn gamut_coverage<T, S1, S2>(inner: &S1, reference: &S2) -> T
+where
+    T: num_traits::Float + PosNormalChannelScalar,
+    S1: ColorSpace<T>,
+    S2: ColorSpace<T>,
+{
+    gamut_area(inner) / gamut_area(reference)
+}
+
+/// Trace the boundary of the gamut of `space` at a fixed CIELAB lightness
+///
+/// Returns `steps` colors with lightness `L` and hues evenly spaced around the circle, starting
+/// at zero. Each has the largest chroma that `space` can display at its lightness and hue, as
+/// found by [`Lchab::max_chroma`](../struct.Lchab.html#method.max_chroma), so together they
+/// outline the slice of the gamut at `L`. This shows how far colors of each hue can be pushed
+/// before clipping, for example to pick the most vivid accent color of a hue.
+///
+/// If `L` is outside of what `space` can display, every color has zero chroma.
+#[allow(non_snake_case)]
+pub fn gamut_boundary_ring<T, W, S>(L: T, steps: usize, space: &S) -> Vec<Lchab<T, W>>
+where
+    T: FreeChannelScalar + PosNormalChannelScalar + ChannelFormatCast<f64> + num_traits::Float,
+    f64: ChannelFormatCast<T>,
+    W: UnitWhitePoint<T>,
+    S: ColorSpace<T>,
+{
+    let step: T = num_traits::cast(360.0 / steps as f64).unwrap();
+    (0..steps)
+        .map(|i| {
+            let hue = Deg(step * num_traits::cast(i).unwrap());
+            let chroma = Lchab::<T, W>::new(L, T::zero(), hue).max_chroma(space);
+            Lchab::new(L, chroma, hue)
+        })
+        .collect()
+}
+
+/// Returns the largest saturation, at most `saturation`, at which a color is in the gamut of `space`
+///
+/// `to_rgb` gives the color at a saturation, encoded with the encoding of `space`. The color is in
+/// gamut if it decodes to linear channels in $`[0, 1]`$, so it survives quantizing to `Rgb<u8>`
+/// and back without clipping. The saturation is first clamped into $`[0, 1]`$, and if the color
+/// is still out of gamut it is reduced by bisection, taking a saturation of zero to be in gamut.
+pub(crate) fn clamp_saturation_to_gamut<T, S, F>(saturation: T, space: &S, to_rgb: F) -> T
+where
+    T: PosNormalChannelScalar + ChannelFormatCast<f64> + num_traits::Float,
+    f64: ChannelFormatCast<T>,
+    S: ColorSpace<T>,
+    F: Fn(T) -> Rgb<T>,
+{
+    let encoding = space.encoding();
+    let in_gamut = |s: T| {
+        let (r, g, b) = to_rgb(s).decode_color(&encoding).to_tuple();
+        [r, g, b].iter().all(|&c| c >= T::zero() && c <= T::one())
+    };
+
+    let saturation = saturation.max(T::zero()).min(T::one());
+    if in_gamut(saturation) {
+        return saturation;
+    }
+    let (mut lo, mut hi) = (T::zero(), saturation);
+    let half: T = num_traits::cast(0.5).unwrap();
+    for _ in 0..50 {
+        let mid = (lo + hi) * half;
+        if in_gamut(mid) {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    lo
+}
+
+fn gamut_area<T, S>(space: &S) -> T
+where
+    T: num_traits::Float + PosNormalChannelScalar,
+    S: ColorSpace<T>,
+{
+    let (ru, rv) = primary_to_uv_prime(space.red_primary());
+    let (gu, gv) = primary_to_uv_prime(space.green_primary());
+    let (bu, bv) = primary_to_uv_prime(space.blue_primary());
+
+    let cross = (gu - ru) * (bv - rv) - (bu - ru) * (gv - rv);
+    cross.abs() * num_traits::cast(0.5).unwrap()
+}
+
+fn primary_to_uv_prime<T>(primary: RgbPrimary<T>) -> (T, T)
+where
+    T: num_traits::Float + PosNormalChannelScalar,
+{
+    let (x, y) = primary.to_tuple();
+    let denom = num_traits::cast::<_, T>(-2.0).unwrap() * x
+        + num_traits::cast::<_, T>(12.0).unwrap() * y
+        + num_traits::cast(3.0).unwrap();
+    let u = num_traits::cast::<_, T>(4.0).unwrap() * x / denom;
+    let v = num_traits::cast::<_, T>(9.0).unwrap() * y / denom;
+    (u, v)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::color_space::named::SRgb;
+    use crate::color_space::LinearColorSpace;
+    use crate::white_point::{WhitePoint, D65};
+    use angle::Angle;
+    use approx::*;
+
+    fn rec2020() -> LinearColorSpace<f64> {
+        LinearColorSpace::new_linear_color_space(
+            RgbPrimary::new(0.708, 0.292),
+            RgbPrimary::new(0.170, 0.797),
+            RgbPrimary::new(0.131, 0.046),
+            D65.get_xyz(),
+        )
+    }
+
+    #[test]
+    fn test_gamut_coverage() {
+        let srgb = SRgb::<f64>::new();
+        let rec2020 = rec2020();
+        assert_relative_eq!(gamut_coverage(&srgb, &srgb), 1.0);
+        assert_relative_eq!(gamut_coverage(&rec2020, &rec2020), 1.0);
+
+        let srgb_in_2020 = gamut_coverage(&srgb, &rec2020);
+        assert_relative_eq!(srgb_in_2020, 0.580310, epsilon = 1e-5);
+        assert_relative_eq!(gamut_coverage(&rec2020, &srgb), 1.0 / srgb_in_2020);
+    }
+
+    #[test]
+    fn test_gamut_boundary_ring() {
+        let srgb = SRgb::<f64>::new();
+        let ring = gamut_boundary_ring::<_, D65, _>(60.0, 24, &srgb);
+        assert_eq!(ring.len(), 24);
+        for (i, color) in ring.iter().enumerate() {
+            assert_relative_eq!(color.L(), 60.0);
+            assert_relative_eq!(color.hue().scalar(), 15.0 * i as f64, epsilon = 1e-9);
+            assert!(color.chroma() > 20.0);
+            assert!(color.clip_with_delta(&srgb).1 < 1e-6);
+            let beyond = Lchab::<_, D65>::new(60.0, color.chroma() + 0.1, color.hue());
+            assert!(beyond.clip_with_delta(&srgb).1 > 0.0);
+        }
+
+        let rec2020 = rec2020();
+        let wide = gamut_boundary_ring::<_, D65, _>(60.0, 24, &rec2020);
+        for (narrow, wide) in ring.iter().zip(wide.iter()) {
+            assert!(wide.chroma() >= narrow.chroma());
+        }
+
+        assert!(gamut_boundary_ring::<_, D65, _>(60.0, 0, &srgb).is_empty());
+        let dark = gamut_boundary_ring::<_, D65, _>(-5.0, 4, &srgb);
+        assert!(dark.iter().all(|color| color.chroma() == 0.0));
+    }
+}
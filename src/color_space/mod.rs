@@ -1,14 +1,19 @@
 //! Traits and structures to define color spaces and convert from device-dependent to device-independent spaces
 
+mod buffer;
 mod color_space;
+mod gamut;
 /// Named built-in color spaces
 pub mod named;
 mod primary;
 mod spaced_color;
 
+pub use self::buffer::convert_buffer;
 pub use self::color_space::{
     ColorSpace, ConvertFromXyz, ConvertToXyz, EncodedColorSpace, LinearColorSpace,
 };
+pub(crate) use self::gamut::clamp_saturation_to_gamut;
+pub use self::gamut::{gamut_boundary_ring, gamut_coverage};
 pub use self::primary::RgbPrimary;
 pub use self::spaced_color::SpacedColor;
 use crate::encoding::{ColorEncoding, EncodableColor};
@@ -8,10 +8,10 @@ use crate::alpha::{Rgba, Xyza};
 use crate::channel::{ChannelFormatCast, FreeChannelScalar, PosNormalChannelScalar};
 use crate::color::Color;
 use crate::color_space::{ColorSpace, EncodedColorSpace, RgbPrimary};
-use crate::encoding::{ColorEncoding, EncodedColor, SrgbEncoding};
+use crate::encoding::{ColorEncoding, EncodedColor, GammaEncoding, Rec709Encoding, SrgbEncoding};
 use crate::linalg::Matrix3;
 use crate::rgb::Rgb;
-use crate::white_point::{WhitePoint, D65};
+use crate::white_point::{WhitePoint, D65, DCI};
 use crate::xyz::Xyz;
 use num_traits;
 use num_traits::cast;
@@ -33,11 +33,68 @@ impl<T> SRgb<T> {
     }
 }
 
-/// Use this macro to easily implement a new color space. You need the primaries, white point and
-/// precomputed forward and backward transformation matrices.
+/// The Display P3 color space
+///
+/// Display P3 uses the wide gamut primaries of DCI-P3 with the D65 white point and the sRgb
+/// transfer function. It is the native space of many recent displays, and is the space named
+/// `display-p3` in CSS.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub struct DisplayP3<T> {
+    _marker: PhantomData<T>,
+}
+
+impl<T> DisplayP3<T> {
+    /// Construct a new DisplayP3 instance
+    pub fn new() -> DisplayP3<T> {
+        DisplayP3 {
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// The DCI-P3 color space used for digital cinema projection
+///
+/// DCI-P3 shares its primaries with [`DisplayP3`](struct.DisplayP3.html), but uses the greenish
+/// [`DCI`](../../white_point/deg_2/struct.DCI.html) theatrical white point and a pure gamma of 2.6,
+/// as specified by SMPTE RP 431-2. The two are often confused, but the same encoded values give
+/// noticeably different colors in each, and even white is not the same.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub struct DciP3<T> {
+    _marker: PhantomData<T>,
+}
+
+impl<T> DciP3<T> {
+    /// Construct a new DciP3 instance
+    pub fn new() -> DciP3<T> {
+        DciP3 {
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// The ITU-R BT.2020 color space used for ultra high definition video
+///
+/// The transfer function is the Rec.709 curve, which BT.2020 specifies for 10-bit signals. The
+/// 12-bit variant uses slightly more precise constants for the same curve.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub struct Rec2020<T> {
+    _marker: PhantomData<T>,
+}
+
+impl<T> Rec2020<T> {
+    /// Construct a new Rec2020 instance
+    pub fn new() -> Rec2020<T> {
+        Rec2020 {
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// Use this macro to easily implement a new color space. You need the primaries, white point,
+/// encoding type and value, and precomputed forward and backward transformation matrices.
 macro_rules! impl_known_color_space {
     ($name:ident primaries=(($rx:expr, $ry:expr), ($gx:expr, $gy:expr), ($bx:expr, $by:expr)),
-        wp=$wp:expr, enc=$enc:ident, mat=[$($m:expr),*], mat_inv=[$($m_inv:expr),*]) =>
+        wp=$wp:expr, enc=$enc:ty = $enc_val:expr, mat=[$($m:expr),*], mat_inv=[$($m_inv:expr),*]) =>
     {
         impl<T> ColorSpace<T> for $name<T>
             where T: num_traits::Float + FreeChannelScalar + PosNormalChannelScalar
@@ -62,7 +119,7 @@ macro_rules! impl_known_color_space {
                 Matrix3::new([$(cast($m_inv).unwrap()),*])
             }
             fn encoding(&self) -> Self::Encoding {
-                Self::Encoding::default()
+                $enc_val
             }
             fn apply_transform(&self, vec: (T, T, T)) -> (T, T, T) {
                 self.get_xyz_transform().transform_vector(vec)
@@ -77,7 +134,7 @@ macro_rules! impl_known_color_space {
                     RgbPrimary::new(cast($gx).unwrap(), cast($gy).unwrap()),
                     RgbPrimary::new(cast($bx).unwrap(), cast($by).unwrap()),
                     $wp.get_xyz(),
-                    $enc::default(),
+                    $enc_val,
                 )
             }
         }
@@ -135,7 +192,31 @@ macro_rules! impl_known_color_space {
 impl_known_color_space!(SRgb
     primaries=((0.6400, 0.3300), (0.300, 0.600), (0.150, 0.060)),
     wp=D65,
-    enc=SrgbEncoding,
+    enc=SrgbEncoding = SrgbEncoding::new(),
     mat=[0.41245643908969226, 0.3575760776439089, 0.1804374832663989, 0.21267285140562256, 0.7151521552878178, 0.07217499330655956, 0.019333895582329303, 0.11919202588130294, 0.9503040785363677],
     mat_inv=[3.2404541621141036, -1.537138512797716, -0.49853140955601594, -0.9692660305051867, 1.8760108454466942, 0.04155601753034982, 0.05564343095911471, -0.20402591351675378, 1.0572251882231791]
 );
+
+impl_known_color_space!(DisplayP3
+    primaries=((0.680, 0.320), (0.265, 0.690), (0.150, 0.060)),
+    wp=D65,
+    enc=SrgbEncoding = SrgbEncoding::new(),
+    mat=[0.48663265, 0.2656631625, 0.1981741875, 0.2290036, 0.691726725, 0.079269675, 0.0, 0.0451126125, 1.0437173875],
+    mat_inv=[2.493180755328967, -0.9312655254971399, -0.4026597237588818, -0.8295031158210788, 1.7626941211197924, 0.023625088741739585, 0.03585362578007157, -0.07618895478265209, 0.9570926215180215]
+);
+
+impl_known_color_space!(DciP3
+    primaries=((0.680, 0.320), (0.265, 0.690), (0.150, 0.060)),
+    wp=DCI,
+    enc=GammaEncoding<T> = GammaEncoding::new(cast(2.6).unwrap()),
+    mat=[0.44516981556455243, 0.2771344092067776, 0.17228266981556456, 0.20949167791273055, 0.7215952541610436, 0.06891306792622583, 0.0, 0.047060560053981106, 0.9073553943619733],
+    mat_inv=[2.725394030491733, -1.018003006227185, -0.4401631951900365, -0.7951680258087642, 1.6897320548436239, 0.02264719060847746, 0.04124189139569989, -0.08763901921586227, 1.1009293786463221]
+);
+
+impl_known_color_space!(Rec2020
+    primaries=((0.708, 0.292), (0.170, 0.797), (0.131, 0.046)),
+    wp=D65,
+    enc=Rec709Encoding = Rec709Encoding::new(),
+    mat=[0.6370101914111008, 0.14461502739696933, 0.16884478119192983, 0.26272171736164046, 0.6779892755022621, 0.05928900713609749, 0.0, 0.028072328847646984, 1.060757671152353],
+    mat_inv=[1.7165106697619736, -0.3556416699867159, -0.2533455418219072, -0.6666930011826241, 1.6165022083469105, 0.015768750389994986, 0.017643638767459134, -0.04277978166904475, 0.9423050727200186]
+);
@@ -70,6 +70,29 @@ where
     pub fn space(&self) -> &S {
         &self.space
     }
+    /// Returns the underlying color only if `self` is in the `expected` color space
+    ///
+    /// The spaces are considered equal when their primaries, white points and encodings match
+    /// exactly. `expected` must use the same type of encoding as the space of `self`. If the spaces
+    /// differ, `self` is returned unchanged in `Err`.
+    pub fn strip_if_space<S2>(self, expected: &S2) -> Result<C, Self>
+    where
+        S2: ColorSpace<T, Encoding = S::Encoding>,
+        S::Encoding: PartialEq,
+        T: PartialEq,
+    {
+        let space = &self.space;
+        if space.encoding() == expected.encoding()
+            && space.red_primary() == expected.red_primary()
+            && space.green_primary() == expected.green_primary()
+            && space.blue_primary() == expected.blue_primary()
+            && space.white_point() == expected.white_point()
+        {
+            Ok(self.strip())
+        } else {
+            Err(self)
+        }
+    }
 }
 
 impl<T, C, E, S> SpacedColor<T, C, E, S>
@@ -386,8 +409,9 @@ where
 mod tests {
     use super::*;
     use crate::color_space::named::SRgb;
-    use crate::color_space::WithColorSpace;
-    use crate::encoding::SrgbEncoding;
+    use crate::color_space::{LinearColorSpace, RgbPrimary, UnitColorSpace, WithColorSpace};
+    use crate::encoding::{GammaEncoding, SrgbEncoding};
+    use crate::white_point::{WhitePoint, D50, D65};
     use crate::{Rgb, Rgba, Xyza};
     use approx::*;
 
@@ -404,6 +428,62 @@ mod tests {
         assert_eq!(rgb1.encoding(), &SrgbEncoding);
     }
 
+    #[test]
+    fn test_strip_if_space() {
+        let rgb = Rgb::new(0.5, 0.75, 1.0f32)
+            .srgb_encoded()
+            .with_color_space(SRgb::<f32>::new());
+
+        assert_eq!(
+            rgb.clone().strip_if_space(&SRgb::new()),
+            Ok(Rgb::new(0.5, 0.75, 1.0))
+        );
+        let linear_srgb = LinearColorSpace::new_linear_color_space(
+            RgbPrimary::new(0.6400, 0.3300),
+            RgbPrimary::new(0.300, 0.600),
+            RgbPrimary::new(0.150, 0.060),
+            D65.get_xyz(),
+        );
+        assert_eq!(
+            rgb.clone()
+                .strip_if_space(&linear_srgb.with_encoding(SrgbEncoding::new())),
+            Ok(Rgb::new(0.5, 0.75, 1.0))
+        );
+        assert_eq!(
+            rgb.clone()
+                .strip_if_space(&SRgb::<f32>::build_color_space_instance()),
+            Ok(Rgb::new(0.5, 0.75, 1.0))
+        );
+        let gamma_srgb =
+            SRgb::<f32>::build_color_space_instance().with_encoding(GammaEncoding::new(2.2f32));
+        let gamma = Rgb::new(0.5, 0.75, 1.0f32)
+            .encoded_as(GammaEncoding::new(2.2))
+            .with_color_space(gamma_srgb.clone());
+        assert_eq!(
+            gamma.clone().strip_if_space(&gamma_srgb),
+            Ok(Rgb::new(0.5, 0.75, 1.0))
+        );
+        let gamma_24 = gamma_srgb.with_encoding(GammaEncoding::new(2.4f32));
+        assert_eq!(gamma.clone().strip_if_space(&gamma_24), Err(gamma));
+
+        let adobe_rgb = LinearColorSpace::new_linear_color_space(
+            RgbPrimary::new(0.6400, 0.3300),
+            RgbPrimary::new(0.2100, 0.7100),
+            RgbPrimary::new(0.1500, 0.0600),
+            D65.get_xyz(),
+        );
+        let adobe_rgb = adobe_rgb.with_encoding(SrgbEncoding::new());
+        assert_eq!(rgb.clone().strip_if_space(&adobe_rgb), Err(rgb.clone()));
+        let d50_srgb = LinearColorSpace::new_linear_color_space(
+            RgbPrimary::new(0.6400, 0.3300),
+            RgbPrimary::new(0.300, 0.600),
+            RgbPrimary::new(0.150, 0.060),
+            D50.get_xyz(),
+        );
+        let d50_srgb = d50_srgb.with_encoding(SrgbEncoding::new());
+        assert_eq!(rgb.clone().strip_if_space(&d50_srgb), Err(rgb));
+    }
+
     #[test]
     fn test_alpha() {
         let rgba1 = Rgba::new(Rgb::new(0.3, 0.5, 0.7), 1.0);
@@ -0,0 +1,150 @@
+//! Defines `DiscreteColor`, an 8-bit Rgb color with a total order for use as a map key
+
+use crate::channel::{ChannelFormatCast, PosNormalChannelScalar};
+use crate::rgb::Rgb;
+use std::fmt;
+
+/// An 8-bit Rgb color packed into a single integer
+///
+/// `DiscreteColor` stores the channels as `0xRRGGBB`, and derives `Eq`, `Ord` and `Hash` from
+/// that integer. It is meant to be used as a key in a `BTreeMap`, `BTreeSet` or `HashMap`, where
+/// float colors can't be used directly. Colors are ordered by red, then green, then blue.
+///
+/// Float colors can be stored by quantizing them to 8 bits with
+/// [`quantize`](#method.quantize), so that colors that would display identically share a key.
+///
+/// ```rust
+/// use prisma::{DiscreteColor, Rgb};
+/// use std::collections::BTreeMap;
+///
+/// let mut counts = BTreeMap::new();
+/// for color in [Rgb::new(200u8, 0, 0), Rgb::new(0, 0, 255), Rgb::new(200, 0, 0)].iter() {
+///     *counts.entry(DiscreteColor::from(*color)).or_insert(0) += 1;
+/// }
+/// let ordered: Vec<_> = counts.into_iter().collect();
+/// assert_eq!(
+///     ordered,
+///     vec![
+///         (DiscreteColor::new(0, 0, 255), 1),
+///         (DiscreteColor::new(200, 0, 0), 2),
+///     ]
+/// );
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DiscreteColor(u32);
+
+impl DiscreteColor {
+    /// Construct a `DiscreteColor` from 8-bit red, green and blue channels
+    pub const fn new(red: u8, green: u8, blue: u8) -> Self {
+        DiscreteColor(((red as u32) << 16) | ((green as u32) << 8) | blue as u32)
+    }
+    /// Construct a `DiscreteColor` from a packed `0xRRGGBB` integer
+    ///
+    /// Any bits above the lowest 24 are ignored.
+    pub const fn from_packed(packed: u32) -> Self {
+        DiscreteColor(packed & 0x00ff_ffff)
+    }
+    /// Construct a `DiscreteColor` by quantizing each channel of `color` to 8 bits
+    pub fn quantize<T>(color: &Rgb<T>) -> Self
+    where
+        T: PosNormalChannelScalar + ChannelFormatCast<u8>,
+    {
+        let color: Rgb<u8> = color.color_cast();
+        DiscreteColor::from(color)
+    }
+    /// Returns the color packed as `0xRRGGBB`
+    pub const fn packed(self) -> u32 {
+        self.0
+    }
+    /// Returns the red channel
+    pub const fn red(self) -> u8 {
+        (self.0 >> 16) as u8
+    }
+    /// Returns the green channel
+    pub const fn green(self) -> u8 {
+        (self.0 >> 8) as u8
+    }
+    /// Returns the blue channel
+    pub const fn blue(self) -> u8 {
+        self.0 as u8
+    }
+}
+
+impl From<Rgb<u8>> for DiscreteColor {
+    fn from(color: Rgb<u8>) -> Self {
+        DiscreteColor::new(color.red(), color.green(), color.blue())
+    }
+}
+
+impl From<DiscreteColor> for Rgb<u8> {
+    fn from(color: DiscreteColor) -> Self {
+        Rgb::new(color.red(), color.green(), color.blue())
+    }
+}
+
+impl fmt::Display for DiscreteColor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "#{:06x}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_construct() {
+        let c1 = DiscreteColor::new(0x12, 0xab, 0xef);
+        assert_eq!(c1.packed(), 0x12abef);
+        assert_eq!((c1.red(), c1.green(), c1.blue()), (0x12, 0xab, 0xef));
+        assert_eq!(DiscreteColor::from_packed(0xff12_abef), c1);
+        assert_eq!(c1.to_string(), "#12abef");
+
+        let rgb = Rgb::new(7u8, 128, 255);
+        assert_eq!(Rgb::from(DiscreteColor::from(rgb)), rgb);
+        assert_eq!(
+            DiscreteColor::quantize(&Rgb::new(1.0f32, 0.5, 0.0)),
+            DiscreteColor::from(Rgb::new(1.0f32, 0.5, 0.0).color_cast::<u8>())
+        );
+        assert_eq!(
+            DiscreteColor::quantize(&Rgb::new(0.0f64, 1.0, 1.0)),
+            DiscreteColor::new(0, 255, 255)
+        );
+    }
+
+    #[test]
+    fn test_btree_map() {
+        let colors = [
+            Rgb::new(255u8, 255, 255),
+            Rgb::new(0, 0, 0),
+            Rgb::new(0, 255, 0),
+            Rgb::new(255, 0, 0),
+            Rgb::new(0, 0, 255),
+            Rgb::new(0, 255, 0),
+            Rgb::new(254, 255, 255),
+        ];
+        let mut map = BTreeMap::new();
+        for (index, color) in colors.iter().enumerate() {
+            map.entry(DiscreteColor::from(*color))
+                .or_insert_with(Vec::new)
+                .push(index);
+        }
+        let entries: Vec<_> = map
+            .into_iter()
+            .map(|(key, indices)| (Rgb::from(key), indices))
+            .collect();
+        assert_eq!(
+            entries,
+            vec![
+                (Rgb::new(0, 0, 0), vec![1]),
+                (Rgb::new(0, 0, 255), vec![4]),
+                (Rgb::new(0, 255, 0), vec![2, 5]),
+                (Rgb::new(254, 255, 255), vec![6]),
+                (Rgb::new(255, 0, 0), vec![3]),
+                (Rgb::new(255, 255, 255), vec![0]),
+            ]
+        );
+    }
+}
@@ -0,0 +1,116 @@
+//! Easing functions for use with [`Lerp::lerp_eased`](../trait.Lerp.html#method.lerp_eased)
+//!
+//! Each function maps a position in $`[0, 1]`$ to a new position in $`[0, 1]`$, with $`0`$ and $`1`$
+//! mapping to themselves. Inputs outside of $`[0, 1]`$ are not clamped.
+
+use num_traits::Float;
+
+/// The identity easing, giving plain linear interpolation
+pub fn linear<T: Float>(t: T) -> T {
+    t
+}
+
+/// Quadratic easing that starts slowly and accelerates: $`t^2`$
+pub fn ease_in<T: Float>(t: T) -> T {
+    t * t
+}
+
+/// Quadratic easing that starts quickly and decelerates: $`t(2 - t)`$
+pub fn ease_out<T: Float>(t: T) -> T {
+    let two: T = num_traits::cast(2.0).unwrap();
+    t * (two - t)
+}
+
+/// Quadratic easing that accelerates until the midpoint and decelerates after
+pub fn ease_in_out<T: Float>(t: T) -> T {
+    let two: T = num_traits::cast(2.0).unwrap();
+    let half: T = num_traits::cast(0.5).unwrap();
+    if t < half {
+        two * t * t
+    } else {
+        let u = two - two * t;
+        T::one() - u * u * half
+    }
+}
+
+/// Hermite smoothstep: $`t^2(3 - 2t)`$
+///
+/// The curve has zero slope at both ends.
+pub fn smoothstep<T: Float>(t: T) -> T {
+    let two: T = num_traits::cast(2.0).unwrap();
+    let three: T = num_traits::cast(3.0).unwrap();
+    t * t * (three - two * t)
+}
+
+/// Ken Perlin's smootherstep: $`t^3(t(6t - 15) + 10)`$
+///
+/// The curve has zero slope and zero second derivative at both ends.
+pub fn smootherstep<T: Float>(t: T) -> T {
+    let six: T = num_traits::cast(6.0).unwrap();
+    let fifteen: T = num_traits::cast(15.0).unwrap();
+    let ten: T = num_traits::cast(10.0).unwrap();
+    t * t * t * (t * (six * t - fifteen) + ten)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::color::Lerp;
+    use crate::hsv::Hsv;
+    use crate::rgb::Rgb;
+    use angle::Deg;
+    use approx::*;
+
+    #[test]
+    fn test_endpoints() {
+        let easings: [fn(f64) -> f64; 6] = [
+            linear,
+            ease_in,
+            ease_out,
+            ease_in_out,
+            smoothstep,
+            smootherstep,
+        ];
+        for easing in easings.iter() {
+            assert_relative_eq!(easing(0.0), 0.0);
+            assert_relative_eq!(easing(1.0), 1.0);
+        }
+        assert_relative_eq!(ease_in_out(0.5), 0.5);
+        assert_relative_eq!(smoothstep(0.5), 0.5);
+        assert_relative_eq!(smootherstep(0.5), 0.5);
+    }
+
+    #[test]
+    fn test_values() {
+        assert_relative_eq!(ease_in(0.25), 0.0625);
+        assert_relative_eq!(ease_out(0.25), 0.4375);
+        assert_relative_eq!(ease_in_out(0.25), 0.125);
+        assert_relative_eq!(ease_in_out(0.75), 0.875);
+        assert_relative_eq!(smoothstep(0.25), 0.15625);
+        assert_relative_eq!(smootherstep(0.25), 0.103515625);
+    }
+
+    #[test]
+    fn test_lerp_eased() {
+        let c1 = Rgb::new(0.2, 0.8, 0.4);
+        let c2 = Rgb::new(1.0, 0.0, 0.6);
+        for t in [0.0, 0.25, 0.6, 1.0].iter() {
+            assert_relative_eq!(c1.lerp_eased(&c2, *t, linear), c1.lerp(&c2, *t));
+            assert_relative_eq!(c1.lerp_eased(&c2, *t, |x| x), c1.lerp(&c2, *t));
+        }
+        // smoothstep(0.25) = 0.15625
+        assert_relative_eq!(
+            c1.lerp_eased(&c2, 0.25, smoothstep),
+            Rgb::new(0.325, 0.675, 0.43125),
+            epsilon = 1e-12
+        );
+
+        let h1 = Hsv::new(Deg(0.0), 0.0, 0.0);
+        let h2 = Hsv::new(Deg(160.0), 1.0, 0.8);
+        assert_relative_eq!(
+            h1.lerp_eased(&h2, 0.25, smoothstep),
+            Hsv::new(Deg(25.0), 0.15625, 0.125),
+            epsilon = 1e-12
+        );
+    }
+}
@@ -49,6 +49,9 @@ where
     A: AngularChannelScalar + Angle<Scalar = T>,
 {
     /// Construct an eHsi instance from hue, saturation and intensity.
+    ///
+    /// The hue is stored as given, so `new` accepts angles outside of a single turn. Use
+    /// [`normalize_hue`](#method.normalize_hue) to wrap it eagerly.
     pub const fn new(hue: A, saturation: T, intensity: T) -> Self {
         eHsi {
             hue: AngularChannel::new(hue),
@@ -57,6 +60,20 @@ where
         }
     }
 
+    impl_color_checked_new!(
+        fn new_checked(hue: A, saturation: T, intensity: T) {
+            crate::channel::debug_check_angle(&hue);
+            crate::channel::debug_check_bounded::<PosNormalBoundedChannel<T>>(&saturation);
+            crate::channel::debug_check_bounded::<PosNormalBoundedChannel<T>>(&intensity);
+        }
+    );
+
+    impl_color_from_hue_units_angular!(eHsi {
+        hue,
+        saturation,
+        intensity
+    });
+
     impl_color_color_cast_angular!(
         eHsi {
             hue,
@@ -92,14 +109,17 @@ where
     }
     /// Set the hue channel value
     pub fn set_hue(&mut self, val: A) {
+        crate::channel::debug_check_angle(&val);
         self.hue.0 = val;
     }
     /// Set the saturation channel value
     pub fn set_saturation(&mut self, val: T) {
+        crate::channel::debug_check_bounded::<PosNormalBoundedChannel<T>>(&val);
         self.saturation.0 = val;
     }
     /// Set the intensity channel value
     pub fn set_intensity(&mut self, val: T) {
+        crate::channel::debug_check_bounded::<PosNormalBoundedChannel<T>>(&val);
         self.intensity.0 = val;
     }
     /// Returns whether the `eHsi` instance would be the same in `Hsi`
@@ -141,6 +161,15 @@ where
             None
         }
     }
+
+    impl_color_complement_angular!(eHsi);
+    impl_color_normalize_hue_angular!(eHsi);
+    impl_color_to_feature_vector_angular!(
+        /// Returns the color as a feature vector `[cos(hue), sin(hue), saturation, intensity]`
+        ///
+        /// The saturation and intensity are converted to `f64` in $`[0, 1]`$.
+        {saturation, intensity}
+    );
 }
 
 impl<T, A> PolarColor for eHsi<T, A>
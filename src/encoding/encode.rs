@@ -8,6 +8,7 @@ use crate::channel::{ChannelFormatCast, PosNormalChannelScalar};
 use crate::color::Color;
 use crate::rgb::Rgb;
 use num_traits;
+use std::error;
 use std::fmt;
 
 /// An object that can encode a color from a linear encoding to a different encoding
@@ -62,6 +63,89 @@ pub struct LinearEncoding;
 /// A gamma encoding scheme with a given value for $`\gamma`$
 #[derive(Clone, Debug, PartialEq)]
 pub struct GammaEncoding<T>(pub T);
+/// The opto-electronic transfer function (OETF) from ITU-R BT.709, used by HD video cameras
+///
+/// Rec.709 is often confused with sRGB as they share primaries, but the curves differ. Rec.709
+/// has a linear region with a slope of 4.5 below $`L = 0.018`$, and then transitions to an exponent
+/// of 0.45:
+///
+/// ```math
+/// V = \begin{cases}
+/// 4.5L & L < 0.018 \\
+/// 1.099L^{0.45} - 0.099 & L \geq 0.018
+/// \end{cases}
+/// ```
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct Rec709Encoding;
+/// A logarithmic encoding in the style of ARRI LogC, used for film scans and digital cinema
+///
+/// The curve is linear below the `cut` point and logarithmic above it:
+///
+/// ```math
+/// V = \begin{cases}
+/// eL + f & L \leq cut \\
+/// c\log_{10}(aL + b) + d & L > cut
+/// \end{cases}
+/// ```
+///
+/// The `Default` implementation uses the ARRI LogC (v3) constants for an exposure index of 800,
+/// which maps a mid-gray of 0.18 to a code value of about 0.391.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LogCEncoding<T> {
+    /// The linear value where the curve switches from the linear to the logarithmic segment
+    pub cut: T,
+    /// The scale applied to the linear value inside the logarithm
+    pub a: T,
+    /// The offset applied to the linear value inside the logarithm
+    pub b: T,
+    /// The scale of the logarithmic segment
+    pub c: T,
+    /// The offset of the logarithmic segment
+    pub d: T,
+    /// The slope of the linear segment
+    pub e: T,
+    /// The offset of the linear segment
+    pub f: T,
+}
+
+/// A transfer function given by sampled curves, for measured or otherwise arbitrary responses
+///
+/// The encode table holds the encoded values at evenly spaced linear values from 0 to 1, and the
+/// decode table holds the linear values at evenly spaced encoded values from 0 to 1. Channels
+/// are linearly interpolated between the two nearest samples, so the accuracy depends on the
+/// number of samples and on how curved the function is between them. Inputs outside of
+/// $`[0, 1]`$ are clamped to the ends of the tables.
+///
+/// Both tables must be monotonically non-decreasing, which is checked on construction.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TableEncoding<T> {
+    encode: Vec<T>,
+    decode: Vec<T>,
+}
+
+/// An error produced when constructing a [`TableEncoding`](struct.TableEncoding.html) from
+/// invalid tables
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum TableEncodingError {
+    /// A table has fewer than two samples, so there is nothing to interpolate between
+    TooFewSamples,
+    /// A table contains an infinite or NaN sample
+    NonFiniteSample,
+    /// A table decreases somewhere
+    NotMonotonic,
+}
+
+impl fmt::Display for TableEncodingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            TableEncodingError::TooFewSamples => write!(f, "tables need at least two samples"),
+            TableEncodingError::NonFiniteSample => write!(f, "table samples must be finite"),
+            TableEncodingError::NotMonotonic => write!(f, "tables must be non-decreasing"),
+        }
+    }
+}
+
+impl error::Error for TableEncodingError {}
 
 impl SrgbEncoding {
     /// Construct a new SrgbEncoding
@@ -162,6 +246,60 @@ impl fmt::Display for LinearEncoding {
     }
 }
 
+impl Rec709Encoding {
+    /// Construct a new `Rec709Encoding`
+    pub fn new() -> Self {
+        Rec709Encoding {}
+    }
+}
+
+impl ChannelDecoder for Rec709Encoding {
+    fn decode_channel<T>(&self, val: T) -> T
+    where
+        T: num_traits::Float,
+    {
+        let one: T = num_traits::cast(1.0).unwrap();
+        let a: T = num_traits::cast(0.099).unwrap();
+        let k: T = num_traits::cast(4.5).unwrap();
+        let exponent: T = num_traits::cast(0.45).unwrap();
+        let linear_threshold: T = num_traits::cast(0.081).unwrap();
+
+        if val.abs() < linear_threshold {
+            val / k
+        } else {
+            let operand = (val.abs() + a) / (one + a);
+            val.signum() * operand.powf(one / exponent)
+        }
+    }
+}
+
+impl ChannelEncoder for Rec709Encoding {
+    fn encode_channel<T>(&self, val: T) -> T
+    where
+        T: num_traits::Float,
+    {
+        let one: T = num_traits::cast(1.0).unwrap();
+        let a: T = num_traits::cast(0.099).unwrap();
+        let k: T = num_traits::cast(4.5).unwrap();
+        let exponent: T = num_traits::cast(0.45).unwrap();
+        let linear_threshold: T = num_traits::cast(0.018).unwrap();
+
+        if val.abs() < linear_threshold {
+            k * val
+        } else {
+            val.signum() * ((one + a) * val.abs().powf(exponent) - a)
+        }
+    }
+}
+
+impl ColorEncoding for Rec709Encoding {}
+
+impl fmt::Display for Rec709Encoding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Rec.709")
+    }
+}
+
 impl<T> GammaEncoding<T>
 where
     T: num_traits::Float,
@@ -217,6 +355,194 @@ where
     }
 }
 
+impl<T> LogCEncoding<T>
+where
+    T: num_traits::Float,
+{
+    /// Construct a new `LogCEncoding` from the curve constants
+    pub fn new(cut: T, a: T, b: T, c: T, d: T, e: T, f: T) -> Self {
+        LogCEncoding {
+            cut,
+            a,
+            b,
+            c,
+            d,
+            e,
+            f,
+        }
+    }
+}
+
+impl<T> ChannelDecoder for LogCEncoding<T>
+where
+    T: num_traits::Float,
+{
+    fn decode_channel<U>(&self, val: U) -> U
+    where
+        U: num_traits::Float,
+    {
+        let cast = |x: T| -> U { num_traits::cast(x).unwrap() };
+        let ten: U = num_traits::cast(10.0).unwrap();
+        let (a, b, c, d, e, f) = (
+            cast(self.a),
+            cast(self.b),
+            cast(self.c),
+            cast(self.d),
+            cast(self.e),
+            cast(self.f),
+        );
+
+        if val > e * cast(self.cut) + f {
+            (ten.powf((val - d) / c) - b) / a
+        } else {
+            (val - f) / e
+        }
+    }
+}
+
+impl<T> ChannelEncoder for LogCEncoding<T>
+where
+    T: num_traits::Float,
+{
+    fn encode_channel<U>(&self, val: U) -> U
+    where
+        U: num_traits::Float,
+    {
+        let cast = |x: T| -> U { num_traits::cast(x).unwrap() };
+
+        if val > cast(self.cut) {
+            cast(self.c) * (cast(self.a) * val + cast(self.b)).log10() + cast(self.d)
+        } else {
+            cast(self.e) * val + cast(self.f)
+        }
+    }
+}
+
+impl<T: num_traits::Float> ColorEncoding for LogCEncoding<T> {}
+
+impl<T: num_traits::Float> Default for LogCEncoding<T> {
+    fn default() -> Self {
+        let cast = |x: f64| -> T { num_traits::cast(x).unwrap() };
+        LogCEncoding::new(
+            cast(0.010591),
+            cast(5.555556),
+            cast(0.052272),
+            cast(0.247190),
+            cast(0.385537),
+            cast(5.367655),
+            cast(0.092809),
+        )
+    }
+}
+
+impl<T> fmt::Display for LogCEncoding<T>
+where
+    T: num_traits::Float + fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "LogC")
+    }
+}
+
+impl<T> TableEncoding<T>
+where
+    T: num_traits::Float,
+{
+    /// Construct a new `TableEncoding` from an encode and a decode table
+    ///
+    /// The tables may have different lengths. An error is returned if either has fewer than two
+    /// samples, holds a sample that is not finite, or decreases anywhere.
+    pub fn new(encode: Vec<T>, decode: Vec<T>) -> Result<Self, TableEncodingError> {
+        Self::validate(&encode)?;
+        Self::validate(&decode)?;
+        Ok(TableEncoding { encode, decode })
+    }
+    /// Construct a `TableEncoding` by sampling another encoding `samples` times per table
+    ///
+    /// This is mainly useful for replacing an expensive transfer function by a cheaper
+    /// approximation. An error is returned if `samples` is less than two, or `encoding` is not
+    /// monotonic over $`[0, 1]`$.
+    pub fn sample<E>(encoding: &E, samples: usize) -> Result<Self, TableEncodingError>
+    where
+        E: ColorEncoding,
+    {
+        let step = T::one() / num_traits::cast(samples.saturating_sub(1).max(1)).unwrap();
+        let points = (0..samples).map(|i| step * num_traits::cast(i).unwrap());
+        let encode = points.clone().map(|x| encoding.encode_channel(x)).collect();
+        let decode = points.map(|x| encoding.decode_channel(x)).collect();
+        TableEncoding::new(encode, decode)
+    }
+    /// Returns the encode table
+    pub fn encode_table(&self) -> &[T] {
+        &self.encode
+    }
+    /// Returns the decode table
+    pub fn decode_table(&self) -> &[T] {
+        &self.decode
+    }
+
+    fn validate(table: &[T]) -> Result<(), TableEncodingError> {
+        if table.len() < 2 {
+            return Err(TableEncodingError::TooFewSamples);
+        }
+        if table.iter().any(|x| !x.is_finite()) {
+            return Err(TableEncodingError::NonFiniteSample);
+        }
+        if table.windows(2).any(|w| w[1] < w[0]) {
+            return Err(TableEncodingError::NotMonotonic);
+        }
+        Ok(())
+    }
+
+    fn interpolate<U>(table: &[T], val: U) -> U
+    where
+        U: num_traits::Float,
+    {
+        let last = table.len() - 1;
+        let pos = val.max(U::zero()).min(U::one()) * num_traits::cast(last).unwrap();
+        let index = pos.floor().to_usize().unwrap_or(0).min(last - 1);
+        let frac = pos - num_traits::cast(index).unwrap();
+        let lo: U = num_traits::cast(table[index]).unwrap();
+        let hi: U = num_traits::cast(table[index + 1]).unwrap();
+        lo + (hi - lo) * frac
+    }
+}
+
+impl<T> ChannelDecoder for TableEncoding<T>
+where
+    T: num_traits::Float,
+{
+    fn decode_channel<U>(&self, val: U) -> U
+    where
+        U: num_traits::Float,
+    {
+        Self::interpolate(&self.decode, val)
+    }
+}
+
+impl<T> ChannelEncoder for TableEncoding<T>
+where
+    T: num_traits::Float,
+{
+    fn encode_channel<U>(&self, val: U) -> U
+    where
+        U: num_traits::Float,
+    {
+        Self::interpolate(&self.encode, val)
+    }
+}
+
+impl<T: num_traits::Float> ColorEncoding for TableEncoding<T> {}
+
+impl<T> fmt::Display for TableEncoding<T>
+where
+    T: num_traits::Float,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Table({}, {})", self.encode.len(), self.decode.len())
+    }
+}
+
 impl<T> TranscodableColor for Rgb<T>
 where
     T: PosNormalChannelScalar + ChannelFormatCast<f64>,
@@ -359,4 +685,118 @@ mod test {
         );
         assert_relative_eq!(t6.decode(), c6, epsilon = 1e-6);
     }
+
+    #[test]
+    fn test_rec709_encoding() {
+        let enc = Rec709Encoding::new();
+        assert_relative_eq!(enc.encode_channel(0.0), 0.0);
+        assert_relative_eq!(enc.encode_channel(1.0), 1.0, epsilon = 1e-12);
+        assert_relative_eq!(enc.encode_channel(0.01), 0.045, epsilon = 1e-12);
+        assert_relative_eq!(enc.encode_channel(0.18), 0.409007728, epsilon = 1e-6);
+        assert_relative_eq!(enc.encode_channel(-0.18), -0.409007728, epsilon = 1e-6);
+
+        let c1 = Rgb::new(0.5, 0.5, 0.5f64).encoded_as(LinearEncoding::new());
+        let t1 = c1.clone().encode(Rec709Encoding::new());
+        let s1 = c1.clone().encode(SrgbEncoding::new());
+        assert_relative_eq!(*t1.color(), Rgb::broadcast(0.705515089), epsilon = 1e-6);
+        assert!((t1.red() - s1.red()).abs() > 0.02);
+        assert_relative_eq!(t1.decode(), c1, epsilon = 1e-12);
+
+        for i in 0..=1000 {
+            let val = i as f64 / 1000.0;
+            let encoded = enc.encode_channel(val);
+            assert_relative_eq!(enc.decode_channel(encoded), val, epsilon = 1e-12);
+            let diff = encoded - SrgbEncoding::new().encode_channel(val);
+            assert!(diff.abs() < 0.1);
+            if i > 0 && i < 1000 {
+                assert!(diff != 0.0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_logc_encoding() {
+        let enc = LogCEncoding::<f64>::default();
+        assert_relative_eq!(enc.encode_channel(0.18), 0.391007, epsilon = 1e-5);
+        assert_relative_eq!(enc.decode_channel(0.391007), 0.18, epsilon = 1e-5);
+        assert_relative_eq!(enc.encode_channel(0.0), 0.092809, epsilon = 1e-12);
+
+        let mut last = enc.encode_channel(-0.01);
+        for i in -9..=2000 {
+            let val = i as f64 / 1000.0;
+            let encoded = enc.encode_channel(val);
+            assert!(encoded > last);
+            assert_relative_eq!(enc.decode_channel(encoded), val, epsilon = 1e-9);
+            last = encoded;
+        }
+
+        let c1 = Rgb::new(0.18, 0.005, 1.5f64).encoded_as(LinearEncoding::new());
+        let t1 = c1.clone().encode(LogCEncoding::<f64>::default());
+        assert_relative_eq!(t1.red(), 0.391007, epsilon = 1e-5);
+        assert_relative_eq!(t1.decode(), c1, epsilon = 1e-9);
+
+        let c2 = Rgb::new(0.18, 0.5, 1.0f32).encoded_as(LinearEncoding::new());
+        let t2 = c2.clone().encode(LogCEncoding::<f32>::default());
+        assert_relative_eq!(t2.decode(), c2, epsilon = 1e-5);
+    }
+
+    #[test]
+    fn test_table_encoding() {
+        let samples = 1024;
+        let table = TableEncoding::<f64>::sample(&SrgbEncoding::new(), samples).unwrap();
+        assert_eq!(table.encode_table().len(), samples);
+        assert_eq!(table.decode_table().len(), samples);
+        assert_eq!(format!("{}", table), "Table(1024, 1024)");
+
+        // The interpolation error shrinks with the square of the step where the curve is smooth,
+        // but the sRgb encode bends sharply near black, which needs a looser bound
+        let step = 1.0 / (samples - 1) as f64;
+        let srgb = SrgbEncoding::new();
+        for i in 0..=1000 {
+            let val = i as f64 / 1000.0;
+            assert_relative_eq!(
+                table.decode_channel(val),
+                srgb.decode_channel(val),
+                epsilon = step * step
+            );
+            assert_relative_eq!(
+                table.encode_channel(val),
+                srgb.encode_channel(val),
+                epsilon = step
+            );
+        }
+        assert_eq!(table.encode_channel(0.0), 0.0);
+        assert_relative_eq!(table.encode_channel(1.0), 1.0, epsilon = 1e-12);
+        assert_relative_eq!(table.encode_channel(1.5), 1.0, epsilon = 1e-12);
+        assert_eq!(table.decode_channel(-0.5), 0.0);
+
+        let c1 = Rgb::new(0.2, 0.55, 0.9f32).encoded_as(LinearEncoding::new());
+        let t1 = c1.clone().encode(table.clone());
+        let s1 = c1.clone().encode(SrgbEncoding::new());
+        assert_relative_eq!(t1.color(), s1.color(), epsilon = 1e-5);
+        assert_relative_eq!(t1.decode(), c1, epsilon = 1e-5);
+
+        let identity = TableEncoding::new(vec![0.0, 1.0], vec![0.0, 0.5, 1.0]).unwrap();
+        assert_relative_eq!(identity.encode_channel(0.3), 0.3, epsilon = 1e-12);
+        assert_relative_eq!(identity.decode_channel(0.7), 0.7, epsilon = 1e-12);
+
+        assert_eq!(
+            TableEncoding::new(vec![0.0], vec![0.0, 1.0]),
+            Err(TableEncodingError::TooFewSamples)
+        );
+        assert_eq!(
+            TableEncoding::new(vec![0.0, 1.0], vec![0.0, 0.6, 0.5, 1.0]),
+            Err(TableEncodingError::NotMonotonic)
+        );
+        assert_eq!(
+            TableEncoding::new(vec![0.0, f64::NAN, 1.0], vec![0.0, 1.0]),
+            Err(TableEncodingError::NonFiniteSample)
+        );
+        assert_eq!(
+            TableEncoding::<f64>::sample(&SrgbEncoding::new(), 1),
+            Err(TableEncodingError::TooFewSamples)
+        );
+        // Flat segments are allowed
+        assert!(TableEncoding::new(vec![0.0, 0.0, 1.0], vec![0.0, 1.0, 1.0]).is_ok());
+    }
 }
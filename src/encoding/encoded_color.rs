@@ -1,11 +1,12 @@
 //! Provides the `EncodedColor` type for storing colors with their encodings.
 
 use super::EncodableColor;
-use crate::channel::{AngularChannelScalar, PosNormalChannelScalar};
+use crate::channel::{AngularChannelScalar, ChannelFormatCast, PosNormalChannelScalar};
 use crate::color_space::{ColorSpace, SpacedColor, WithColorSpace};
 use crate::convert::{FromColor, FromHsi, FromYCbCr};
-use crate::encoding::encode::{ColorEncoding, LinearEncoding, TranscodableColor};
+use crate::encoding::encode::{ColorEncoding, LinearEncoding, SrgbEncoding, TranscodableColor};
 use crate::hsi::{Hsi, HsiOutOfGamutMode};
+use crate::rgb::Rgb;
 use crate::ycbcr::{YCbCr, YCbCrModel, YCbCrOutOfGamutMode};
 use crate::{Bounded, Broadcast, Color, Color3, Color4, FromTuple, Invert, Lerp, PolarColor};
 use angle::Angle;
@@ -103,6 +104,61 @@ where
     }
 }
 
+impl<T, E> EncodedColor<Rgb<T>, E>
+where
+    T: PosNormalChannelScalar + ChannelFormatCast<f64>,
+    f64: ChannelFormatCast<T>,
+    E: ColorEncoding,
+{
+    /// Change the channel scalar type and the encoding in a single step
+    ///
+    /// The color is decoded to linear and re-encoded with `encoding` using `f64` channels, then cast
+    /// to `ToT`. Doing the transcode at full precision avoids the banding that would result from
+    /// decoding after casting to a narrow integer type.
+    pub fn recode<ToT, ToE>(&self, encoding: ToE) -> EncodedColor<Rgb<ToT>, ToE>
+    where
+        ToT: PosNormalChannelScalar,
+        f64: ChannelFormatCast<ToT>,
+        ToE: ColorEncoding,
+    {
+        let wide: Rgb<f64> = self.color.color_cast();
+        let (recoded, encoding) = wide
+            .encoded_as(self.encoding.clone())
+            .transcode(encoding)
+            .decompose();
+        EncodedColor::new(recoded.color_cast(), encoding)
+    }
+}
+
+impl EncodedColor<Rgb<f32>, SrgbEncoding> {
+    /// Decode the color to linear and quantize it to 16 bits per channel
+    ///
+    /// The returned channels store *linear* light, not sRgb encoded values, so they must not be
+    /// displayed or saved as if they were sRgb. Sixteen bits are enough to keep linear data free
+    /// of visible banding in the shadows, which eight bits are not. Channels are clamped into
+    /// $`[0, 1]`$ and rounded to the nearest step. The decode is done in `f64`.
+    pub fn to_linear_u16(&self) -> Rgb<u16> {
+        let wide: Rgb<f64> = self.color.color_cast();
+        let linear = wide.decode_color(&self.encoding);
+        let quantize = |v: f64| (v.clamp(0.0, 1.0) * 65535.0).round() as u16;
+        Rgb::new(
+            quantize(linear.red()),
+            quantize(linear.green()),
+            quantize(linear.blue()),
+        )
+    }
+    /// Construct an sRgb encoded color from linear 16-bit channels
+    ///
+    /// This is the inverse of [`to_linear_u16`](#method.to_linear_u16): each channel of `color`
+    /// is read as linear light and encoded with the sRgb transfer function.
+    pub fn from_linear_u16(color: &Rgb<u16>) -> Self {
+        let wide: Rgb<f64> = color.color_cast();
+        let encoding = SrgbEncoding::new();
+        let encoded = wide.encode_color(&encoding);
+        EncodedColor::new(encoded.color_cast(), encoding)
+    }
+}
+
 impl<C, E> EncodedColor<C, E>
 where
     C: Color + Broadcast + EncodableColor,
@@ -347,6 +403,7 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::encoding::SrgbEncoding;
     use crate::test;
     use crate::{Hsv, Rgb};
     use angle::Deg;
@@ -399,4 +456,49 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_recode() {
+        let c1 = Rgb::new(200u8, 64, 0).srgb_encoded();
+        let r1: EncodedColor<Rgb<f32>, _> = c1.recode(LinearEncoding::new());
+        let manual = Rgb::new(200u8, 64, 0)
+            .color_cast::<f32>()
+            .srgb_encoded()
+            .decode();
+        assert_relative_eq!(r1, manual, epsilon = 1e-6);
+        assert_eq!(r1.encoding(), &LinearEncoding::new());
+
+        let r2: EncodedColor<Rgb<u8>, _> = r1.recode(SrgbEncoding::new());
+        assert_eq!(r2, c1);
+
+        let c3 = Rgb::new(0.5, 0.25, 1.0).linear();
+        let r3: EncodedColor<Rgb<f64>, _> = c3.recode(LinearEncoding::new());
+        assert_relative_eq!(r3, c3, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_linear_u16() {
+        let gray = Rgb::new(0.5f32, 0.5, 0.5).srgb_encoded();
+        // The sRgb decode of 0.5 is 0.21404, or 14027.1 in 16 bits
+        assert_eq!(gray.to_linear_u16(), Rgb::new(14027u16, 14027, 14027));
+
+        let black = Rgb::new(0.0f32, 0.0, 0.0).srgb_encoded();
+        assert_eq!(black.to_linear_u16(), Rgb::new(0u16, 0, 0));
+        let white = Rgb::new(1.0f32, 1.0, 1.0).srgb_encoded();
+        assert_eq!(white.to_linear_u16(), Rgb::new(65535u16, 65535, 65535));
+
+        let c1 = Rgb::new(0.8f32, 0.35, 0.02).srgb_encoded();
+        let linear = c1.to_linear_u16();
+        let manual = c1.clone().decode();
+        assert_relative_eq!(
+            linear.color_cast::<f32>(),
+            *manual.color(),
+            epsilon = 1.0 / 65535.0
+        );
+        let back = EncodedColor::from_linear_u16(&linear);
+        assert_eq!(back.encoding(), &SrgbEncoding::new());
+        // A 16-bit linear step is at most about 0.001 after encoding, near black
+        assert_relative_eq!(back, c1, epsilon = 1e-3);
+        assert_eq!(back.to_linear_u16(), linear);
+    }
 }
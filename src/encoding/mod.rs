@@ -30,11 +30,14 @@
 //!
 //! ## Encoding Schemes:
 //!
-//! Prisma provides three different encoding schemes:
+//! Prisma provides six different encoding schemes:
 //!
 //! * [`LinearEncoding`](encode/struct.LinearEncoding.html) A color with no encoding at all, linear in intensity
 //! * [`SrgbEncoding`](encode/struct.SrgbEncoding.html) A modified gamma encoding used specifically with the sRGB color space
 //! * [`GammaEncoding`](encode/struct.GammaEncoding.html) A general gamma encoding with specified value for gamma
+//! * [`Rec709Encoding`](encode/struct.Rec709Encoding.html) The camera transfer function used by HD video
+//! * [`LogCEncoding`](encode/struct.LogCEncoding.html) A logarithmic encoding used for film scans and digital cinema
+//! * [`TableEncoding`](encode/struct.TableEncoding.html) An arbitrary transfer function given by sampled curves
 //!
 //! A color can have its encoding specified in the type system by wrapping it in [`EncodedColor`](encoded_color/struct.EncodedColor.html).
 //!
@@ -71,8 +74,8 @@ mod encode;
 mod encoded_color;
 
 pub use self::encode::{
-    ChannelDecoder, ChannelEncoder, ColorEncoding, GammaEncoding, LinearEncoding, SrgbEncoding,
-    TranscodableColor,
+    ChannelDecoder, ChannelEncoder, ColorEncoding, GammaEncoding, LinearEncoding, LogCEncoding,
+    Rec709Encoding, SrgbEncoding, TableEncoding, TableEncodingError, TranscodableColor,
 };
 pub use self::encoded_color::{EncodedColor, LinearColor};
 
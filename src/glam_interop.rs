@@ -0,0 +1,53 @@
+//! Conversions between colors and `glam` vectors
+//!
+//! This module is only available with the `glam` feature enabled. `Rgb<f32>` converts to and
+//! from a `Vec3` holding the red, green and blue channels, and `Rgba<f32>` to and from a `Vec4`
+//! with alpha last. The channels are copied as they are, without any change of encoding.
+
+use crate::alpha::Rgba;
+use crate::rgb::Rgb;
+
+impl From<Rgb<f32>> for glam::Vec3 {
+    fn from(color: Rgb<f32>) -> Self {
+        glam::Vec3::new(color.red(), color.green(), color.blue())
+    }
+}
+
+impl From<glam::Vec3> for Rgb<f32> {
+    fn from(vec: glam::Vec3) -> Self {
+        Rgb::new(vec.x, vec.y, vec.z)
+    }
+}
+
+impl From<Rgba<f32>> for glam::Vec4 {
+    fn from(color: Rgba<f32>) -> Self {
+        glam::Vec4::new(color.red(), color.green(), color.blue(), color.alpha())
+    }
+}
+
+impl From<glam::Vec4> for Rgba<f32> {
+    fn from(vec: glam::Vec4) -> Self {
+        Rgba::new(Rgb::new(vec.x, vec.y, vec.z), vec.w)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_glam_vec3() {
+        let color = Rgb::new(0.25f32, 0.5, 0.75);
+        let vec: glam::Vec3 = color.into();
+        assert_eq!(vec, glam::Vec3::new(0.25, 0.5, 0.75));
+        assert_eq!(Rgb::from(vec), color);
+    }
+
+    #[test]
+    fn test_glam_vec4() {
+        let color = Rgba::new(Rgb::new(0.1f32, 0.2, 0.3), 0.4);
+        let vec: glam::Vec4 = color.into();
+        assert_eq!(vec, glam::Vec4::new(0.1, 0.2, 0.3, 0.4));
+        assert_eq!(Rgba::from(vec), color);
+    }
+}
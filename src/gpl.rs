@@ -0,0 +1,184 @@
+//! Reading and writing GIMP (`.gpl`) palettes
+//!
+//! This module is only available with the `gpl` feature enabled.
+//!
+//! A GPL file is a plain text file used by GIMP, Inkscape, Krita and other open source tools. It
+//! starts with a `GIMP Palette` line, optionally followed by `Name:` and `Columns:` headers. Each
+//! remaining line holds the red, green and blue values of one color in $`[0, 255]`$, separated by
+//! whitespace and followed by an optional name. Lines starting with `#` are comments.
+//!
+//! ```text
+//! GIMP Palette
+//! Name: Traffic lights
+//! #
+//! 255   0   0 Red
+//! 255 191   0 Amber
+//!   0 255   0 Green
+//! ```
+
+use crate::rgb::Rgb;
+use std::io;
+use std::io::{BufRead, BufReader, Read, Write};
+
+const HEADER: &str = "GIMP Palette";
+
+/// Read the colors from a GPL palette
+///
+/// Returns each color's name along with its color, in the order they appear. Colors without a
+/// name are given an empty one. The palette's `Name:` and `Columns:` headers and all comments are
+/// skipped.
+///
+/// Errors of kind `InvalidData` are returned if the `GIMP Palette` header is missing, or a color
+/// line does not start with three values in $`[0, 255]`$.
+pub fn read_gpl<R: Read>(reader: R) -> io::Result<Vec<(String, Rgb<u8>)>> {
+    let mut lines = BufReader::new(reader).lines();
+    let header = lines.next().transpose()?;
+    if header.as_ref().map(|line| line.trim()) != Some(HEADER) {
+        return Err(invalid_data("missing GIMP Palette header"));
+    }
+
+    let mut colors = Vec::new();
+    for line in lines {
+        let line = line?;
+        let line = line.trim();
+        if line.is_empty()
+            || line.starts_with('#')
+            || line.starts_with("Name:")
+            || line.starts_with("Columns:")
+        {
+            continue;
+        }
+        colors.push(parse_color_line(line)?);
+    }
+
+    Ok(colors)
+}
+
+/// Write a palette as a GPL file
+///
+/// The palette is written with a `Name:` header holding `name`, followed by one line per color.
+/// The values are right aligned in columns as GIMP itself writes them, and separated from the
+/// color's name by a tab.
+///
+/// An error of kind `InvalidInput` is returned if `name` or any color name contains a line break.
+pub fn write_gpl<W: Write>(
+    mut writer: W,
+    name: &str,
+    colors: &[(String, Rgb<u8>)],
+) -> io::Result<()> {
+    check_name(name)?;
+    writeln!(writer, "{}", HEADER)?;
+    writeln!(writer, "Name: {}", name)?;
+    writeln!(writer, "#")?;
+    for (color_name, color) in colors {
+        check_name(color_name)?;
+        writeln!(
+            writer,
+            "{:3} {:3} {:3}\t{}",
+            color.red(),
+            color.green(),
+            color.blue(),
+            color_name
+        )?;
+    }
+
+    Ok(())
+}
+
+fn parse_color_line(line: &str) -> io::Result<(String, Rgb<u8>)> {
+    let mut rest = line;
+    let mut channels = [0u8; 3];
+    for channel in channels.iter_mut() {
+        let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+        *channel = rest[..end]
+            .parse()
+            .map_err(|_| invalid_data("invalid color value"))?;
+        rest = rest[end..].trim_start();
+    }
+
+    Ok((
+        rest.to_string(),
+        Rgb::new(channels[0], channels[1], channels[2]),
+    ))
+}
+
+fn check_name(name: &str) -> io::Result<()> {
+    if name.contains(&['\n', '\r'][..]) {
+        return Err(invalid_input("name contains a line break"));
+    }
+    Ok(())
+}
+
+fn invalid_data(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+fn invalid_input(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, msg)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let palette = vec![
+            ("Red".to_string(), Rgb::new(255, 0, 0)),
+            ("Sky blue".to_string(), Rgb::new(135, 206, 235)),
+            ("Gris clair ☁".to_string(), Rgb::new(204, 204, 204)),
+            ("".to_string(), Rgb::new(7, 80, 9)),
+        ];
+        let mut buf = Vec::new();
+        write_gpl(&mut buf, "Test", &palette).unwrap();
+        let text = String::from_utf8(buf.clone()).unwrap();
+        assert!(text.starts_with("GIMP Palette\nName: Test\n"));
+        assert!(text.contains("\n135 206 235\tSky blue\n"));
+        assert!(text.contains("\n  7  80   9\t\n"));
+
+        assert_eq!(read_gpl(buf.as_slice()).unwrap(), palette);
+
+        let mut empty = Vec::new();
+        write_gpl(&mut empty, "Empty", &[]).unwrap();
+        assert_eq!(read_gpl(empty.as_slice()).unwrap(), vec![]);
+    }
+
+    #[test]
+    fn test_read() {
+        let file = "GIMP Palette\r\n\
+                    Name: Traffic lights\r\n\
+                    Columns: 3\r\n\
+                    # A comment\r\n\
+                    \r\n\
+                    255   0   0\tRed\r\n\
+                    255 191 0 Deep   amber\r\n\
+                    \t0 255 0\r\n";
+        let colors = read_gpl(file.as_bytes()).unwrap();
+        assert_eq!(
+            colors,
+            vec![
+                ("Red".to_string(), Rgb::new(255, 0, 0)),
+                ("Deep   amber".to_string(), Rgb::new(255, 191, 0)),
+                ("".to_string(), Rgb::new(0, 255, 0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        let err = read_gpl(&b"Name: No header\n0 0 0 Black\n"[..]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        let err = read_gpl(&b""[..]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        let err = read_gpl(&b"GIMP Palette\n256 0 0 Too red\n"[..]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        let err = read_gpl(&b"GIMP Palette\n10 20\n"[..]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+        let colors = [("Two\nlines".to_string(), Rgb::new(0, 0, 0))];
+        let err = write_gpl(Vec::new(), "Palette", &colors).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        let err = write_gpl(Vec::new(), "Bad\rname", &[]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
+}
@@ -5,9 +5,11 @@ use crate::channel::{
     PosNormalChannelScalar,
 };
 use crate::color;
-use crate::color::{Bounded, Color, FromTuple, Invert, Lerp, PolarColor};
+use crate::color::{Bounded, Color, FromTuple, Invert, Lerp, Lightness, PolarColor};
 use crate::convert::{FromColor, FromHsi, GetHue};
 use crate::encoding::EncodableColor;
+use crate::hsl::Hsl;
+use crate::hsv::Hsv;
 use crate::rgb::Rgb;
 use crate::tags::HsiTag;
 use angle;
@@ -61,6 +63,9 @@ where
     A: AngularChannelScalar + Angle<Scalar = T>,
 {
     /// Construct an `Hsi` instance from hue, saturation and intensity
+    ///
+    /// The hue is stored as given, so `new` accepts angles outside of a single turn. Use
+    /// [`normalize_hue`](#method.normalize_hue) to wrap it eagerly.
     pub const fn new(hue: A, saturation: T, intensity: T) -> Self {
         Hsi {
             hue: AngularChannel::new(hue),
@@ -69,6 +74,20 @@ where
         }
     }
 
+    impl_color_checked_new!(
+        fn new_checked(hue: A, saturation: T, intensity: T) {
+            crate::channel::debug_check_angle(&hue);
+            crate::channel::debug_check_bounded::<PosNormalBoundedChannel<T>>(&saturation);
+            crate::channel::debug_check_bounded::<PosNormalBoundedChannel<T>>(&intensity);
+        }
+    );
+
+    impl_color_from_hue_units_angular!(Hsi {
+        hue,
+        saturation,
+        intensity
+    });
+
     impl_color_color_cast_angular!(
         Hsi {
             hue,
@@ -104,14 +123,17 @@ where
     }
     /// Set the hue channel value
     pub fn set_hue(&mut self, val: A) {
+        crate::channel::debug_check_angle(&val);
         self.hue.0 = val;
     }
     /// Set the saturation channel value
     pub fn set_saturation(&mut self, val: T) {
+        crate::channel::debug_check_bounded::<PosNormalBoundedChannel<T>>(&val);
         self.saturation.0 = val;
     }
     /// Set the intensity channel value
     pub fn set_intensity(&mut self, val: T) {
+        crate::channel::debug_check_bounded::<PosNormalBoundedChannel<T>>(&val);
         self.intensity.0 = val;
     }
     /// Returns whether the `Hsi` instance would be equivalent in `eHsi`
@@ -126,6 +148,15 @@ where
 
         self.intensity() <= i_limit
     }
+
+    impl_color_complement_angular!(Hsi);
+    impl_color_normalize_hue_angular!(Hsi);
+    impl_color_to_feature_vector_angular!(
+        /// Returns the color as a feature vector `[cos(hue), sin(hue), saturation, intensity]`
+        ///
+        /// The saturation and intensity are converted to `f64` in $`[0, 1]`$.
+        {saturation, intensity}
+    );
 }
 
 impl<T, A> PolarColor for Hsi<T, A>
@@ -153,6 +184,22 @@ where
     }
 }
 
+impl<T, A> Lightness for Hsi<T, A>
+where
+    T: PosNormalChannelScalar,
+    A: AngularChannelScalar,
+{
+    type LightnessFormat = T;
+
+    fn lightness(&self) -> T {
+        self.intensity.0.clone()
+    }
+    fn with_lightness(mut self, lightness: T) -> Self {
+        self.intensity.0 = lightness;
+        self
+    }
+}
+
 impl<T, A> FromTuple for Hsi<T, A>
 where
     T: PosNormalChannelScalar + num_traits::Float,
@@ -328,6 +375,72 @@ where
     }
 }
 
+impl<T, A> FromColor<Hsv<T, A>> for Hsi<T, A>
+where
+    T: PosNormalChannelScalar + num_traits::Float,
+    A: AngularChannelScalar + Angle<Scalar = T> + FromAngle<Rad<T>> + fmt::Display,
+{
+    /// Convert from `Hsv`, by way of the `Rgb` value both describe
+    ///
+    /// Neither the brightness axis nor the hue carry over directly: $`I`$ is the mean of all three
+    /// `Rgb` channels, while $`V`$ is their maximum, and `Hsi` measures hue as a true angle
+    /// around the gray axis rather than around the `Hsv` hexagon. The two hues only agree at the
+    /// primary and secondary colors. The full `Rgb` color is therefore reconstructed.
+    fn from_color(from: &Hsv<T, A>) -> Self {
+        Hsi::from_color(&Rgb::from_color(from))
+    }
+}
+
+impl<T, A> FromColor<Hsl<T, A>> for Hsi<T, A>
+where
+    T: PosNormalChannelScalar + num_traits::Float,
+    A: AngularChannelScalar + Angle<Scalar = T> + FromAngle<Rad<T>> + fmt::Display,
+{
+    /// Convert from `Hsl`, by way of the `Rgb` value both describe
+    ///
+    /// As with the conversion from `Hsv`, $`I`$ and $`L`$ differ and the hues only agree at the
+    /// primary and secondary colors, so the full `Rgb` color is reconstructed.
+    fn from_color(from: &Hsl<T, A>) -> Self {
+        Hsi::from_color(&Rgb::from_color(from))
+    }
+}
+
+impl<T, A> FromHsi<Hsi<T, A>> for Hsv<T, A>
+where
+    T: PosNormalChannelScalar + num_traits::Float,
+    A: AngularChannelScalar
+        + Angle<Scalar = T>
+        + IntoAngle<Rad<T>, OutputScalar = T>
+        + FromAngle<angle::Turns<T>>,
+{
+    /// Convert to `Hsv`, by way of `Rgb`
+    ///
+    /// `out_of_gamut_mode` is used when converting `from` to `Rgb`, as not every `Hsi` value has
+    /// an `Hsv` equivalent. Any channels still outside of $`[0, 1]`$ afterwards, as with
+    /// `HsiOutOfGamutMode::Preserve`, are clamped.
+    fn from_hsi(from: &Hsi<T, A>, out_of_gamut_mode: HsiOutOfGamutMode) -> Self {
+        Hsv::from_color(&Rgb::from_hsi(from, out_of_gamut_mode).normalize())
+    }
+}
+
+impl<T, A> FromHsi<Hsi<T, A>> for Hsl<T, A>
+where
+    T: PosNormalChannelScalar + num_traits::Float,
+    A: AngularChannelScalar
+        + Angle<Scalar = T>
+        + IntoAngle<Rad<T>, OutputScalar = T>
+        + FromAngle<angle::Turns<T>>,
+{
+    /// Convert to `Hsl`, by way of `Rgb`
+    ///
+    /// `out_of_gamut_mode` is used when converting `from` to `Rgb`, as not every `Hsi` value has
+    /// an `Hsl` equivalent. Any channels still outside of $`[0, 1]`$ afterwards, as with
+    /// `HsiOutOfGamutMode::Preserve`, are clamped.
+    fn from_hsi(from: &Hsi<T, A>, out_of_gamut_mode: HsiOutOfGamutMode) -> Self {
+        Hsl::from_color(&Rgb::from_hsi(from, out_of_gamut_mode).normalize())
+    }
+}
+
 impl<T, A> Hsi<T, A>
 where
     T: PosNormalChannelScalar + num_traits::Float,
@@ -490,6 +603,89 @@ mod test {
         assert_relative_eq!(rgb3_4, Rgb::new(0.0, 0.0, 0.9), epsilon = 1e-6);
     }
 
+    #[test]
+    fn test_hsv_hsl() {
+        use crate::convert::FromHsi;
+        // Worked out from the Rgb channels, with the Hsi hue given by
+        // atan2(sqrt(3) (G - B), 2R - G - B)
+        let test_data = [
+            // Rgb(1, 0, 0)
+            (
+                Hsv::new(Deg(0.0), 1.0, 1.0),
+                Hsl::new(Deg(0.0), 1.0, 0.5),
+                Hsi::new(Deg(0.0), 1.0, 1.0 / 3.0),
+            ),
+            // Rgb(0.75, 0.75, 0)
+            (
+                Hsv::new(Deg(60.0), 1.0, 0.75),
+                Hsl::new(Deg(60.0), 1.0, 0.375),
+                Hsi::new(Deg(60.0), 1.0, 0.5),
+            ),
+            // Rgb(0.5, 1, 0.5)
+            (
+                Hsv::new(Deg(120.0), 0.5, 1.0),
+                Hsl::new(Deg(120.0), 1.0, 0.75),
+                Hsi::new(Deg(120.0), 0.25, 2.0 / 3.0),
+            ),
+            // Rgb(1, 0.5, 0)
+            (
+                Hsv::new(Deg(30.0), 1.0, 1.0),
+                Hsl::new(Deg(30.0), 1.0, 0.5),
+                Hsi::new(Deg(30.0), 1.0, 0.5),
+            ),
+            // Rgb(0.2, 0.4, 0.9)
+            (
+                Hsv::new(Deg(1560.0 / 7.0), 7.0 / 9.0, 0.9),
+                Hsl::new(Deg(1560.0 / 7.0), 7.0 / 9.0, 0.55),
+                Hsi::new(Deg(223.8978862480), 0.6, 0.5),
+            ),
+            // Rgb(0.9, 0.3, 0.6)
+            (
+                Hsv::new(Deg(330.0), 2.0 / 3.0, 0.9),
+                Hsl::new(Deg(330.0), 0.75, 0.6),
+                Hsi::new(Deg(330.0), 0.5, 0.6),
+            ),
+            // Rgb(0.25, 0.6, 0.45)
+            (
+                Hsv::new(Deg(1080.0 / 7.0), 7.0 / 12.0, 0.6),
+                Hsl::new(Deg(1080.0 / 7.0), 7.0 / 17.0, 0.425),
+                Hsi::new(Deg(154.7150039539), 11.0 / 26.0, 13.0 / 30.0),
+            ),
+            // Rgb(0.5, 0.5, 0.5)
+            (
+                Hsv::new(Deg(0.0), 0.0, 0.5),
+                Hsl::new(Deg(0.0), 0.0, 0.5),
+                Hsi::new(Deg(0.0), 0.0, 0.5),
+            ),
+        ];
+
+        for (hsv, hsl, hsi) in test_data.iter() {
+            assert_relative_eq!(Hsi::from_color(hsv), *hsi, epsilon = 1e-6);
+            assert_relative_eq!(Hsi::from_color(hsl), *hsi, epsilon = 1e-6);
+            assert_relative_eq!(
+                Hsv::from_hsi(hsi, HsiOutOfGamutMode::Clip),
+                *hsv,
+                epsilon = 1e-6
+            );
+            assert_relative_eq!(
+                Hsl::from_hsi(hsi, HsiOutOfGamutMode::Clip),
+                *hsl,
+                epsilon = 1e-6
+            );
+        }
+
+        // The intensity is the mean of the channels, not the value or lightness
+        let orange = Hsv::new(Deg(45.0f64), 1.0, 1.0);
+        let hsi = Hsi::from_color(&orange);
+        assert_relative_eq!(hsi.intensity(), 1.75 / 3.0, epsilon = 1e-12);
+        assert_relative_eq!(hsi.saturation(), 1.0, epsilon = 1e-12);
+        // and the hue agrees only at the primary and secondary colors
+        assert_relative_eq!(hsi.hue(), Deg(46.102), epsilon = 1e-3);
+        let cyan = Hsi::from_color(&Hsl::new(Deg(180.0), 1.0, 0.5));
+        assert_relative_eq!(cyan.hue(), Deg(180.0), epsilon = 1e-9);
+        assert_relative_eq!(cyan.intensity(), 2.0 / 3.0, epsilon = 1e-12);
+    }
+
     #[test]
     fn test_color_cast() {
         let c1 = Hsi::new(Deg(120.0), 0.53, 0.94);
@@ -518,4 +714,11 @@ mod test {
         let deserialize: Hsi<f32, Deg<f32>> = serde_json::from_str(&serialized).unwrap();
         assert_eq!(deserialize, c1);
     }
+
+    #[test]
+    fn test_lightness() {
+        let c1 = Hsi::new(Deg(45.0), 0.7, 0.4);
+        assert_eq!(c1.lightness(), 0.4);
+        assert_eq!(c1.with_lightness(0.1), Hsi::new(Deg(45.0), 0.7, 0.1));
+    }
 }
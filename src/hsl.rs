@@ -5,7 +5,8 @@ use crate::channel::{
     PosNormalChannelScalar,
 };
 use crate::color;
-use crate::color::{Color, FromTuple};
+use crate::color::{Bounded, Color, FromTuple, Lightness};
+use crate::color_space::{clamp_saturation_to_gamut, ColorSpace};
 use crate::convert;
 use crate::convert::GetChroma;
 use crate::encoding::EncodableColor;
@@ -53,6 +54,9 @@ where
     A: AngularChannelScalar,
 {
     /// Construct an `Hsl` instance from hue, saturation and lightness
+    ///
+    /// The hue is stored as given, so `new` accepts angles outside of a single turn. Use
+    /// [`normalize_hue`](#method.normalize_hue) to wrap it eagerly.
     pub const fn new(hue: A, saturation: T, lightness: T) -> Self {
         Hsl {
             hue: AngularChannel::new(hue),
@@ -61,6 +65,20 @@ where
         }
     }
 
+    impl_color_checked_new!(
+        fn new_checked(hue: A, saturation: T, lightness: T) {
+            crate::channel::debug_check_angle(&hue);
+            crate::channel::debug_check_bounded::<PosNormalBoundedChannel<T>>(&saturation);
+            crate::channel::debug_check_bounded::<PosNormalBoundedChannel<T>>(&lightness);
+        }
+    );
+
+    impl_color_from_hue_units_angular!(Hsl {
+        hue,
+        saturation,
+        lightness
+    });
+
     impl_color_color_cast_angular!(
         Hsl {
             hue,
@@ -96,16 +114,113 @@ where
     }
     /// Set the hue channel value
     pub fn set_hue(&mut self, val: A) {
+        crate::channel::debug_check_angle(&val);
         self.hue.0 = val;
     }
     /// Set the saturation channel value
     pub fn set_saturation(&mut self, val: T) {
+        crate::channel::debug_check_bounded::<PosNormalBoundedChannel<T>>(&val);
         self.saturation.0 = val;
     }
     /// Set the lightness channel value
     pub fn set_lightness(&mut self, val: T) {
+        crate::channel::debug_check_bounded::<PosNormalBoundedChannel<T>>(&val);
         self.lightness.0 = val;
     }
+
+    impl_color_complement_angular!(Hsl);
+    impl_color_normalize_hue_angular!(Hsl);
+    impl_color_to_feature_vector_angular!(
+        /// Returns the color as a feature vector `[cos(hue), sin(hue), saturation, lightness]`
+        ///
+        /// The saturation and lightness are converted to `f64` in $`[0, 1]`$.
+        {saturation, lightness}
+    );
+}
+
+impl<T> Hsl<T, Deg<T>>
+where
+    T: PosNormalChannelScalar + num_traits::Float,
+    Deg<T>: AngularChannelScalar,
+{
+    /// Construct an Hsl instance with the hue given in degrees
+    ///
+    /// This is a shorthand for `Hsl::new(Deg(hue), saturation, lightness)`, for when the hue is a
+    /// plain number.
+    pub fn from_degrees(hue: T, saturation: T, lightness: T) -> Self {
+        Hsl::new(Deg(hue), saturation, lightness)
+    }
+}
+
+impl<T, A> Hsl<T, A>
+where
+    T: PosNormalChannelScalar + num_traits::Float,
+    A: AngularChannelScalar,
+{
+    /// Move the color along a single pastel to vivid axis, keeping the hue
+    ///
+    /// `v` is clamped to $`[0, 1]`$ and becomes the saturation. The lightness follows the curve
+    ///
+    /// ```math
+    /// L_v = 0.9 - 0.4v
+    /// ```
+    ///
+    /// so `v = 0` gives a pale, nearly white pastel at $`L = 0.9`$ and `v = 1` gives a fully
+    /// saturated color at the mid lightness $`L = 0.5`$. A color off the curve keeps its place
+    /// relative to it: the lightness is measured as a fraction of the way from $`L_v`$ at the
+    /// current saturation towards white, or towards black if it is darker, and the same fraction
+    /// is applied at the new vividness. The saturation of the result reads back as `v`, and
+    /// setting the vividness back to the original saturation restores the original color.
+    pub fn set_vividness(self, v: T) -> Self {
+        let base: T = num_traits::cast(0.9).unwrap();
+        let slope: T = num_traits::cast(0.4).unwrap();
+        let curve = |v: T| base - slope * v;
+
+        let (saturation, lightness) = (self.saturation(), self.lightness());
+        let from = curve(saturation);
+        let offset = if lightness >= from {
+            (lightness - from) / (T::one() - from)
+        } else {
+            (lightness - from) / from
+        };
+
+        let v = v.max(T::zero()).min(T::one());
+        let to = curve(v);
+        let lightness = if offset >= T::zero() {
+            to + offset * (T::one() - to)
+        } else {
+            to + offset * to
+        };
+        Hsl::new(self.hue(), v, lightness)
+    }
+}
+
+impl<T, A> Hsl<T, A>
+where
+    T: PosNormalChannelScalar + ChannelFormatCast<f64> + num_traits::Float,
+    f64: ChannelFormatCast<T>,
+    A: AngularChannelScalar,
+{
+    /// Pull the color into the gamut of `space`, so it can be displayed after converting to `Rgb`
+    ///
+    /// An `Hsl` with an out of range saturation or lightness converts to an `Rgb` value outside of
+    /// the unit cube, which then clips when quantized, e.g. to `Rgb<u8>`. The converted `Rgb` is
+    /// taken to be encoded with the encoding of `space`. This clamps the lightness and reduces the
+    /// saturation until the color decodes to a linear value inside the space's gamut, so it
+    /// round-trips through `Rgb<u8>` without clipping. The hue is left untouched, and colors that
+    /// are already displayable are returned unchanged.
+    pub fn clamp_to_displayable<S>(self, space: &S) -> Self
+    where
+        S: ColorSpace<T>,
+    {
+        let lightness = self.lightness.normalize().0;
+        let saturation = self.saturation();
+        let hue = self.hue.0;
+        let saturation = clamp_saturation_to_gamut(saturation, space, |s| {
+            convert::FromColor::from_color(&Hsl::new(hue.clone(), s, lightness))
+        });
+        Hsl::new(hue, saturation, lightness)
+    }
 }
 
 impl<T, A> Color for Hsl<T, A>
@@ -124,6 +239,22 @@ where
     }
 }
 
+impl<T, A> Lightness for Hsl<T, A>
+where
+    T: PosNormalChannelScalar,
+    A: AngularChannelScalar,
+{
+    type LightnessFormat = T;
+
+    fn lightness(&self) -> T {
+        self.lightness()
+    }
+    fn with_lightness(mut self, lightness: T) -> Self {
+        self.set_lightness(lightness);
+        self
+    }
+}
+
 impl<T, A> FromTuple for Hsl<T, A>
 where
     T: PosNormalChannelScalar,
@@ -165,6 +296,14 @@ where
     impl_color_lerp_angular!(Hsl<T> {hue, saturation, lightness});
 }
 
+impl<T, A> Hsl<T, A>
+where
+    T: PosNormalChannelScalar + color::Lerp,
+    A: AngularChannelScalar + color::Lerp,
+{
+    impl_color_lerp_retain_hue!(Hsl);
+}
+
 impl<T, A> color::Bounded for Hsl<T, A>
 where
     T: PosNormalChannelScalar,
@@ -331,6 +470,58 @@ mod test {
         assert_eq!(c2.lightness(), 0.90);
     }
 
+    #[test]
+    fn test_from_degrees() {
+        assert_eq!(
+            Hsl::from_degrees(120.0, 0.5, 0.8),
+            Hsl::new(Deg(120.0), 0.5, 0.8)
+        );
+        assert_eq!(
+            Hsl::from_degrees(300.0f32, 1.0, 0.25),
+            Hsl::new(Deg(300.0f32), 1.0, 0.25)
+        );
+    }
+
+    #[test]
+    fn test_set_vividness() {
+        let c = Hsl::new(Deg(200.0), 0.3, 0.78);
+
+        let pastel = c.set_vividness(0.05);
+        assert_relative_eq!(pastel.hue(), Deg(200.0));
+        assert!(pastel.saturation() < 0.1);
+        assert!(pastel.lightness() > 0.85);
+        let rgb = Rgb::from_color(&pastel);
+        assert!(rgb.red() > 0.8 && rgb.green() > 0.8 && rgb.blue() > 0.8);
+
+        let vivid = c.set_vividness(0.95);
+        assert_relative_eq!(vivid.hue(), Deg(200.0));
+        assert!(vivid.saturation() > 0.9);
+        assert_relative_eq!(vivid.lightness(), 0.52, epsilon = 1e-12);
+        assert!(vivid.get_chroma() > 0.9);
+
+        assert_relative_eq!(c.set_vividness(1.0), Hsl::new(Deg(200.0), 1.0, 0.5));
+        assert_relative_eq!(c.set_vividness(-1.0), Hsl::new(Deg(200.0), 0.0, 0.9));
+        assert_relative_eq!(pastel.set_vividness(0.95), vivid, epsilon = 1e-12);
+
+        // Colors off the curve keep their lightness relative to it
+        let dark = Hsl::new(Deg(40.0), 0.3, 0.3);
+        let light = Hsl::new(Deg(300.0), 0.6, 0.95);
+        for &color in [dark, light, Hsl::new(Deg(0.0), 0.0, 0.0)].iter() {
+            for &v in [0.0, 0.2, 0.7, 1.0].iter() {
+                let moved = color.set_vividness(v);
+                assert_relative_eq!(moved.saturation(), v);
+                assert!(moved.lightness() >= 0.0 && moved.lightness() <= 1.0);
+                assert_relative_eq!(
+                    moved.set_vividness(color.saturation()),
+                    color,
+                    epsilon = 1e-12
+                );
+            }
+        }
+        assert!(dark.set_vividness(0.9).lightness() < 0.5);
+        assert!(light.set_vividness(0.1).lightness() > 0.9);
+    }
+
     #[test]
     fn test_chroma() {
         let test_data = test::build_hs_test_data();
@@ -361,6 +552,17 @@ mod test {
         assert_relative_eq!(c1.lerp(&c2, 0.5), Hsl::new(Turns(0.0), 0.5, 0.55));
     }
 
+    #[test]
+    fn test_lerp_retain_hue() {
+        let green = Hsl::new(Deg(120.0), 0.6, 0.4);
+        let gray = Hsl::from_color(&Rgb::new(0.8, 0.8, 0.8));
+        let mid = green.lerp_retain_hue(&gray, 0.5);
+        assert_relative_eq!(mid.hue(), Deg(120.0));
+        assert_relative_eq!(mid.saturation(), 0.3);
+        assert_relative_eq!(mid.lightness(), 0.6, epsilon = 1e-12);
+        assert_relative_eq!(gray.lerp_retain_hue(&green, 0.5).hue(), Deg(120.0));
+    }
+
     #[test]
     fn test_hsl_to_rgb() {
         let test_data = test::build_hs_test_data();
@@ -373,6 +575,32 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_clamp_to_displayable() {
+        use crate::color_space::named::SRgb;
+        let c1 = Hsl::new(Deg(200.0), 1.4, 0.6);
+        let rgb = Rgb::from_color(&c1);
+        assert!(!rgb.is_normalized());
+        let space = SRgb::new();
+        let clamped = c1.clamp_to_displayable(&space);
+        assert_eq!(clamped, Hsl::new(Deg(200.0), 1.0, 0.6));
+        let rgb = Rgb::from_color(&clamped);
+        assert!(rgb.is_normalized());
+        let round_trip: Rgb<f64> = rgb.color_cast::<u8>().color_cast();
+        assert_relative_eq!(round_trip, rgb, epsilon = 1.0 / 255.0);
+        assert_relative_eq!(Hsl::from_color(&round_trip), clamped, epsilon = 1e-2);
+
+        let c2 = Hsl::new(Deg(330.0), 0.7, 0.35);
+        assert!(Rgb::from_color(&c2).is_normalized());
+        assert_eq!(c2.clamp_to_displayable(&space), c2);
+
+        let c3 = Hsl::new(Deg(20.0), -0.3, 1.2);
+        assert_eq!(
+            c3.clamp_to_displayable(&space),
+            Hsl::new(Deg(20.0), 0.0, 1.0)
+        );
+    }
+
     #[test]
     fn test_color_cast() {
         let c1 = Hsl::new(Deg(90.0), 0.23, 0.45);
@@ -397,4 +625,11 @@ mod test {
         let deserialized: Hsl<f32, Deg<f32>> = serde_json::from_str(&serialized).unwrap();
         assert_eq!(deserialized, c1);
     }
+
+    #[test]
+    fn test_lightness() {
+        let c1 = Hsl::new(Deg(200.0), 0.3, 0.6);
+        assert_eq!(Lightness::lightness(&c1), 0.6);
+        assert_eq!(c1.with_lightness(0.9), Hsl::new(Deg(200.0), 0.3, 0.9));
+    }
 }
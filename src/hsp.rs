@@ -0,0 +1,395 @@
+//! The HSP device-dependent polar color model
+
+use crate::channel::{
+    AngularChannel, AngularChannelScalar, ChannelCast, ChannelFormatCast, PosNormalBoundedChannel,
+    PosNormalChannelScalar,
+};
+use crate::color;
+use crate::color::{Color, FromTuple};
+use crate::convert;
+use crate::encoding::EncodableColor;
+use crate::hsv::Hsv;
+use crate::rgb::Rgb;
+use crate::tags::HspTag;
+use angle::{Angle, Deg, FromAngle, IntoAngle};
+use std::fmt;
+
+/// The HSP device-dependent polar color model
+///
+/// HSP is defined by a hue (base color), saturation (color richness) and perceived brightness.
+/// It was introduced by Darel Rex Finley as a replacement for HSV and HSL where the brightness
+/// channel tracks how light a color actually looks. The perceived brightness is computed as
+///
+/// ```math
+/// P = \sqrt{0.299R^2 + 0.587G^2 + 0.114B^2}
+/// ```
+///
+/// Hue and saturation are identical to those in [`Hsv`](struct.Hsv.html), so changing the hue of
+/// an `Hsp` color keeps its apparent brightness. This is useful for color pickers or
+/// palettes where several hues should read as equally bright.
+///
+/// Not every combination of channels is representable in Rgb: fully saturated blues can never
+/// reach a brightness above $`\sqrt{0.114}`$. Converting such colors to `Rgb` will produce
+/// channels larger than one.
+///
+/// Hsp takes two type parameters: the cartesian channel scalar, and an angular channel scalar.
+///
+/// Hsp is in the same color space and encoding as the parent RGB space, it is merely a geometric
+/// transformation and distortion.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Hsp<T, A = Deg<T>> {
+    hue: AngularChannel<A>,
+    saturation: PosNormalBoundedChannel<T>,
+    brightness: PosNormalBoundedChannel<T>,
+}
+
+impl<T, A> Hsp<T, A>
+where
+    T: PosNormalChannelScalar,
+    A: AngularChannelScalar,
+{
+    /// Construct an `Hsp` instance from hue, saturation and perceived brightness
+    ///
+    /// The hue is stored as given, so `new` accepts angles outside of a single turn. Use
+    /// [`normalize_hue`](#method.normalize_hue) to wrap it eagerly.
+    pub const fn new(hue: A, saturation: T, brightness: T) -> Self {
+        Hsp {
+            hue: AngularChannel::new(hue),
+            saturation: PosNormalBoundedChannel::new_const(saturation),
+            brightness: PosNormalBoundedChannel::new_const(brightness),
+        }
+    }
+
+    impl_color_checked_new!(
+        fn new_checked(hue: A, saturation: T, brightness: T) {
+            crate::channel::debug_check_angle(&hue);
+            crate::channel::debug_check_bounded::<PosNormalBoundedChannel<T>>(&saturation);
+            crate::channel::debug_check_bounded::<PosNormalBoundedChannel<T>>(&brightness);
+        }
+    );
+
+    impl_color_from_hue_units_angular!(Hsp {
+        hue,
+        saturation,
+        brightness
+    });
+
+    impl_color_color_cast_angular!(
+        Hsp {
+            hue,
+            saturation,
+            brightness
+        },
+        chan_traits = { PosNormalChannelScalar }
+    );
+
+    /// Returns the hue scalar
+    pub fn hue(&self) -> A {
+        self.hue.0.clone()
+    }
+    /// Returns the saturation scalar
+    pub fn saturation(&self) -> T {
+        self.saturation.0.clone()
+    }
+    /// Returns the perceived brightness scalar
+    pub fn brightness(&self) -> T {
+        self.brightness.0.clone()
+    }
+    /// Returns a mutable reference to the hue scalar
+    pub fn hue_mut(&mut self) -> &mut A {
+        &mut self.hue.0
+    }
+    /// Returns a mutable reference to the saturation scalar
+    pub fn saturation_mut(&mut self) -> &mut T {
+        &mut self.saturation.0
+    }
+    /// Returns a mutable reference to the perceived brightness scalar
+    pub fn brightness_mut(&mut self) -> &mut T {
+        &mut self.brightness.0
+    }
+    /// Set the hue channel value
+    pub fn set_hue(&mut self, val: A) {
+        crate::channel::debug_check_angle(&val);
+        self.hue.0 = val;
+    }
+    /// Set the saturation channel value
+    pub fn set_saturation(&mut self, val: T) {
+        crate::channel::debug_check_bounded::<PosNormalBoundedChannel<T>>(&val);
+        self.saturation.0 = val;
+    }
+    /// Set the perceived brightness channel value
+    pub fn set_brightness(&mut self, val: T) {
+        crate::channel::debug_check_bounded::<PosNormalBoundedChannel<T>>(&val);
+        self.brightness.0 = val;
+    }
+
+    impl_color_complement_angular!(Hsp);
+    impl_color_normalize_hue_angular!(Hsp);
+    impl_color_to_feature_vector_angular!(
+        /// Returns the color as a feature vector `[cos(hue), sin(hue), saturation, brightness]`
+        ///
+        /// The saturation and brightness are converted to `f64` in $`[0, 1]`$.
+        {saturation, brightness}
+    );
+}
+
+impl<T, A> Color for Hsp<T, A>
+where
+    T: PosNormalChannelScalar,
+    A: AngularChannelScalar,
+{
+    type Tag = HspTag;
+    type ChannelsTuple = (A, T, T);
+
+    fn num_channels() -> u32 {
+        3
+    }
+    fn to_tuple(self) -> Self::ChannelsTuple {
+        (self.hue.0, self.saturation.0, self.brightness.0)
+    }
+}
+
+impl<T, A> FromTuple for Hsp<T, A>
+where
+    T: PosNormalChannelScalar,
+    A: AngularChannelScalar,
+{
+    fn from_tuple(values: Self::ChannelsTuple) -> Self {
+        Hsp::new(values.0, values.1, values.2)
+    }
+}
+
+impl<T, A> color::PolarColor for Hsp<T, A>
+where
+    T: PosNormalChannelScalar,
+    A: AngularChannelScalar,
+{
+    type Angular = A;
+    type Cartesian = T;
+}
+
+impl<T, A> color::Invert for Hsp<T, A>
+where
+    T: PosNormalChannelScalar,
+    A: AngularChannelScalar,
+{
+    impl_color_invert!(Hsp {
+        hue,
+        saturation,
+        brightness
+    });
+}
+
+impl<T, A> color::Lerp for Hsp<T, A>
+where
+    T: PosNormalChannelScalar + color::Lerp,
+    A: AngularChannelScalar + color::Lerp,
+{
+    type Position = A::Position;
+
+    impl_color_lerp_angular!(Hsp<T> {hue, saturation, brightness});
+}
+
+impl<T, A> Hsp<T, A>
+where
+    T: PosNormalChannelScalar + color::Lerp,
+    A: AngularChannelScalar + color::Lerp,
+{
+    impl_color_lerp_retain_hue!(Hsp);
+}
+
+impl<T, A> color::Bounded for Hsp<T, A>
+where
+    T: PosNormalChannelScalar,
+    A: AngularChannelScalar,
+{
+    impl_color_bounded!(Hsp {
+        hue,
+        saturation,
+        brightness
+    });
+}
+
+impl<T, A> EncodableColor for Hsp<T, A>
+where
+    T: PosNormalChannelScalar + num_traits::Float,
+    A: AngularChannelScalar + Angle<Scalar = T> + FromAngle<angle::Turns<T>>,
+{
+}
+
+#[cfg(feature = "approx")]
+impl<T, A> approx::AbsDiffEq for Hsp<T, A>
+where
+    T: PosNormalChannelScalar + approx::AbsDiffEq<Epsilon = A::Epsilon>,
+    A: AngularChannelScalar + approx::AbsDiffEq,
+    A::Epsilon: Clone + num_traits::Float,
+{
+    impl_abs_diff_eq!({hue, saturation, brightness});
+}
+#[cfg(feature = "approx")]
+impl<T, A> approx::RelativeEq for Hsp<T, A>
+where
+    T: PosNormalChannelScalar + approx::RelativeEq<Epsilon = A::Epsilon>,
+    A: AngularChannelScalar + approx::RelativeEq,
+    A::Epsilon: Clone + num_traits::Float,
+{
+    impl_rel_eq!({hue, saturation, brightness});
+}
+#[cfg(feature = "approx")]
+impl<T, A> approx::UlpsEq for Hsp<T, A>
+where
+    T: PosNormalChannelScalar + approx::UlpsEq<Epsilon = A::Epsilon>,
+    A: AngularChannelScalar + approx::UlpsEq,
+    A::Epsilon: Clone + num_traits::Float,
+{
+    impl_ulps_eq!({hue, saturation, brightness});
+}
+
+impl<T, A> Default for Hsp<T, A>
+where
+    T: PosNormalChannelScalar + num_traits::Zero,
+    A: AngularChannelScalar + num_traits::Zero,
+{
+    impl_color_default!(Hsp {
+        hue: AngularChannel,
+        saturation: PosNormalBoundedChannel,
+        brightness: PosNormalBoundedChannel
+    });
+}
+
+impl<T, A> fmt::Display for Hsp<T, A>
+where
+    T: PosNormalChannelScalar + fmt::Display,
+    A: AngularChannelScalar + fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Hsp({}, {}, {})",
+            self.hue, self.saturation, self.brightness
+        )
+    }
+}
+
+impl<T, A> convert::GetHue for Hsp<T, A>
+where
+    T: PosNormalChannelScalar,
+    A: AngularChannelScalar,
+{
+    impl_color_get_hue_angular!(Hsp);
+}
+
+fn perceived_brightness<T>(rgb: &Rgb<T>) -> T
+where
+    T: PosNormalChannelScalar + num_traits::Float,
+{
+    let (r, g, b) = rgb.clone().to_tuple();
+    (num_traits::cast::<_, T>(0.299).unwrap() * r * r
+        + num_traits::cast::<_, T>(0.587).unwrap() * g * g
+        + num_traits::cast::<_, T>(0.114).unwrap() * b * b)
+        .sqrt()
+}
+
+impl<T, A> convert::FromColor<Rgb<T>> for Hsp<T, A>
+where
+    T: PosNormalChannelScalar + num_traits::Float,
+    A: AngularChannelScalar + FromAngle<angle::Turns<T>>,
+{
+    fn from_color(from: &Rgb<T>) -> Self {
+        let hsv: Hsv<T, A> = convert::FromColor::from_color(from);
+        Hsp::new(hsv.hue(), hsv.saturation(), perceived_brightness(from))
+    }
+}
+
+impl<T, A> convert::FromColor<Hsp<T, A>> for Rgb<T>
+where
+    T: PosNormalChannelScalar + num_traits::Float,
+    A: AngularChannelScalar,
+{
+    fn from_color(from: &Hsp<T, A>) -> Self {
+        // Perceived brightness scales linearly with the channels, so find the color with the
+        // right hue and saturation at full value and rescale it to the target brightness.
+        let one: T = num_traits::cast(1.0).unwrap();
+        let full = Rgb::from_color(&Hsv::new(from.hue(), from.saturation(), one));
+        let scale = from.brightness() / perceived_brightness(&full);
+        let (r, g, b) = full.to_tuple();
+        Rgb::new(r * scale, g * scale, b * scale)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::color::*;
+    use crate::convert::*;
+    use crate::rgb::Rgb;
+    use angle::*;
+    use approx::*;
+
+    use crate::test;
+
+    #[test]
+    fn test_construct() {
+        let c1 = Hsp::new(Deg(90.0), 0.5, 0.25);
+        assert_eq!(c1.hue(), Deg(90.0));
+        assert_eq!(c1.saturation(), 0.5);
+        assert_eq!(c1.brightness(), 0.25);
+        assert_eq!(c1.to_tuple(), (Deg(90.0), 0.5, 0.25));
+        assert_eq!(Hsp::from_tuple(c1.to_tuple()), c1);
+    }
+
+    #[test]
+    fn test_lerp() {
+        let c1 = Hsp::new(Turns(0.2), 0.25, 0.80);
+        let c2 = Hsp::new(Turns(0.8), 0.75, 0.30);
+        assert_relative_eq!(c1.lerp(&c2, 0.0), c1);
+        assert_relative_eq!(c1.lerp(&c2, 1.0), c2);
+        assert_relative_eq!(c1.lerp(&c2, 0.5), Hsp::new(Turns(0.0), 0.5, 0.55));
+    }
+
+    #[test]
+    fn test_from_rgb() {
+        let c1: Hsp<f64> = Hsp::from_color(&Rgb::new(1.0, 1.0, 1.0));
+        assert_relative_eq!(c1, Hsp::new(Deg(0.0), 0.0, 1.0), epsilon = 1e-6);
+        let c2: Hsp<f64> = Hsp::from_color(&Rgb::new(0.0, 0.0, 1.0));
+        assert_relative_eq!(
+            c2,
+            Hsp::new(Deg(240.0), 1.0, 0.114f64.sqrt()),
+            epsilon = 1e-6
+        );
+        let c3: Hsp<f64> = Hsp::from_color(&Rgb::new(0.5, 0.5, 0.5));
+        assert_relative_eq!(c3, Hsp::new(Deg(0.0), 0.0, 0.5), epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_hsp_to_rgb() {
+        let test_data = test::build_hs_test_data();
+
+        for item in test_data.iter() {
+            let hsp: Hsp<f32> = Hsp::from_color(&item.rgb);
+            assert_relative_eq!(hsp.hue(), item.hsv.hue(), epsilon = 1e-1);
+            assert_relative_eq!(hsp.saturation(), item.hsv.saturation(), epsilon = 1e-3);
+            let rgb = Rgb::from_color(&hsp);
+            assert_relative_eq!(rgb, item.rgb, epsilon = 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_keeps_brightness() {
+        let mut c1 = Hsp::new(Deg(20.0), 0.6, 0.5);
+        for hue in [60.0, 120.0, 200.0, 300.0].iter() {
+            c1.set_hue(Deg(*hue));
+            let rgb = Rgb::from_color(&c1);
+            assert_relative_eq!(Hsp::<f64>::from_color(&rgb), c1, epsilon = 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_color_cast() {
+        let c1 = Hsp::new(Deg(90.0), 0.23, 0.45);
+        assert_relative_eq!(c1.color_cast(), Hsp::new(Turns(0.25f32), 0.23f32, 0.45f32));
+        assert_relative_eq!(c1.color_cast(), c1, epsilon = 1e-7);
+    }
+}
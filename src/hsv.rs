@@ -6,9 +6,11 @@ use crate::channel::{
     PosNormalChannelScalar,
 };
 use crate::color;
-use crate::color::{Bounded, Color, FromTuple, Invert, Lerp, PolarColor};
+use crate::color::{Bounded, Color, FromTuple, Invert, Lerp, Lightness, PolarColor};
+use crate::color_space::{clamp_saturation_to_gamut, ColorSpace};
 use crate::convert;
 use crate::encoding::EncodableColor;
+use crate::parse::ParseColorError;
 use crate::rgb;
 use crate::tags::HsvTag;
 use angle;
@@ -52,6 +54,9 @@ where
     A: AngularChannelScalar,
 {
     /// Construct an Hsv instance from hue, saturation and value
+    ///
+    /// The hue is stored as given, so `new` accepts angles outside of a single turn. Use
+    /// [`normalize_hue`](#method.normalize_hue) to wrap it eagerly.
     pub const fn new(hue: A, saturation: T, value: T) -> Self {
         Hsv {
             hue: AngularChannel::new(hue),
@@ -60,6 +65,20 @@ where
         }
     }
 
+    impl_color_checked_new!(
+        fn new_checked(hue: A, saturation: T, value: T) {
+            crate::channel::debug_check_angle(&hue);
+            crate::channel::debug_check_bounded::<PosNormalBoundedChannel<T>>(&saturation);
+            crate::channel::debug_check_bounded::<PosNormalBoundedChannel<T>>(&value);
+        }
+    );
+
+    impl_color_from_hue_units_angular!(Hsv {
+        hue,
+        saturation,
+        value
+    });
+
     impl_color_color_cast_angular!(
         Hsv {
             hue,
@@ -95,16 +114,42 @@ where
     }
     /// Set the hue channel value
     pub fn set_hue(&mut self, val: A) {
+        crate::channel::debug_check_angle(&val);
         self.hue.0 = val;
     }
     /// Set the saturation channel value
     pub fn set_saturation(&mut self, val: T) {
+        crate::channel::debug_check_bounded::<PosNormalBoundedChannel<T>>(&val);
         self.saturation.0 = val;
     }
     /// Set the value channel value
     pub fn set_value(&mut self, val: T) {
+        crate::channel::debug_check_bounded::<PosNormalBoundedChannel<T>>(&val);
         self.value.0 = val;
     }
+
+    impl_color_complement_angular!(Hsv);
+    impl_color_normalize_hue_angular!(Hsv);
+    impl_color_to_feature_vector_angular!(
+        /// Returns the color as a feature vector `[cos(hue), sin(hue), saturation, value]`
+        ///
+        /// The saturation and value are converted to `f64` in $`[0, 1]`$.
+        {saturation, value}
+    );
+}
+
+impl<T> Hsv<T, Deg<T>>
+where
+    T: PosNormalChannelScalar + num_traits::Float,
+    Deg<T>: AngularChannelScalar,
+{
+    /// Construct an Hsv instance with the hue given in degrees
+    ///
+    /// This is a shorthand for `Hsv::new(Deg(hue), saturation, value)`, for when the hue is a
+    /// plain number.
+    pub fn from_degrees(hue: T, saturation: T, value: T) -> Self {
+        Hsv::new(Deg(hue), saturation, value)
+    }
 }
 
 impl<T, A> PolarColor for Hsv<T, A>
@@ -116,6 +161,34 @@ where
     type Cartesian = T;
 }
 
+impl<T, A> Hsv<T, A>
+where
+    T: PosNormalChannelScalar + ChannelFormatCast<f64> + num_traits::Float,
+    f64: ChannelFormatCast<T>,
+    A: AngularChannelScalar,
+{
+    /// Pull the color into the gamut of `space`, so it can be displayed after converting to `Rgb`
+    ///
+    /// An `Hsv` with an out of range saturation or value converts to an `Rgb` value outside of
+    /// the unit cube, which then clips when quantized, e.g. to `Rgb<u8>`. The converted `Rgb` is
+    /// taken to be encoded with the encoding of `space`. This clamps the value and reduces the
+    /// saturation until the color decodes to a linear value inside the space's gamut, so it
+    /// round-trips through `Rgb<u8>` without clipping. The hue is left untouched, and colors that
+    /// are already displayable are returned unchanged.
+    pub fn clamp_to_displayable<S>(self, space: &S) -> Self
+    where
+        S: ColorSpace<T>,
+    {
+        let value = self.value.normalize().0;
+        let saturation = self.saturation();
+        let hue = self.hue.0;
+        let saturation = clamp_saturation_to_gamut(saturation, space, |s| {
+            convert::FromColor::from_color(&Hsv::new(hue.clone(), s, value))
+        });
+        Hsv::new(hue, saturation, value)
+    }
+}
+
 impl<T, A> Color for Hsv<T, A>
 where
     T: PosNormalChannelScalar,
@@ -132,6 +205,22 @@ where
     }
 }
 
+impl<T, A> Lightness for Hsv<T, A>
+where
+    T: PosNormalChannelScalar,
+    A: AngularChannelScalar,
+{
+    type LightnessFormat = T;
+
+    fn lightness(&self) -> T {
+        self.value()
+    }
+    fn with_lightness(mut self, lightness: T) -> Self {
+        self.set_value(lightness);
+        self
+    }
+}
+
 impl<T, A> FromTuple for Hsv<T, A>
 where
     T: PosNormalChannelScalar,
@@ -164,6 +253,14 @@ where
     impl_color_lerp_angular!(Hsv<T> {hue, saturation, value});
 }
 
+impl<T, A> Hsv<T, A>
+where
+    T: PosNormalChannelScalar + color::Lerp,
+    A: AngularChannelScalar + color::Lerp,
+{
+    impl_color_lerp_retain_hue!(Hsv);
+}
+
 impl<T, A> Bounded for Hsv<T, A>
 where
     T: PosNormalChannelScalar,
@@ -294,6 +391,63 @@ where
     }
 }
 
+impl<T, A> Hsv<T, A>
+where
+    T: PosNormalChannelScalar + num_traits::Float,
+    A: AngularChannelScalar + FromAngle<angle::Turns<T>>,
+    u8: ChannelFormatCast<T>,
+{
+    /// Parse an sRgb hex string such as `"#3366cc"` directly into `Hsv`
+    ///
+    /// `Hsv` shares the color space of its parent Rgb, so the result is in sRgb as well.
+    pub fn from_hex(hex: &str) -> Result<Self, ParseColorError> {
+        let rgb: rgb::Rgb<T> = rgb::Rgb::from_hex(hex)?.color_cast();
+        Ok(convert::FromColor::from_color(&rgb))
+    }
+}
+
+/// Convert an 8-bit HSV color to `Rgb<u8>` using only integer math
+///
+/// This is the classic sextant algorithm used for driving LEDs from microcontrollers without a
+/// floating point unit. The full turn of hue is mapped onto `0..=255`, so each of the six
+/// sextants is 43 steps wide (the last is 41). Every product is divided by 255 with rounding,
+/// which keeps the primaries, grays and fully saturated colors exact.
+///
+/// The result is an approximation of converting through `Hsv<f32>`, and can differ from it by a
+/// few steps, mostly because of the coarse hue quantization.
+///
+/// ```
+/// use prisma::{hsv_u8_to_rgb, Rgb};
+///
+/// assert_eq!(hsv_u8_to_rgb(0, 255, 255), Rgb::new(255, 0, 0));
+/// assert_eq!(hsv_u8_to_rgb(43, 255, 128), Rgb::new(128, 128, 0));
+/// assert_eq!(hsv_u8_to_rgb(170, 0, 90), Rgb::new(90, 90, 90));
+/// ```
+pub fn hsv_u8_to_rgb(h: u8, s: u8, v: u8) -> rgb::Rgb<u8> {
+    if s == 0 {
+        return rgb::Rgb::new(v, v, v);
+    }
+    let scale = |a: u32, b: u32| -> u32 { (a * b + 127) / 255 };
+    let (h, s, v) = (u32::from(h), u32::from(s), u32::from(v));
+    let sextant = h / 43;
+    // The position within the sextant, in `0..=252`
+    let frac = (h - sextant * 43) * 6;
+
+    let p = scale(v, 255 - s) as u8;
+    let q = scale(v, 255 - scale(s, frac)) as u8;
+    let t = scale(v, 255 - scale(s, 255 - frac)) as u8;
+    let v = v as u8;
+
+    match sextant {
+        0 => rgb::Rgb::new(v, t, p),
+        1 => rgb::Rgb::new(q, v, p),
+        2 => rgb::Rgb::new(p, v, t),
+        3 => rgb::Rgb::new(p, q, v),
+        4 => rgb::Rgb::new(t, p, v),
+        _ => rgb::Rgb::new(v, p, q),
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -304,6 +458,29 @@ mod test {
     use std::f32::consts;
 
     use crate::test;
+    use crate::AngleUnit;
+
+    #[test]
+    fn test_to_feature_vector() {
+        let c1 = Hsv::new(Deg(90.0f64), 0.25, 0.75);
+        let f1 = c1.to_feature_vector();
+        assert_relative_eq!(f1[0], 0.0, epsilon = 1e-12);
+        assert_relative_eq!(f1[1], 1.0, epsilon = 1e-12);
+        assert_eq!(&f1[2..], &[0.25, 0.75]);
+
+        for &hue in [0.0, 45.0, 135.0, 200.0, 359.0].iter() {
+            let f = Hsv::new(Deg(hue), 1.0, 1.0).to_feature_vector();
+            let angle = f[1].atan2(f[0]).to_degrees();
+            assert_relative_eq!((angle + 360.0) % 360.0, hue, epsilon = 1e-9);
+        }
+
+        // Hues on either side of zero are close, and the angle unit does not matter
+        let f2 = Hsv::new(Deg(359.0f64), 0.5, 0.5).to_feature_vector();
+        let f3 = Hsv::new(Deg(1.0f64), 0.5, 0.5).to_feature_vector();
+        assert!((f2[0] - f3[0]).abs() < 1e-3 && (f2[1] - f3[1]).abs() < 0.04);
+        let f4 = Hsv::new(Turns(0.25f64), 0.25, 0.75).to_feature_vector();
+        assert_relative_eq!(f4[1], f1[1], epsilon = 1e-12);
+    }
 
     #[test]
     fn test_construct() {
@@ -322,6 +499,43 @@ mod test {
         assert_eq!(c3.to_tuple(), (Deg(50.0), 0.33, 0.66));
     }
 
+    #[test]
+    fn test_from_degrees() {
+        assert_eq!(
+            Hsv::from_degrees(120.0, 0.5, 0.8),
+            Hsv::new(Deg(120.0), 0.5, 0.8)
+        );
+        assert_eq!(
+            Hsv::from_degrees(300.0f32, 1.0, 0.25),
+            Hsv::new(Deg(300.0f32), 1.0, 0.25)
+        );
+    }
+
+    #[test]
+    fn test_from_turns_radians() {
+        let c1: Hsv<f64> = Hsv::from_turns(0.25, 0.5, 0.8);
+        assert_relative_eq!(c1, Hsv::new(Deg(90.0), 0.5, 0.8), epsilon = 1e-12);
+        let c2: Hsv<f64, Turns<f64>> = Hsv::from_turns(0.25, 0.5, 0.8);
+        assert_eq!(c2, Hsv::new(Turns(0.25), 0.5, 0.8));
+        let c3: Hsv<f32> = Hsv::from_radians(consts::PI, 1.0, 0.25);
+        assert_relative_eq!(c3, Hsv::new(Deg(180.0), 1.0, 0.25), epsilon = 1e-4);
+        let c4: Hsv<f32, Rad<f32>> = Hsv::from_radians(consts::FRAC_PI_2, 1.0, 0.25);
+        assert_eq!(c4, Hsv::new(Rad(consts::FRAC_PI_2), 1.0, 0.25));
+    }
+
+    #[test]
+    fn test_from_scalar_hue() {
+        let c1: Hsv<f64> = Hsv::from_scalar_hue(135.0, AngleUnit::Degrees, 0.5, 0.8);
+        assert_eq!(c1, Hsv::new(Deg(135.0), 0.5, 0.8));
+        let c2: Hsv<f64> =
+            Hsv::from_scalar_hue(0.75 * std::f64::consts::PI, AngleUnit::Radians, 0.5, 0.8);
+        assert_relative_eq!(c2, c1, epsilon = 1e-12);
+        let c3: Hsv<f64> = Hsv::from_scalar_hue(0.375, AngleUnit::Turns, 0.5, 0.8);
+        assert_relative_eq!(c3, c1, epsilon = 1e-12);
+        let c4: Hsv<f64, Rad<f64>> = Hsv::from_scalar_hue(135.0, AngleUnit::Degrees, 0.5, 0.8);
+        assert_relative_eq!(c4.hue(), Rad(0.75 * std::f64::consts::PI), epsilon = 1e-12);
+    }
+
     #[test]
     fn test_invert() {
         let c1 = Hsv::new(Deg(30.0), 0.3, 0.6);
@@ -357,6 +571,80 @@ mod test {
         assert_ulps_eq!(c2.normalize(), Hsv::new(Turns(0.25), 0.0, 1.0));
     }
 
+    #[test]
+    fn test_lerp_retain_hue() {
+        let blue = Hsv::new(Deg(200.0), 0.8, 0.6);
+        let gray = Hsv::from_color(&rgb::Rgb::new(0.5, 0.5, 0.5));
+        assert_eq!(gray.hue(), Deg(0.0));
+        assert!(blue.lerp(&gray, 0.5).hue() != Deg(200.0));
+
+        for &pos in [0.0, 0.25, 0.5, 0.75].iter() {
+            let to_gray = blue.lerp_retain_hue(&gray, pos);
+            assert_relative_eq!(to_gray.hue(), Deg(200.0));
+            assert_relative_eq!(to_gray.saturation(), 0.8 * (1.0 - pos));
+            assert_relative_eq!(to_gray.value(), 0.6 - 0.1 * pos, epsilon = 1e-12);
+            let from_gray = gray.lerp_retain_hue(&blue, pos);
+            assert_relative_eq!(from_gray.hue(), Deg(200.0));
+        }
+
+        // Fading out and back in through gray keeps the same hue on both sides
+        let faded = blue.lerp_retain_hue(&gray, 1.0);
+        let restored = faded.lerp_retain_hue(&blue, 0.5);
+        assert_relative_eq!(restored.hue(), Deg(200.0));
+
+        let other_gray = Hsv::new(Deg(90.0), 0.0, 0.2);
+        assert_relative_eq!(gray.lerp_retain_hue(&other_gray, 0.5).hue(), Deg(0.0));
+        let red = Hsv::new(Deg(0.0), 1.0, 1.0);
+        assert_relative_eq!(red.lerp_retain_hue(&blue, 0.5), red.lerp(&blue, 0.5));
+    }
+
+    #[test]
+    fn test_complement() {
+        let c1 = Hsv::new(Deg(30.0), 0.4, 0.7);
+        assert_relative_eq!(c1.complement(), Hsv::new(Deg(210.0), 0.4, 0.7));
+        assert_relative_eq!(c1.invert(), Hsv::new(Deg(210.0), 0.6, 0.3), epsilon = 1e-6);
+        assert_relative_eq!(c1.complement().complement(), c1);
+
+        let c2 = Hsv::new(Turns(0.75), 1.0, 1.0);
+        assert_relative_eq!(c2.complement(), Hsv::new(Turns(0.25), 1.0, 1.0));
+    }
+
+    #[test]
+    fn test_normalize_hue() {
+        let c1 = Hsv::new(Deg(450.0), 0.5, 0.8);
+        assert_eq!(c1.hue(), Deg(450.0));
+        assert_relative_eq!(c1.normalize_hue(), Hsv::new(Deg(90.0), 0.5, 0.8));
+        assert_relative_eq!(
+            Hsv::new(Deg(-30.0), 0.5, 0.8).normalize_hue(),
+            Hsv::new(Deg(330.0), 0.5, 0.8)
+        );
+        assert_relative_eq!(
+            Hsv::new(Turns(2.25), 1.2, -0.1).normalize_hue(),
+            Hsv::new(Turns(0.25), 1.2, -0.1)
+        );
+        let c2 = Hsv::new(Deg(120.0), 0.3, 0.4);
+        assert_eq!(c2.normalize_hue(), c2);
+    }
+
+    #[test]
+    fn test_clamp_to_displayable() {
+        use crate::color_space::named::SRgb;
+        let space = SRgb::new();
+        let c1 = Hsv::new(Deg(50.0), 1.25, 1.1);
+        assert!(!rgb::Rgb::from_color(&c1).is_normalized());
+        let clamped = c1.clamp_to_displayable(&space);
+        assert_eq!(clamped, Hsv::new(Deg(50.0), 1.0, 1.0));
+        let rgb = rgb::Rgb::from_color(&clamped);
+        assert!(rgb.is_normalized());
+        let round_trip: rgb::Rgb<f64> = rgb.color_cast::<u8>().color_cast();
+        assert_relative_eq!(round_trip, rgb, epsilon = 1.0 / 255.0);
+        assert_relative_eq!(Hsv::from_color(&round_trip), clamped, epsilon = 1e-2);
+
+        let c2 = Hsv::new(Deg(310.0), 0.5, 0.8);
+        assert!(rgb::Rgb::from_color(&c2).is_normalized());
+        assert_eq!(c2.clamp_to_displayable(&space), c2);
+    }
+
     #[test]
     fn test_chroma() {
         let test_data = test::build_hs_test_data();
@@ -421,6 +709,57 @@ mod test {
         assert_relative_eq!(c3.color_cast(), c3);
     }
 
+    #[test]
+    fn test_hsv_u8_to_rgb() {
+        let table = [
+            ((0, 255, 255), (255, 0, 0)),
+            ((21, 255, 255), (255, 126, 0)),
+            ((43, 255, 255), (255, 255, 0)),
+            ((86, 255, 255), (0, 255, 0)),
+            ((128, 255, 255), (0, 255, 252)),
+            ((172, 255, 255), (0, 0, 255)),
+            ((213, 255, 255), (246, 0, 255)),
+            ((255, 255, 255), (255, 0, 15)),
+            ((0, 128, 255), (255, 127, 127)),
+            ((64, 200, 100), (61, 100, 22)),
+            ((200, 100, 180), (156, 109, 180)),
+            ((150, 255, 10), (0, 5, 10)),
+            ((0, 255, 0), (0, 0, 0)),
+            ((99, 0, 200), (200, 200, 200)),
+        ];
+        for &((h, s, v), (r, g, b)) in table.iter() {
+            assert_eq!(hsv_u8_to_rgb(h, s, v), rgb::Rgb::new(r, g, b));
+        }
+
+        // Within a few steps of the float conversion, limited by the hue quantization
+        for h in (0..=255u8).step_by(3) {
+            for &(s, v) in [(255u8, 255u8), (180, 200), (60, 90)].iter() {
+                let hsv = Hsv::new(
+                    Turns(f64::from(h) / 256.0),
+                    f64::from(s) / 255.0,
+                    f64::from(v) / 255.0,
+                );
+                let expected = rgb::Rgb::from_color(&hsv);
+                let actual = hsv_u8_to_rgb(h, s, v).color_cast::<f64>();
+                assert_relative_eq!(actual, expected, epsilon = 11.0 / 255.0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_from_hex() {
+        let c1: Hsv<f32> = Hsv::from_hex("#000000").unwrap();
+        assert_relative_eq!(c1, Hsv::new(Deg(0.0), 0.0, 0.0), epsilon = 1e-6);
+        let c2: Hsv<f32> = Hsv::from_hex("#ffffff").unwrap();
+        assert_relative_eq!(c2, Hsv::new(Deg(0.0), 0.0, 1.0), epsilon = 1e-6);
+        let c3: Hsv<f32> = Hsv::from_hex("#3366cc").unwrap();
+        assert_relative_eq!(c3, Hsv::new(Deg(220.0), 0.75, 0.8), epsilon = 1e-4);
+        assert_eq!(
+            Hsv::<f32>::from_hex("3366c"),
+            Err(ParseColorError::InvalidLength)
+        );
+    }
+
     #[cfg(feature = "serde")]
     #[test]
     fn test_serde() {
@@ -430,4 +769,11 @@ mod test {
         let deserialized: Hsv<f32> = serde_json::from_str(&serialized).unwrap();
         assert_eq!(deserialized, color);
     }
+
+    #[test]
+    fn test_lightness() {
+        let c1 = Hsv::new(Deg(120.0), 0.5, 0.8);
+        assert_eq!(Lightness::lightness(&c1), 0.8);
+        assert_eq!(c1.with_lightness(0.25), Hsv::new(Deg(120.0), 0.5, 0.25));
+    }
 }
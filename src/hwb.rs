@@ -50,6 +50,9 @@ where
     A: AngularChannelScalar,
 {
     /// Construct a `Hwb` instance from hue, whiteness and blackness
+    ///
+    /// The hue is stored as given, so `new` accepts angles outside of a single turn. Use
+    /// [`normalize_hue`](#method.normalize_hue) to wrap it eagerly.
     pub const fn new(hue: A, whiteness: T, blackness: T) -> Self {
         Hwb {
             hue: AngularChannel::new(hue),
@@ -58,6 +61,20 @@ where
         }
     }
 
+    impl_color_checked_new!(
+        fn new_checked(hue: A, whiteness: T, blackness: T) {
+            crate::channel::debug_check_angle(&hue);
+            crate::channel::debug_check_bounded::<PosNormalBoundedChannel<T>>(&whiteness);
+            crate::channel::debug_check_bounded::<PosNormalBoundedChannel<T>>(&blackness);
+        }
+    );
+
+    impl_color_from_hue_units_angular!(Hwb {
+        hue,
+        whiteness,
+        blackness
+    });
+
     impl_color_color_cast_angular!(
         Hwb {
             hue,
@@ -93,16 +110,28 @@ where
     }
     /// Set the hue channel value
     pub fn set_hue(&mut self, val: A) {
+        crate::channel::debug_check_angle(&val);
         self.hue.0 = val;
     }
     /// Set the whiteness channel value
     pub fn set_whiteness(&mut self, val: T) {
+        crate::channel::debug_check_bounded::<PosNormalBoundedChannel<T>>(&val);
         self.whiteness.0 = val;
     }
     /// Set the blackness channel value
     pub fn set_blackness(&mut self, val: T) {
+        crate::channel::debug_check_bounded::<PosNormalBoundedChannel<T>>(&val);
         self.blackness.0 = val;
     }
+
+    impl_color_complement_angular!(Hwb);
+    impl_color_normalize_hue_angular!(Hwb);
+    impl_color_to_feature_vector_angular!(
+        /// Returns the color as a feature vector `[cos(hue), sin(hue), whiteness, blackness]`
+        ///
+        /// The whiteness and blackness are converted to `f64` in $`[0, 1]`$.
+        {whiteness, blackness}
+    );
 }
 
 impl<T, A> Hwb<T, A>
@@ -332,6 +361,9 @@ where
     T: HwbBoundedChannelTraits + num_traits::Float,
     A: AngularChannelScalar,
 {
+    /// Convert directly from `Hsv`, using $`W = (1 - S) V`$ and $`B = 1 - V`$
+    ///
+    /// This is exact, with no intermediate `Rgb` value, and keeps the hue unchanged.
     fn from_color(from: &hsv::Hsv<T, A>) -> Self {
         let one: T = num_traits::cast(1.0).unwrap();
         let blackness = one - from.value();
@@ -345,13 +377,21 @@ where
     T: HwbBoundedChannelTraits + num_traits::Float,
     A: AngularChannelScalar,
 {
+    /// Convert directly to `Hsv`, using $`V = 1 - B`$ and $`S = 1 - W / V`$
+    ///
+    /// Colors with $`W + B > 1`$ are first rescaled with [`rescale_wb`](struct.Hwb.html#method.rescale_wb),
+    /// giving the gray they are displayed as. Black has no defined saturation, and gets a
+    /// saturation of zero. The hue is kept unchanged.
     fn from_color(from: &Hwb<T, A>) -> Self {
-        let epsilon: T = num_traits::cast(1e-10).unwrap();
         let c = from.clone().rescale_wb();
         let one: T = num_traits::cast(1.0).unwrap();
 
         let value = one - c.blackness();
-        let saturation = one - c.whiteness() / (value + epsilon);
+        let saturation = if value > T::zero() {
+            one - c.whiteness() / value
+        } else {
+            T::zero()
+        };
 
         hsv::Hsv::new(c.hue(), saturation, value)
     }
@@ -469,6 +509,43 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_hsv_round_trip() {
+        for h in 0..12 {
+            for s in 0..=10 {
+                for v in 0..=10 {
+                    let hsv = Hsv::new(Deg(h as f64 * 30.0), s as f64 / 10.0, v as f64 / 10.0);
+                    let hwb = Hwb::from_color(&hsv);
+                    assert!(!hwb.wb_needs_rescaled());
+                    let back = Hsv::from_color(&hwb);
+                    assert_eq!(back.hue(), hsv.hue());
+                    assert_relative_eq!(back.value(), hsv.value(), epsilon = 1e-7);
+                    if hsv.value() > 0.0 {
+                        assert_relative_eq!(back.saturation(), hsv.saturation(), epsilon = 1e-7);
+                    } else {
+                        assert_eq!(back.saturation(), 0.0);
+                    }
+                }
+            }
+        }
+
+        let dark = Hsv::new(Deg(40.0), 0.75, 1e-6);
+        let back = Hsv::from_color(&Hwb::from_color(&dark));
+        assert_relative_eq!(back, dark, epsilon = 1e-9);
+
+        // Whiteness and blackness adding up to more than one give a gray
+        let hwb = Hwb::new(Deg(100.0), 0.8, 0.6);
+        assert!(hwb.wb_needs_rescaled());
+        let hsv = Hsv::from_color(&hwb);
+        assert_relative_eq!(hsv.saturation(), 0.0, epsilon = 1e-12);
+        assert_relative_eq!(hsv.value(), 0.8 / 1.4, epsilon = 1e-12);
+        assert_relative_eq!(
+            Rgb::from_color(&hsv),
+            Rgb::from_color(&hwb),
+            epsilon = 1e-12
+        );
+    }
+
     #[test]
     fn test_to_hsv() {
         let test_data = test::build_hs_test_data();
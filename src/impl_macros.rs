@@ -65,6 +65,17 @@ macro_rules! impl_color_as_slice {
     }
 }
 
+macro_rules! impl_color_as_slice_mut {
+    ($T: ty) => {
+        fn as_slice_mut(&mut self) -> &mut [Self::ChannelFormat] {
+            unsafe {
+                let ptr = self as *mut Self as *mut Self::ChannelFormat;
+                slice::from_raw_parts_mut(ptr, Self::num_channels() as usize)
+            }
+        }
+    };
+}
+
 macro_rules! impl_color_from_slice_square {
     ($name: ident<$T:ident> {$($fields:ident:$chan:ident - $i:expr),*}, phantom={$($phantom:ident),*}) => {
         fn from_slice(vals: &[$T]) -> Self {
@@ -156,6 +167,33 @@ macro_rules! impl_color_lerp_angular {
     };
 }
 
+macro_rules! impl_color_lerp_retain_hue {
+    ($name:ident) => {
+        /// Linearly interpolate between two colors, carrying the hue through gray endpoints
+        ///
+        /// The hue of a color with zero saturation has no effect on its appearance, and is simply
+        /// zero after converting a gray from `Rgb`. Ordinary `lerp` still interpolates towards
+        /// that meaningless hue, so a fade from a color to gray passes through unrelated hues.
+        ///
+        /// Here, if one endpoint has zero saturation, it takes on the hue of the other endpoint,
+        /// so the hue stays constant along the whole interpolation and only the saturation and
+        /// other channels change. If both endpoints are gray, the hue of `self` is used. To keep a
+        /// hue through a sequence of edits that pass through gray, store it separately from the
+        /// color.
+        pub fn lerp_retain_hue(&self, right: &Self, pos: A::Position) -> Self {
+            let gray = T::min_bound();
+            let mut left = self.clone();
+            let mut right = right.clone();
+            if right.saturation.0 == gray {
+                right.hue = left.hue.clone();
+            } else if left.saturation.0 == gray {
+                left.hue = right.hue.clone();
+            }
+            crate::color::Lerp::lerp(&left, &right, pos)
+        }
+    };
+}
+
 macro_rules! impl_color_default {
     ($name:ident {$($fields:ident:$ChanType:ident),*}, phantom={$($phantom:ident),*}) => {
         fn default() -> Self {
@@ -220,6 +258,129 @@ macro_rules! impl_color_get_hue_angular {
     }
 }
 
+macro_rules! impl_color_complement_angular {
+    ($name:ident) => {
+        /// Returns the complementary color
+        ///
+        /// The hue is rotated by half a turn and normalized while the other channels are kept as
+        /// they are. This differs from `Invert::invert`, which also inverts every non-hue channel.
+        pub fn complement(&self) -> Self {
+            let mut out = self.clone();
+            out.hue.0 = <A as Angle>::normalize(<A as Angle>::invert(self.hue.0.clone()));
+            out
+        }
+    };
+}
+
+macro_rules! impl_color_normalize_hue_angular {
+    ($name:ident) => {
+        /// Returns the color with the hue wrapped into a single turn
+        ///
+        /// The hue is brought into $`[0, 360)`$ degrees, or the equivalent range for other angle
+        /// units, while the other channels are kept as they are. Unlike `Bounded::normalize`, no
+        /// other channel is clamped.
+        pub fn normalize_hue(self) -> Self {
+            let mut out = self;
+            out.hue.0 = <A as Angle>::normalize(out.hue.0);
+            out
+        }
+    };
+}
+
+macro_rules! impl_color_from_hue_units_angular {
+    ($name:ident {hue, $c1:ident, $c2:ident}) => {
+        /// Construct the color with the hue given as a fraction of a turn
+        ///
+        /// The hue is wrapped in `Turns` and converted into the hue type `A`, so a hue of `0.25`
+        /// is the same as 90 degrees.
+        pub fn from_turns(hue: T, $c1: T, $c2: T) -> Self
+        where
+            T: num_traits::Float,
+            A: angle::FromAngle<angle::Turns<T>>,
+        {
+            $name::new(A::from_angle(angle::Turns(hue)), $c1, $c2)
+        }
+        /// Construct the color with the hue given in radians
+        ///
+        /// The hue is wrapped in `Rad` and converted into the hue type `A`.
+        pub fn from_radians(hue: T, $c1: T, $c2: T) -> Self
+        where
+            T: num_traits::Float,
+            A: angle::FromAngle<angle::Rad<T>>,
+        {
+            $name::new(A::from_angle(angle::Rad(hue)), $c1, $c2)
+        }
+        /// Construct the color from a scalar hue in a unit chosen at runtime
+        ///
+        /// The hue is converted from `unit` into the hue type `A`.
+        pub fn from_scalar_hue(hue: T, unit: crate::AngleUnit, $c1: T, $c2: T) -> Self
+        where
+            T: num_traits::Float,
+            A: angle::FromAngle<angle::Deg<T>>
+                + angle::FromAngle<angle::Rad<T>>
+                + angle::FromAngle<angle::Turns<T>>,
+        {
+            $name::new(unit.to_angle(hue), $c1, $c2)
+        }
+    };
+    ($name:ident {$c1:ident, $c2:ident, hue}) => {
+        /// Construct the color with the hue given as a fraction of a turn
+        ///
+        /// The hue is wrapped in `Turns` and converted into the hue type `A`, so a hue of `0.25`
+        /// is the same as 90 degrees.
+        pub fn from_turns($c1: T, $c2: T, hue: T) -> Self
+        where
+            T: num_traits::Float,
+            A: angle::FromAngle<angle::Turns<T>>,
+        {
+            $name::new($c1, $c2, A::from_angle(angle::Turns(hue)))
+        }
+        /// Construct the color with the hue given in radians
+        ///
+        /// The hue is wrapped in `Rad` and converted into the hue type `A`.
+        pub fn from_radians($c1: T, $c2: T, hue: T) -> Self
+        where
+            T: num_traits::Float,
+            A: angle::FromAngle<angle::Rad<T>>,
+        {
+            $name::new($c1, $c2, A::from_angle(angle::Rad(hue)))
+        }
+        /// Construct the color from a scalar hue in a unit chosen at runtime
+        ///
+        /// The hue is converted from `unit` into the hue type `A`.
+        pub fn from_scalar_hue($c1: T, $c2: T, hue: T, unit: crate::AngleUnit) -> Self
+        where
+            T: num_traits::Float,
+            A: angle::FromAngle<angle::Deg<T>>
+                + angle::FromAngle<angle::Rad<T>>
+                + angle::FromAngle<angle::Turns<T>>,
+        {
+            $name::new($c1, $c2, unit.to_angle(hue))
+        }
+    };
+}
+
+macro_rules! impl_color_to_feature_vector_angular {
+    ($(#[$attr:meta])* {$($fields:ident),*}) => {
+        $(#[$attr])*
+        ///
+        /// The hue is given as the pair $`(\cos h, \sin h)`$, each in $`[-1, 1]`$, so that hues on
+        /// either side of zero give nearby vectors. The hue is recovered with `atan2(sin, cos)`.
+        pub fn to_feature_vector(&self) -> [f64; 4]
+        where
+            T: crate::channel::ChannelFormatCast<f64>,
+        {
+            let turns: f64 = num_traits::cast(self.hue.0.scalar() / A::period()).unwrap();
+            let (sin, cos) = (turns * 2.0 * std::f64::consts::PI).sin_cos();
+            [
+                cos,
+                sin,
+                $(crate::channel::ChannelFormatCast::<f64>::cast(self.$fields.0.clone())),*
+            ]
+        }
+    };
+}
+
 macro_rules! impl_color_homogeneous_color_square {
     ($name:ident<$T:ident> {$($fields:ident),*}, phantom={$($phantom:ident),*}) => {
         fn clamp(self, min: $T, max: $T) -> Self {
@@ -249,3 +410,16 @@ macro_rules! impl_color_broadcast {
         impl_color_broadcast!($name<$T> {$($fields),*}, chan=$chan, phantom={});
     };
 }
+
+macro_rules! impl_color_checked_new {
+    (fn new_checked($($arg:ident: $Ty:ty),*) $check:block) => {
+        /// Construct the color like `new`, checking the channels in `strict` mode
+        ///
+        /// With the `strict` feature and debug assertions enabled, this panics if a bounded
+        /// channel is out of range or an angle is not finite. Otherwise it is the same as `new`.
+        pub fn new_checked($($arg: $Ty),*) -> Self {
+            $check
+            Self::new($($arg),*)
+        }
+    };
+}
@@ -3,10 +3,15 @@
 #![allow(non_snake_case)]
 use crate::channel::{
     ChannelCast, ChannelFormatCast, ColorChannel, FreeChannel, FreeChannelScalar, PosFreeChannel,
+    PosNormalChannelScalar,
 };
-use crate::color::{Bounded, Broadcast, Color, FromTuple, HomogeneousColor, Lerp};
+use crate::color::{Bounded, Broadcast, Color, FromTuple, HomogeneousColor, Lerp, Lightness};
+use crate::color_space::ColorSpace;
+use crate::lms::bradford_adaptation;
+use crate::parse::{self, ParseColorError};
+use crate::rgb::Rgb;
 use crate::tags::LabTag;
-use crate::white_point::{UnitWhitePoint, WhitePoint};
+use crate::white_point::{DefaultWhitePoint, UnitWhitePoint, WhitePoint, D65};
 use crate::xyz::Xyz;
 #[cfg(feature = "approx")]
 use approx;
@@ -19,7 +24,10 @@ use std::fmt;
 /// perceptually uniform, that is, such that a euclidean distance in any direction appears to change
 /// the same amount. Unlike XYZ, Lab spaces require a reference white point in order to be defined.
 /// This means that there are many different lab spaces that are incompatible because of having different
-/// white points. Like XYZ, most values in `Lab` lie outside the visible gamut of the eye.
+/// white points. Like XYZ, most values in `Lab` lie outside the visible gamut of the eye. The white
+/// point parameter `W` defaults to [`DefaultWhitePoint`](white_point/type.DefaultWhitePoint.html),
+/// so `Lab<f64>` is a D65 Lab color. A default is not used when inferring types, so an
+/// unannotated `Lab::new` cannot pick it; use [`LabD65`](type.LabD65.html) there instead.
 ///
 /// The `L` value represents lightness, while a and b are green vs red and blue vs yellow respectively.
 /// Lab is one of two commonly used perceptually uniform spaces, the other being [`Luv`](struct.Luv.html).
@@ -29,13 +37,16 @@ use std::fmt;
 #[repr(C)]
 #[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub struct Lab<T, W> {
+pub struct Lab<T, W = DefaultWhitePoint> {
     L: PosFreeChannel<T>,
     a: FreeChannel<T>,
     b: FreeChannel<T>,
     white_point: W,
 }
 
+/// A `Lab` color with a D65 white point, for constructing without naming the white point
+pub type LabD65<T> = Lab<T, D65>;
+
 impl<T, W> Lab<T, W>
 where
     T: FreeChannelScalar,
@@ -124,6 +135,37 @@ where
     pub fn white_point(&self) -> &W {
         &self.white_point
     }
+
+    /// Scale the chroma of the color by `factor`, keeping the lightness and hue
+    ///
+    /// Both `a` and `b` are multiplied by `factor`. As $`L^*`$ is unchanged, this is a
+    /// saturation adjustment that keeps the perceived brightness, unlike scaling the saturation
+    /// of an `Hsv` or `Hsl` color. A factor of zero gives the gray of the same lightness.
+    pub fn scale_chroma(self, factor: T) -> Self {
+        Lab {
+            L: self.L,
+            a: FreeChannel::new(self.a.0 * factor),
+            b: FreeChannel::new(self.b.0 * factor),
+            white_point: self.white_point,
+        }
+    }
+
+    /// Find the position `t` in `[0, 1]` for which `a.lerp(b, t)` is closest to `target`
+    ///
+    /// Since `lerp` moves along a straight line in Lab, this is the orthogonal projection of
+    /// `target` onto the line from `a` to `b`, clamped to the segment between them. The result
+    /// minimizes the euclidean color difference $`\Delta E_{ab}^*`$. If `a` and `b` are the same
+    /// color, every `t` is equally close and zero is returned.
+    pub fn solve_mix(a: &Lab<T, W>, b: &Lab<T, W>, target: &Lab<T, W>) -> T {
+        let dir = [b.L() - a.L(), b.a() - a.a(), b.b() - a.b()];
+        let offset = [target.L() - a.L(), target.a() - a.a(), target.b() - a.b()];
+        let len_sq = dir[0] * dir[0] + dir[1] * dir[1] + dir[2] * dir[2];
+        if len_sq == T::zero() {
+            return T::zero();
+        }
+        let t = (offset[0] * dir[0] + offset[1] * dir[1] + offset[2] * dir[2]) / len_sq;
+        num_traits::clamp(t, T::zero(), T::one())
+    }
 }
 
 impl<T, W> Color for Lab<T, W>
@@ -143,6 +185,22 @@ where
     }
 }
 
+impl<T, W> Lightness for Lab<T, W>
+where
+    T: FreeChannelScalar,
+    W: WhitePoint<T>,
+{
+    type LightnessFormat = T;
+
+    fn lightness(&self) -> T {
+        self.L()
+    }
+    fn with_lightness(mut self, lightness: T) -> Self {
+        self.set_L(lightness);
+        self
+    }
+}
+
 impl<T, W> FromTuple for Lab<T, W>
 where
     T: FreeChannelScalar,
@@ -261,8 +319,7 @@ where
         let x = from.x() / wp_xyz.x();
         let y = from.y() / wp_xyz.y();
         let z = from.z() / wp_xyz.z();
-        let L = num_traits::cast::<_, T>(116.0).unwrap() * Lab::<T, W>::lab_f(y)
-            - num_traits::cast(16.0).unwrap();
+        let L = Lab::<T, W>::lightness_from_relative_y(y);
         let a = num_traits::cast::<_, T>(500.0).unwrap()
             * (Lab::<T, W>::lab_f(x) - Lab::<T, W>::lab_f(y));
         let b = num_traits::cast::<_, T>(200.0).unwrap()
@@ -283,6 +340,92 @@ where
         let z = Self::calc_xz(fz) * wp.z();
         Xyz::new(x, y, z)
     }
+    /// Returns the CIEDE2000 color difference $`\Delta E_{00}`$ between `self` and `other`
+    ///
+    /// CIEDE2000 corrects the euclidean distance in Lab for the remaining perceptual
+    /// non-uniformities of the space, particularly in the blue region and for near neutral colors.
+    /// A difference of around one is just noticeable. The parametric weighting factors
+    /// $`k_L`$, $`k_C`$ and $`k_H`$ are all taken to be one.
+    ///
+    /// Both colors are assumed to be relative to the same white point.
+    pub fn ciede2000(&self, other: &Lab<T, W>) -> T {
+        let channels = |lab: &Lab<T, W>| -> (f64, f64, f64) {
+            (
+                num_traits::cast(lab.L()).unwrap(),
+                num_traits::cast(lab.a()).unwrap(),
+                num_traits::cast(lab.b()).unwrap(),
+            )
+        };
+        let (L1, a1, b1) = channels(self);
+        let (L2, a2, b2) = channels(other);
+        let pow7 = |v: f64| v.powi(7);
+        let two_pi = 2.0 * std::f64::consts::PI;
+
+        let c_bar = 0.5 * (a1.hypot(b1) + a2.hypot(b2));
+        let g = 0.5 * (1.0 - (pow7(c_bar) / (pow7(c_bar) + pow7(25.0))).sqrt());
+        let (a1, a2) = ((1.0 + g) * a1, (1.0 + g) * a2);
+        let (c1, c2) = (a1.hypot(b1), a2.hypot(b2));
+        let hue = |b: f64, a: f64| {
+            if a == 0.0 && b == 0.0 {
+                0.0
+            } else {
+                b.atan2(a).rem_euclid(two_pi)
+            }
+        };
+        let (h1, h2) = (hue(b1, a1), hue(b2, a2));
+
+        let delta_L = L2 - L1;
+        let delta_c = c2 - c1;
+        let delta_h = if c1 * c2 == 0.0 {
+            0.0
+        } else {
+            let diff = h2 - h1;
+            if diff > std::f64::consts::PI {
+                diff - two_pi
+            } else if diff < -std::f64::consts::PI {
+                diff + two_pi
+            } else {
+                diff
+            }
+        };
+        let delta_H = 2.0 * (c1 * c2).sqrt() * (0.5 * delta_h).sin();
+
+        let L_bar = 0.5 * (L1 + L2);
+        let c_bar = 0.5 * (c1 + c2);
+        let h_bar = if c1 * c2 == 0.0 {
+            h1 + h2
+        } else if (h1 - h2).abs() <= std::f64::consts::PI {
+            0.5 * (h1 + h2)
+        } else if h1 + h2 < two_pi {
+            0.5 * (h1 + h2 + two_pi)
+        } else {
+            0.5 * (h1 + h2 - two_pi)
+        };
+
+        let t = 1.0 - 0.17 * (h_bar - 30f64.to_radians()).cos()
+            + 0.24 * (2.0 * h_bar).cos()
+            + 0.32 * (3.0 * h_bar + 6f64.to_radians()).cos()
+            - 0.20 * (4.0 * h_bar - 63f64.to_radians()).cos();
+        let delta_theta =
+            30f64.to_radians() * (-((h_bar.to_degrees() - 275.0) / 25.0).powi(2)).exp();
+        let r_c = 2.0 * (pow7(c_bar) / (pow7(c_bar) + pow7(25.0))).sqrt();
+        let L_offset = (L_bar - 50.0) * (L_bar - 50.0);
+        let s_L = 1.0 + 0.015 * L_offset / (20.0 + L_offset).sqrt();
+        let s_c = 1.0 + 0.045 * c_bar;
+        let s_h = 1.0 + 0.015 * c_bar * t;
+        let r_t = -(2.0 * delta_theta).sin() * r_c;
+
+        let (dl, dc, dh) = (delta_L / s_L, delta_c / s_c, delta_H / s_h);
+        num_traits::cast((dl * dl + dc * dc + dh * dh + r_t * dc * dh).sqrt()).unwrap()
+    }
+
+    /// Returns the lightness $`L^*`$ of a luminance `y` relative to the white point's luminance
+    ///
+    /// The curve does not depend on the white point itself.
+    pub(crate) fn lightness_from_relative_y(y: T) -> T {
+        num_traits::cast::<_, T>(116.0).unwrap() * Self::lab_f(y) - num_traits::cast(16.0).unwrap()
+    }
+
     fn lab_f(channel: T) -> T {
         if channel > Self::epsilon() {
             channel.cbrt()
@@ -338,13 +481,128 @@ where
     }
 }
 
+impl<T> Lab<T, D65>
+where
+    T: FreeChannelScalar + PosNormalChannelScalar + ChannelFormatCast<f64>,
+    f64: ChannelFormatCast<T>,
+    u8: ChannelFormatCast<T>,
+{
+    /// Parse an sRgb hex string such as `"#3366cc"` directly into `Lab`
+    ///
+    /// The color is interpreted as sRgb encoded, taken to `Xyz` through the
+    /// [`SRgb`](color_space/named/struct.SRgb.html) color space and then to `Lab` relative to D65.
+    pub fn from_hex(hex: &str) -> Result<Self, ParseColorError> {
+        let xyz = parse::srgb_hex_to_xyz(hex)?;
+        Ok(Lab::from_xyz(&xyz, D65))
+    }
+}
+
+/// Convert `rgb`, encoded with the encoding of `space`, into CIELAB relative to the space's white
+///
+/// The color is adapted from the white point of `space` to D65 with the Bradford transform, so
+/// the space's white maps to `Lab::new(100, 0, 0)` whatever its white point is. The inverse is
+/// [`Lchab::clip_with_delta`](struct.Lchab.html#method.clip_with_delta) on a D65 color, which
+/// adapts back the same way.
+pub(crate) fn rgb_to_lab_in_space<T, S>(rgb: &Rgb<T>, space: &S) -> Lab<T, D65>
+where
+    T: FreeChannelScalar + PosNormalChannelScalar + ChannelFormatCast<f64>,
+    f64: ChannelFormatCast<T>,
+    S: ColorSpace<T>,
+{
+    let xyz = space.convert_rgb_to_xyz(rgb, &space.encoding());
+    xyz_to_lab_relative_to(&xyz, &space.white_point())
+}
+
+/// Convert `xyz`, seen under `white`, into CIELAB relative to `white`
+///
+/// This is [`rgb_to_lab_in_space`](fn.rgb_to_lab_in_space.html) for colors already in XYZ.
+pub(crate) fn xyz_to_lab_relative_to<T>(xyz: &Xyz<T>, white: &Xyz<T>) -> Lab<T, D65>
+where
+    T: FreeChannelScalar + PosNormalChannelScalar,
+{
+    let adaptation = bradford_adaptation(white, &D65.get_xyz());
+    let (x, y, z) = adaptation.transform_vector(xyz.clone().to_tuple());
+    Lab::from_xyz(&Xyz::new(x, y, z), D65)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::lchab::{Lchab, LchabD65};
+    use crate::lchuv::{Lchuv, LchuvD65};
+    use crate::luv::{Luv, LuvD65};
     use crate::white_point::*;
     use crate::xyz::Xyz;
+    use angle::Deg;
     use approx::*;
 
+    #[test]
+    fn test_ciede2000() {
+        // Test pairs from Sharma, Wu and Dalal, "The CIEDE2000 Color-Difference Formula"
+        let pairs = [
+            ((50.0, 2.6772, -79.7751), (50.0, 0.0, -82.7485), 2.0425),
+            ((50.0, 2.8361, -74.0200), (50.0, 0.0, -82.7485), 3.4412),
+            ((50.0, -1.3802, -84.2814), (50.0, 0.0, -82.7485), 1.0000),
+            ((50.0, 2.5, 0.0), (73.0, 25.0, -18.0), 27.1492),
+            ((50.0, 2.5, 0.0), (61.0, -5.0, 29.0), 22.8977),
+            ((50.0, 2.5, 0.0), (50.0, 0.0, -2.5), 4.3065),
+        ];
+        for &((L1, a1, b1), (L2, a2, b2), expected) in pairs.iter() {
+            let c1 = Lab::<f64, D65>::new(L1, a1, b1);
+            let c2 = Lab::<f64, D65>::new(L2, a2, b2);
+            assert_relative_eq!(c1.ciede2000(&c2), expected, epsilon = 1e-4);
+            assert_relative_eq!(c2.ciede2000(&c1), expected, epsilon = 1e-4);
+        }
+        let c = Lab::<f64, D65>::new(42.0, 12.0, -30.0);
+        assert_eq!(c.ciede2000(&c), 0.0);
+    }
+
+    #[test]
+    fn test_scale_chroma() {
+        let c1 = Lab::<f64, D65>::new(62.0, 30.0, -40.0);
+        let c2 = c1.scale_chroma(1.5);
+        assert_eq!(c2.L(), 62.0);
+        assert_relative_eq!(c2.a().hypot(c2.b()), 1.5 * c1.a().hypot(c1.b()));
+        assert_relative_eq!(c2.b().atan2(c2.a()), c1.b().atan2(c1.a()));
+        assert_eq!(c1.scale_chroma(0.0), Lab::new(62.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_default_white_point() {
+        let c1: Lab<f64> = Lab::new(50.0, 10.0, -5.0);
+        assert_eq!(c1, Lab::<_, D65>::new(50.0, 10.0, -5.0));
+        assert_eq!(
+            Lab::new(50.0, 10.0, -5.0),
+            Lab::<f32, D65>::new(50.0, 10.0, -5.0)
+        );
+        let c2: Luv<f64> = Luv::new(50.0, 10.0, -5.0);
+        assert_eq!(c2, Luv::<_, D65>::new(50.0, 10.0, -5.0));
+
+        assert_eq!(LabD65::new(50.0, 10.0, -5.0), c1);
+        assert_eq!(LuvD65::new(50.0, 10.0, -5.0), c2);
+        let lch = LchabD65::new(50.0, 10.0, Deg(30.0));
+        assert_eq!(lch, Lchab::<f64, D65>::new(50.0, 10.0, Deg(30.0)));
+        let lch = LchuvD65::new(50.0f32, 10.0, Deg(30.0));
+        assert_eq!(lch, Lchuv::<f32, D65>::new(50.0, 10.0, Deg(30.0)));
+    }
+
+    #[test]
+    fn test_solve_mix() {
+        let c1 = Lab::<f64, D65>::new(30.0, 50.0, -20.0);
+        let c2 = Lab::<f64, D65>::new(80.0, -10.0, 40.0);
+        let mid = c1.lerp(&c2, 0.5);
+        assert_relative_eq!(Lab::solve_mix(&c1, &c2, &mid), 0.5);
+        assert_relative_eq!(Lab::solve_mix(&c1, &c2, &c1.lerp(&c2, 0.2)), 0.2);
+        assert_relative_eq!(Lab::solve_mix(&c2, &c1, &mid), 0.5);
+
+        let off_line = Lab::new(mid.L(), mid.a() + 10.0, mid.b() + 10.0);
+        assert_relative_eq!(Lab::solve_mix(&c1, &c2, &off_line), 0.5);
+
+        assert_eq!(Lab::solve_mix(&c1, &c2, &Lab::new(0.0, 80.0, -50.0)), 0.0);
+        assert_eq!(Lab::solve_mix(&c1, &c2, &Lab::new(100.0, -40.0, 80.0)), 1.0);
+        assert_eq!(Lab::solve_mix(&c1, &c1, &c2), 0.0);
+    }
+
     #[test]
     fn test_construct() {
         let c1 = Lab::<_, D65>::new(82.00, -32.0, 77.7);
@@ -443,6 +701,20 @@ mod test {
         assert_relative_eq!(c1.color_cast::<f32>().color_cast(), c1);
     }
 
+    #[test]
+    fn test_from_hex() {
+        let black = Lab::<f64, _>::from_hex("#000000").unwrap();
+        assert_relative_eq!(black, Lab::new(0.0, 0.0, 0.0), epsilon = 1e-4);
+        let white = Lab::<f64, _>::from_hex("#ffffff").unwrap();
+        assert_relative_eq!(white, Lab::new(100.0, 0.0, 0.0), epsilon = 1e-2);
+        let c1 = Lab::<f64, _>::from_hex("#3366cc").unwrap();
+        assert_relative_eq!(c1, Lab::new(45.0315, 18.7111, -57.8530), epsilon = 1e-3);
+        assert_eq!(
+            Lab::<f64, D65>::from_hex("#33g6cc"),
+            Err(ParseColorError::InvalidDigit)
+        );
+    }
+
     #[cfg(feature = "serde")]
     #[test]
     fn test_serde() {
@@ -455,4 +727,44 @@ mod test {
         let deserialized: Lab<f32, D65> = serde_json::from_str(&serialized).unwrap();
         assert_eq!(deserialized, c1.color_cast());
     }
+
+    #[test]
+    fn test_lightness() {
+        use crate::color::Lightness;
+
+        let c1 = Lab::<_, D65>::new(50.0, -20.0, 35.0);
+        assert_eq!(c1.lightness(), 50.0);
+        assert_eq!(c1.with_lightness(80.0), Lab::new(80.0, -20.0, 35.0));
+        let c2 = Luv::<_, D65>::new(30.0, 10.0, -60.0);
+        assert_eq!(c2.lightness(), 30.0);
+        assert_eq!(c2.with_lightness(5.0), Luv::new(5.0, 10.0, -60.0));
+    }
+
+    #[test]
+    fn test_rgb_to_lab_in_space() {
+        use crate::color_space::named::{DciP3, SRgb};
+        use crate::convert::FromColor;
+        use crate::lchab::Lchab;
+
+        let white = Rgb::new(1.0, 1.0, 1.0);
+        let srgb = SRgb::new();
+        assert_relative_eq!(
+            rgb_to_lab_in_space(&white, &srgb),
+            Lab::new(100.0, 0.0, 0.0),
+            epsilon = 1e-6
+        );
+        let dci = DciP3::new();
+        assert_relative_eq!(
+            rgb_to_lab_in_space(&white, &dci),
+            Lab::new(100.0, 0.0, 0.0),
+            epsilon = 1e-4
+        );
+
+        // Clipping a D65 color into the space adapts back to the space's white
+        let rgb = Rgb::new(0.8, 0.3, 0.6);
+        let lch: Lchab<f64, D65> = Lchab::from_color(&rgb_to_lab_in_space(&rgb, &dci));
+        let (round_trip, delta) = lch.clip_with_delta(&dci);
+        assert_eq!(delta, 0.0);
+        assert_relative_eq!(round_trip, rgb, epsilon = 1e-9);
+    }
 }
@@ -4,13 +4,18 @@
 
 use crate::channel::{
     AngularChannel, AngularChannelScalar, ChannelCast, ChannelFormatCast, ColorChannel,
-    FreeChannelScalar, PosFreeChannel,
+    FreeChannelScalar, PosFreeChannel, PosNormalChannelScalar,
 };
 use crate::color::{Bounded, Color, FromTuple, Lerp, PolarColor};
+use crate::color_space::ColorSpace;
 use crate::convert::{FromColor, GetChroma, GetHue};
+use crate::encoding::EncodableColor;
 use crate::lab::Lab;
+use crate::lms::bradford_adaptation;
+use crate::rgb::Rgb;
 use crate::tags::LchabTag;
-use crate::white_point::{UnitWhitePoint, WhitePoint};
+use crate::white_point::{DefaultWhitePoint, UnitWhitePoint, WhitePoint, D65};
+use crate::xyz::Xyz;
 use angle::{Angle, Deg, FromAngle, IntoAngle, Rad};
 #[cfg(feature = "approx")]
 use approx;
@@ -31,16 +36,24 @@ use std::fmt;
 ///
 /// It is a useful space for computing smooth gradients in a polar space, but like `Lab` is out of gamut
 /// for many values which are not bounded by a simple geometric object.
+///
+/// The white point parameter `W` defaults to
+/// [`DefaultWhitePoint`](white_point/type.DefaultWhitePoint.html) and the hue to degrees, so
+/// `Lchab<f64>` is a D65 color with its hue in degrees. Defaults are not used when inferring
+/// types; use [`LchabD65`](type.LchabD65.html) to construct without naming the white point.
 #[repr(C)]
 #[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub struct Lchab<T, W, A = Deg<T>> {
+pub struct Lchab<T, W = DefaultWhitePoint, A = Deg<T>> {
     L: PosFreeChannel<T>,
     chroma: PosFreeChannel<T>,
     hue: AngularChannel<A>,
     white_point: W,
 }
 
+/// An `Lchab` color with a D65 white point, for constructing without naming the white point
+pub type LchabD65<T, A = Deg<T>> = Lchab<T, D65, A>;
+
 impl<T, W, A> Lchab<T, W, A>
 where
     T: FreeChannelScalar,
@@ -51,6 +64,9 @@ where
     ///
     /// Unlike `new_with_whitepoint`, `new` constructs a default instance of a [`UnitWhitePoint`](white_point/trait.UnitWhitePoint.html).
     /// It is only valid when `W` is a `UnitWhitePoint`.
+    ///
+    /// The hue is stored as given, so `new` accepts angles outside of a single turn. Use
+    /// [`normalize_hue`](#method.normalize_hue) to wrap it eagerly.
     pub const fn new(L: T, chroma: T, hue: A) -> Self {
         Lchab {
             L: PosFreeChannel::new_const(L),
@@ -59,6 +75,14 @@ where
             white_point: W::DEFAULT,
         }
     }
+
+    impl_color_checked_new!(
+        fn new_checked(L: T, chroma: T, hue: A) {
+            crate::channel::debug_check_angle(&hue);
+        }
+    );
+
+    impl_color_from_hue_units_angular!(Lchab { L, chroma, hue });
 }
 
 impl<T, W, A> Lchab<T, W, A>
@@ -127,12 +151,16 @@ where
     }
     /// Sets the `hue` channel scalar
     pub fn set_hue(&mut self, val: A) {
+        crate::channel::debug_check_angle(&val);
         self.hue.0 = val;
     }
     /// Returns a reference to the white point for the `Lchab` color space
     pub fn white_point(&self) -> &W {
         &self.white_point
     }
+
+    impl_color_complement_angular!(Lchab);
+    impl_color_normalize_hue_angular!(Lchab);
 }
 
 impl<T, W, A> Color for Lchab<T, W, A>
@@ -282,6 +310,139 @@ where
     impl_color_get_hue_angular!(Lchab);
 }
 
+impl<T, W, A> Lchab<T, W, A>
+where
+    T: FreeChannelScalar + PosNormalChannelScalar + ChannelFormatCast<f64>,
+    f64: ChannelFormatCast<T>,
+    A: AngularChannelScalar + Angle<Scalar = T>,
+    W: WhitePoint<T>,
+{
+    /// Clip `self` into the gamut of `space`, returning the clipped color and how far it moved
+    ///
+    /// Colors outside of the gamut have their chroma reduced, keeping the lightness and hue, until
+    /// they fit. Colors that are too light or dark to be displayed at any chroma are clamped
+    /// afterwards. The returned `Rgb` value is encoded with the space's own encoding, and the
+    /// scalar is the CIEDE2000 difference between `self` and the clipped color, which is zero if
+    /// `self` was already in gamut.
+    ///
+    /// If the white point of `self` differs from that of `space`, colors are adapted between them
+    /// with the Bradford transform, the same adaptation used by
+    /// [`convert_buffer`](color_space/fn.convert_buffer.html).
+    pub fn clip_with_delta<S>(&self, space: &S) -> (Rgb<T>, T)
+    where
+        S: ColorSpace<T>,
+    {
+        let to_linear = |chroma: T| self.linear_with_chroma(chroma, space);
+        let in_gamut = Self::linear_in_gamut;
+        let encode = |rgb: Rgb<T>| -> Rgb<T> {
+            rgb.normalize()
+                .linear()
+                .encode(space.encoding())
+                .strip_encoding()
+        };
+
+        let linear = to_linear(self.chroma());
+        if in_gamut(&linear) {
+            return (encode(linear), T::zero());
+        }
+
+        let (mut lo, mut hi) = (T::zero(), self.chroma());
+        let half: T = num_traits::cast(0.5).unwrap();
+        for _ in 0..50 {
+            let mid = (lo + hi) * half;
+            if in_gamut(&to_linear(mid)) {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+        let clipped = to_linear(lo).normalize();
+
+        let adaptation = bradford_adaptation(&space.white_point(), &self.white_point.get_xyz());
+        let (x, y, z) = (adaptation * space.get_xyz_transform())
+            .transform_vector(clipped.clone().to_tuple());
+        let clipped_xyz = Xyz::new(x, y, z);
+        let clipped_lab = Lab::from_xyz(&clipped_xyz, self.white_point.clone());
+        let delta = Lab::from_color(self).ciede2000(&clipped_lab);
+
+        (encode(clipped), delta)
+    }
+
+    /// Returns the largest chroma that `space` can display at the lightness and hue of `self`
+    ///
+    /// Returns zero if the lightness is outside of what `space` can display at all. Like
+    /// [`clip_with_delta`](#method.clip_with_delta), colors are adapted from the white point of
+    /// `self` to that of `space` with the Bradford transform.
+    pub fn max_chroma<S>(&self, space: &S) -> T
+    where
+        S: ColorSpace<T>,
+    {
+        if !Self::linear_in_gamut(&self.linear_with_chroma(T::zero(), space)) {
+            return T::zero();
+        }
+        // Grow the upper bound until it leaves the gamut, then bisect
+        let mut lo = T::zero();
+        let mut hi: T = num_traits::cast(100.0).unwrap();
+        let two: T = num_traits::cast(2.0).unwrap();
+        for _ in 0..16 {
+            if !Self::linear_in_gamut(&self.linear_with_chroma(hi, space)) {
+                break;
+            }
+            lo = hi;
+            hi = hi * two;
+        }
+        let half: T = num_traits::cast(0.5).unwrap();
+        for _ in 0..50 {
+            let mid = (lo + hi) * half;
+            if Self::linear_in_gamut(&self.linear_with_chroma(mid, space)) {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+        lo
+    }
+
+    /// Returns the chroma of `self` as a fraction of [`max_chroma`](#method.max_chroma)
+    ///
+    /// This normalizes colorfulness across lightness and hue: a color on the boundary of the gamut
+    /// of `space` reports one and a gray reports zero, whatever their lightness. The result is
+    /// clamped into $`[0, 1]`$, so colors outside of the gamut also report one. Colors whose
+    /// lightness cannot be displayed at all report zero.
+    pub fn relative_chroma<S>(&self, space: &S) -> T
+    where
+        S: ColorSpace<T>,
+    {
+        let max = self.max_chroma(space);
+        if max <= T::zero() {
+            return T::zero();
+        }
+        (self.chroma() / max).min(T::one())
+    }
+
+    /// Returns the linear `Rgb` value in `space` of `self` with its chroma replaced by `chroma`
+    fn linear_with_chroma<S>(&self, chroma: T, space: &S) -> Rgb<T>
+    where
+        S: ColorSpace<T>,
+    {
+        let adaptation = bradford_adaptation(&self.white_point.get_xyz(), &space.white_point());
+        let lch =
+            Lchab::new_with_whitepoint(self.L(), chroma, self.hue(), self.white_point.clone());
+        let xyz = Lab::from_color(&lch).to_xyz();
+        let (r, g, b) =
+            (space.get_inverse_xyz_transform() * adaptation).transform_vector(xyz.to_tuple());
+        Rgb::new(r, g, b)
+    }
+
+    fn linear_in_gamut(rgb: &Rgb<T>) -> bool {
+        let tolerance: T = num_traits::cast(1e-9).unwrap();
+        let (r, g, b) = rgb.clone().to_tuple();
+        [r, g, b]
+            .iter()
+            .all(|&c| c >= -tolerance && c <= T::one() + tolerance)
+    }
+}
+
 impl<T, W, A> FromColor<Lab<T, W>> for Lchab<T, W, A>
 where
     T: FreeChannelScalar,
@@ -323,10 +484,38 @@ where
 mod test {
     use super::*;
     use crate::lab::Lab;
+    use crate::lchuv::Lchuv;
     use crate::white_point::*;
     use angle::Turns;
     use approx::*;
 
+    #[test]
+    fn test_default_white_point() {
+        let c1: Lchab<f64> = Lchab::new(50.0, 30.0, Deg(120.0));
+        assert_eq!(c1, Lchab::<_, D65>::new(50.0, 30.0, Deg(120.0)));
+        let c2: Lchuv<f64> = Lchuv::new(50.0, 30.0, Deg(120.0));
+        assert_eq!(c2, Lchuv::<_, D65>::new(50.0, 30.0, Deg(120.0)));
+    }
+
+    #[test]
+    fn test_from_turns_radians() {
+        let c1: Lchab<f64> = Lchab::from_turns(50.0, 30.0, 0.25);
+        assert_relative_eq!(c1, Lchab::new(50.0, 30.0, Deg(90.0)), epsilon = 1e-12);
+        let c2: Lchuv<f64> = Lchuv::from_radians(50.0, 30.0, std::f64::consts::PI);
+        assert_relative_eq!(c2, Lchuv::new(50.0, 30.0, Deg(180.0)), epsilon = 1e-12);
+        let c3: Lchab<f64> = Lchab::from_scalar_hue(50.0, 30.0, 0.25, crate::AngleUnit::Turns);
+        assert_relative_eq!(c3, c1, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_normalize_hue() {
+        let c1 = Lchab::<_, D65, _>::new(40.0, 120.0, Deg(725.0));
+        let c2 = c1.normalize_hue();
+        assert_relative_eq!(c2.hue(), Deg(5.0), epsilon = 1e-9);
+        assert_relative_eq!(c2.L(), 40.0);
+        assert_relative_eq!(c2.chroma(), 120.0);
+    }
+
     #[test]
     fn test_construct() {
         let c1 = Lchab::<_, D65, _>::new(55.3, 12.9, Deg(90.0));
@@ -344,6 +533,110 @@ mod test {
         assert_relative_eq!(Lchab::from_tuple(c2.to_tuple()), c2);
     }
 
+    #[test]
+    fn test_clip_with_delta() {
+        use crate::color_space::named::SRgb;
+        use crate::color_space::ConvertToXyz;
+        use crate::encoding::EncodedColor;
+        let space = SRgb::new();
+
+        let rgb = Rgb::new(0.8, 0.4, 0.2);
+        let xyz = space.convert_to_xyz(&EncodedColor::new(rgb, space.encoding()));
+        let lch: Lchab<f64, D65> = Lchab::from_color(&Lab::from_xyz(&xyz, D65));
+        let (clipped, delta) = lch.clip_with_delta(&space);
+        assert_eq!(delta, 0.0);
+        assert_relative_eq!(clipped, rgb, epsilon = 1e-6);
+
+        let vivid = Lchab::<f64, D65>::new(60.0, 140.0, Deg(150.0));
+        let (clipped, delta) = vivid.clip_with_delta(&space);
+        assert!(delta > 1.0);
+        assert!(clipped.is_normalized());
+        // The lightness is kept while the chroma is reduced
+        let xyz = space.convert_to_xyz(&EncodedColor::new(clipped, space.encoding()));
+        let clipped_lch: Lchab<f64, D65> = Lchab::from_color(&Lab::from_xyz(&xyz, D65));
+        assert_relative_eq!(clipped_lch.L(), 60.0, epsilon = 1e-4);
+        assert!(clipped_lch.chroma() < 140.0);
+        assert_relative_eq!(clipped_lch.hue(), Deg(150.0), epsilon = 1e-3);
+
+        let too_light = Lchab::<f64, D65>::new(120.0, 0.0, Deg(0.0));
+        let (clipped, delta) = too_light.clip_with_delta(&space);
+        assert_relative_eq!(clipped, Rgb::new(1.0, 1.0, 1.0), epsilon = 1e-9);
+        assert!(delta > 0.0);
+
+        // A D50 color is adapted into the D65 space, and the delta is measured back in D50
+        let to_d50 = bradford_adaptation(&space.white_point(), &D50.get_xyz());
+        let vivid = Lchab::<f64, D50>::new(60.0, 140.0, Deg(150.0));
+        let (clipped, delta) = vivid.clip_with_delta(&space);
+        let xyz = space.convert_to_xyz(&EncodedColor::new(clipped, space.encoding()));
+        let (x, y, z) = to_d50.transform_vector(xyz.to_tuple());
+        let clipped_lab = Lab::from_xyz(&Xyz::new(x, y, z), D50);
+        assert_relative_eq!(clipped_lab.L(), 60.0, epsilon = 1e-4);
+        assert_relative_eq!(
+            delta,
+            Lab::from_color(&vivid).ciede2000(&clipped_lab),
+            epsilon = 1e-6
+        );
+    }
+
+    #[test]
+    fn test_relative_chroma() {
+        use crate::color_space::named::SRgb;
+        use crate::color_space::ConvertToXyz;
+        use crate::encoding::EncodedColor;
+        let space = SRgb::new();
+        let to_lch = |rgb: Rgb<f64>| -> Lchab<f64, D65> {
+            let xyz = space.convert_to_xyz(&EncodedColor::new(rgb, space.encoding()));
+            Lchab::from_color(&Lab::from_xyz(&xyz, D65))
+        };
+
+        let red = to_lch(Rgb::new(1.0, 0.0, 0.0));
+        assert_relative_eq!(red.max_chroma(&space), red.chroma(), epsilon = 1e-6);
+        assert_relative_eq!(red.relative_chroma(&space), 1.0, epsilon = 1e-6);
+        let blue = to_lch(Rgb::new(0.0, 0.0, 1.0));
+        assert_relative_eq!(blue.relative_chroma(&space), 1.0, epsilon = 1e-6);
+        let boundary = to_lch(Rgb::new(1.0, 0.6, 0.3));
+        assert_relative_eq!(boundary.relative_chroma(&space), 1.0, epsilon = 1e-6);
+
+        let muted = to_lch(Rgb::new(0.6, 0.45, 0.4));
+        let relative = muted.relative_chroma(&space);
+        assert!(relative > 0.0 && relative < 1.0);
+
+        for &L in [0.0, 20.0, 50.0, 90.0, 100.0].iter() {
+            let gray = Lchab::<f64, D65>::new(L, 0.0, Deg(120.0));
+            assert_eq!(gray.relative_chroma(&space), 0.0);
+        }
+
+        let vivid = Lchab::<f64, D65>::new(60.0, 140.0, Deg(150.0));
+        assert!(vivid.max_chroma(&space) < 140.0);
+        assert_eq!(vivid.relative_chroma(&space), 1.0);
+        let too_light = Lchab::<f64, D65>::new(120.0, 10.0, Deg(0.0));
+        assert_eq!(too_light.max_chroma(&space), 0.0);
+        assert_eq!(too_light.relative_chroma(&space), 0.0);
+    }
+
+    #[test]
+    fn test_max_chroma_adapts_white() {
+        use crate::color_space::named::SRgb;
+        use crate::color_space::ConvertToXyz;
+        use crate::encoding::EncodedColor;
+        let space = SRgb::new();
+        let to_d50 = bradford_adaptation(&space.white_point(), &D50.get_xyz());
+        let to_lch = |rgb: Rgb<f64>| -> Lchab<f64, D50> {
+            let xyz = space.convert_to_xyz(&EncodedColor::new(rgb, space.encoding()));
+            let (x, y, z) = to_d50.transform_vector(xyz.to_tuple());
+            Lchab::from_color(&Lab::from_xyz(&Xyz::new(x, y, z), D50))
+        };
+
+        let red = to_lch(Rgb::new(1.0, 0.0, 0.0));
+        assert_relative_eq!(red.max_chroma(&space), red.chroma(), epsilon = 1e-6);
+        let (clipped, delta) = red.clip_with_delta(&space);
+        assert_relative_eq!(delta, 0.0, epsilon = 1e-6);
+        assert_relative_eq!(clipped, Rgb::new(1.0, 0.0, 0.0), epsilon = 1e-6);
+
+        let gray = to_lch(Rgb::new(0.5, 0.5, 0.5));
+        assert_relative_eq!(gray.chroma(), 0.0, epsilon = 1e-9);
+    }
+
     #[test]
     fn test_lerp() {
         let c1 = Lchab::<_, D65, _>::new(25.0, 90.0, Deg(300.0));
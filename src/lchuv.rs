@@ -8,9 +8,11 @@ use crate::channel::{
 };
 use crate::color::{Bounded, Color, FromTuple, Lerp, PolarColor};
 use crate::convert::{FromColor, GetChroma, GetHue};
+use crate::lab::Lab;
+use crate::lchab::Lchab;
 use crate::luv::Luv;
 use crate::tags::LchuvTag;
-use crate::white_point::{UnitWhitePoint, WhitePoint};
+use crate::white_point::{DefaultWhitePoint, UnitWhitePoint, WhitePoint, D65};
 use angle::{Angle, Deg, FromAngle, IntoAngle, Rad};
 #[cfg(feature = "approx")]
 use approx;
@@ -31,16 +33,24 @@ use std::fmt;
 ///
 /// It is a useful space for computing smooth gradients in a polar space, but like `Luv` is out of gamut
 /// for many values which are not bounded by a simple geometric object.
+///
+/// The white point parameter `W` defaults to
+/// [`DefaultWhitePoint`](white_point/type.DefaultWhitePoint.html) and the hue to degrees, so
+/// `Lchuv<f64>` is a D65 color with its hue in degrees. Defaults are not used when inferring
+/// types; use [`LchuvD65`](type.LchuvD65.html) to construct without naming the white point.
 #[repr(C)]
 #[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub struct Lchuv<T, W, A = Deg<T>> {
+pub struct Lchuv<T, W = DefaultWhitePoint, A = Deg<T>> {
     L: PosFreeChannel<T>,
     chroma: PosFreeChannel<T>,
     hue: AngularChannel<A>,
     white_point: W,
 }
 
+/// An `Lchuv` color with a D65 white point, for constructing without naming the white point
+pub type LchuvD65<T, A = Deg<T>> = Lchuv<T, D65, A>;
+
 impl<T, W, A> Lchuv<T, W, A>
 where
     T: FreeChannelScalar,
@@ -51,6 +61,9 @@ where
     ///
     /// Unlike `new_with_whitepoint`, `new` constructs a default instance of a [`UnitWhitePoint`](white_point/trait.UnitWhitePoint.html).
     /// It is only valid when `W` is a `UnitWhitePoint`.
+    ///
+    /// The hue is stored as given, so `new` accepts angles outside of a single turn. Use
+    /// [`normalize_hue`](#method.normalize_hue) to wrap it eagerly.
     pub const fn new(L: T, chroma: T, hue: A) -> Self {
         Lchuv {
             L: PosFreeChannel::new_const(L),
@@ -59,6 +72,14 @@ where
             white_point: W::DEFAULT,
         }
     }
+
+    impl_color_checked_new!(
+        fn new_checked(L: T, chroma: T, hue: A) {
+            crate::channel::debug_check_angle(&hue);
+        }
+    );
+
+    impl_color_from_hue_units_angular!(Lchuv { L, chroma, hue });
 }
 
 impl<T, W, A> Lchuv<T, W, A>
@@ -127,12 +148,16 @@ where
     }
     /// Sets the `hue` channel scalar
     pub fn set_hue(&mut self, val: A) {
+        crate::channel::debug_check_angle(&val);
         self.hue.0 = val;
     }
     /// Returns a reference to the white point for the `Lchab` color space
     pub fn white_point(&self) -> &W {
         &self.white_point
     }
+
+    impl_color_complement_angular!(Lchuv);
+    impl_color_normalize_hue_angular!(Lchuv);
 }
 
 impl<T, W, A> Color for Lchuv<T, W, A>
@@ -314,6 +339,38 @@ where
     }
 }
 
+impl<T, W, A> FromColor<Lchuv<T, W, A>> for Lchab<T, W, A>
+where
+    T: FreeChannelScalar + fmt::Display,
+    A: AngularChannelScalar + FromAngle<Rad<T>> + Angle<Scalar = T>,
+    W: WhitePoint<T>,
+{
+    /// Construct an `Lchab` value from an `Lchuv` value, by way of XYZ
+    ///
+    /// Both colors use the same white point, so no chromatic adaptation is needed.
+    fn from_color(from: &Lchuv<T, W, A>) -> Self {
+        let xyz = Luv::from_color(from).to_xyz();
+        let lab = Lab::from_xyz(&xyz, from.white_point().clone());
+        Lchab::from_color(&lab)
+    }
+}
+
+impl<T, W, A> FromColor<Lchab<T, W, A>> for Lchuv<T, W, A>
+where
+    T: FreeChannelScalar + fmt::Display,
+    A: AngularChannelScalar + FromAngle<Rad<T>> + Angle<Scalar = T>,
+    W: WhitePoint<T>,
+{
+    /// Construct an `Lchuv` value from an `Lchab` value, by way of XYZ
+    ///
+    /// Both colors use the same white point, so no chromatic adaptation is needed.
+    fn from_color(from: &Lchab<T, W, A>) -> Self {
+        let xyz = Lab::from_color(from).to_xyz();
+        let luv = Luv::from_xyz(&xyz, from.white_point().clone());
+        Lchuv::from_color(&luv)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -332,6 +389,32 @@ mod test {
         assert_relative_eq!(Lchuv::from_tuple(c1.to_tuple()), c1);
     }
 
+    #[test]
+    fn test_lchab_round_trip() {
+        let colors = [
+            Lchab::<f64, D65>::new(50.0, 40.0, Deg(30.0)),
+            Lchab::new(85.0, 70.0, Deg(95.0)),
+            Lchab::new(30.0, 60.0, Deg(290.0)),
+        ];
+        for color in colors.iter() {
+            let lchuv = Lchuv::from_color(color);
+            assert_relative_eq!(lchuv.L(), color.L(), epsilon = 1e-9);
+            let back = Lchab::from_color(&lchuv);
+            assert_relative_eq!(back, *color, epsilon = 1e-5);
+        }
+
+        // The hue of a neutral color is meaningless, so only the lightness and chroma survive
+        let gray = Lchab::<f64, D65>::new(65.0, 0.0, Deg(0.0));
+        let back = Lchab::from_color(&Lchuv::from_color(&gray));
+        assert_relative_eq!(back.L(), 65.0, epsilon = 1e-9);
+        assert_relative_eq!(back.chroma(), 0.0, epsilon = 1e-5);
+
+        let xyz = Lab::<f64, D50>::new(40.0, -30.0, 20.0).to_xyz();
+        let lchuv: Lchuv<f64, D50> = Lchuv::from_color(&Luv::from_xyz(&xyz, D50));
+        let lchab: Lchab<f64, D50> = Lchab::from_color(&lchuv);
+        assert_relative_eq!(Lab::from_color(&lchab).to_xyz(), xyz, epsilon = 1e-7);
+    }
+
     #[test]
     fn test_lerp() {
         let c1 = Lchuv::<_, D65, _>::new(50.0, 70.0, Deg(120.0));
@@ -31,10 +31,13 @@
 //! * **[`Rgi`](struct.Rgi.html)** - A chromaticity model constructed from Rgb that decouples chromaticity and lightness
 //! * **[`Hsv`](struct.Hsv.html)** - Hue, saturation, value: a more intuitive polar Rgb model
 //! * **[`Hsl`](struct.Hsl.html)** - Hue, saturation, lightness: an alternate to Hsv fulfilling similar roles
+//! * **[`Hsp`](struct.Hsp.html)** - Hue, saturation, perceived brightness: Hsv with a brightness that tracks apparent lightness
 //! * **[`Hsi`](struct.Hsi.html)** - Hue, saturation, intensity: a hue-based model without distortion
 //! * **[`eHsi`](struct.eHsi.html)** - An extension to `Hsi` that rescaled saturation to avoid going out of gamut in Rgb
 //! * **[`Hwb`](struct.Hwb.html)** - Hue, whiteness, blackness: a hue-based model made to be easy for users to select colors in
 //! * **[`YCbCr`](ycbcr/struct.YCbCr.html)** - A representation of the various YUV and YIQ models used in display and broadcast
+//! * **[`YCoCg`](struct.YCoCg.html)** - A cheap, exactly invertible luma and chroma model used in compression
+//! * **[`OpponentLog`](struct.OpponentLog.html)** - An experimental `YCoCg` variant with logarithmically compressed chroma
 //!
 //! #### Device Independent:
 //! * **[`Xyz`](struct.Xyz.html)** - The "parent" absolute color space other color spaces are defined in terms of
@@ -169,22 +172,41 @@ extern crate angular_units as angle;
 #[macro_use]
 mod impl_macros;
 
+pub mod analysis;
 pub mod channel;
 mod linalg;
 
 pub mod color_space;
+pub mod easing;
 pub mod encoding;
+pub mod palette;
+pub mod spectral;
 pub mod tags;
+pub mod vision;
 pub mod white_point;
 
+mod accumulator;
 mod alpha;
+mod angle_unit;
+#[cfg(feature = "ase")]
+pub mod ase;
+#[cfg(feature = "bench")]
+pub mod bench;
 mod chromaticity;
 mod color;
 mod convert;
+mod discrete;
+#[cfg(feature = "gpl")]
+pub mod gpl;
+#[cfg(feature = "glam")]
+mod glam_interop;
+#[cfg(feature = "mint")]
+mod mint_interop;
 
 mod ehsi;
 mod hsi;
 mod hsl;
+mod hsp;
 mod hsv;
 mod hwb;
 mod lab;
@@ -192,37 +214,52 @@ mod lchab;
 mod lchuv;
 pub mod lms;
 mod luv;
+mod mix;
+mod opponent_log;
+mod parse;
+mod premultiplied;
 mod rgb;
 mod rgi;
 mod xyy;
 mod xyz;
 pub mod ycbcr;
+mod ycocg;
 
 #[cfg(test)]
 pub mod test;
 
 pub use crate::color::{
-    Bounded, Broadcast, Color, Color3, Color4, DeviceDependentColor, Flatten, FromTuple,
-    HomogeneousColor, Invert, Lerp, PolarColor,
+    Bounded, Broadcast, Color, Color3, Color4, DeviceDependentColor, Flatten, FlattenMut,
+    FromTuple, HomogeneousColor, Invert, Lerp, Lightness, PolarColor,
 };
 
+pub use crate::accumulator::ColorAccumulator;
 pub use crate::alpha::{
-    eHsia, Alpha, Hsia, Hsla, Hsva, Hwba, Laba, Lchaba, Lchauv, Lmsa, Luva, Rgba, Rgia, XyYa, Xyza,
-    YCbCra,
+    composite_xyya, eHsia, mean_opaque, over_u8, Alpha, Hsia, Hsla, Hspa, Hsva, Hwba, Laba, Lchaba,
+    Lchauv, Lmsa, Luva, Rgba, Rgia, XyYa, Xyza, YCbCra, YCoCga,
 };
+pub use crate::angle_unit::AngleUnit;
 pub use crate::chromaticity::ChromaticityCoordinates;
 pub use crate::convert::{FromColor, FromHsi, FromYCbCr};
+pub use crate::discrete::DiscreteColor;
 pub use crate::ehsi::eHsi;
 pub use crate::hsi::{Hsi, HsiOutOfGamutMode};
 pub use crate::hsl::Hsl;
-pub use crate::hsv::Hsv;
+pub use crate::hsp::Hsp;
+pub use crate::hsv::{hsv_u8_to_rgb, Hsv};
 pub use crate::hwb::{Hwb, HwbBoundedChannelTraits};
-pub use crate::lab::Lab;
-pub use crate::lchab::Lchab;
-pub use crate::lchuv::Lchuv;
+pub use crate::lab::{Lab, LabD65};
+pub use crate::lchab::{Lchab, LchabD65};
+pub use crate::lchuv::{Lchuv, LchuvD65};
 pub use crate::linalg::Matrix3;
-pub use crate::luv::Luv;
-pub use crate::rgb::Rgb;
+pub use crate::luv::{Luv, LuvD65};
+pub use crate::mix::{mix_in, Gradient, MixSpace};
+pub use crate::opponent_log::OpponentLog;
+pub use crate::parse::{parse_color_function, CssSpacedColor, ParseColorError};
+pub use crate::premultiplied::PremultipliedRgba;
+pub use crate::rgb::{apca_contrast, relight, rgb_slice_to_hsv, Rgb, RgbChannel};
 pub use crate::rgi::Rgi;
-pub use crate::xyy::XyY;
+pub use crate::white_point::DefaultWhitePoint;
+pub use crate::xyy::{XyY, XyYError};
 pub use crate::xyz::Xyz;
+pub use crate::ycocg::{YCoCg, YCoCgR};
@@ -52,12 +52,27 @@ where
         Matrix3::new([one, zero, zero, zero, one, zero, zero, zero, one])
     }
 
+    /// Construct a diagonal matrix with `x`, `y` and `z` along the diagonal
+    ///
+    /// Transforming a vector by this matrix scales each component independently.
+    #[inline]
+    pub fn diagonal(x: T, y: T, z: T) -> Self {
+        let zero = T::zero();
+        Matrix3::new([x, zero, zero, zero, y, zero, zero, zero, z])
+    }
+
     /// Construct a new `Matrix3` with all values set to `val`
     #[inline]
     pub fn broadcast(val: T) -> Self {
         Matrix3 { m: [val; 9] }
     }
 
+    /// Return a new `Matrix3` with every element multiplied by `s`
+    #[inline]
+    pub fn scale(&self, s: T) -> Self {
+        *self * s
+    }
+
     /// Return a slice to the elements in the matrix
     #[inline]
     pub fn as_slice(&self) -> &[T] {
@@ -464,6 +479,27 @@ mod test {
         assert_eq!(m6 * Matrix3::identity(), m6);
     }
 
+    #[test]
+    fn test_constructors() {
+        let v = (0.25f32, -1.5, 3.0);
+        assert_eq!(Matrix3::<f32>::identity().transform_vector(v), v);
+        assert_eq!(Matrix3::<f32>::zero().transform_vector(v), (0.0, 0.0, 0.0));
+        assert_eq!(
+            Matrix3::diagonal(2.0, 2.0, 2.0).transform_vector(v),
+            (0.5, -3.0, 6.0)
+        );
+        assert_eq!(
+            Matrix3::diagonal(1.0, 0.5, -1.0).transform_vector(v),
+            (0.25, -0.75, -3.0)
+        );
+        assert_eq!(Matrix3::diagonal(1, 1, 1), Matrix3::identity());
+        assert_eq!(
+            Matrix3::<f32>::identity().scale(2.0),
+            Matrix3::diagonal(2.0, 2.0, 2.0)
+        );
+        assert_eq!(Matrix3::broadcast(3i32).scale(0), Matrix3::zero());
+    }
+
     #[test]
     fn test_add() {
         let m1 = Matrix3::broadcast(1.0) + Matrix3::broadcast(2.0);
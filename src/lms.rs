@@ -6,7 +6,7 @@
 use crate::channel::{
     ChannelCast, ChannelFormatCast, ColorChannel, FreeChannel, FreeChannelScalar,
 };
-use crate::color::{Bounded, Broadcast, Color, Flatten, FromTuple, HomogeneousColor, Lerp};
+use crate::color::{Bounded, Broadcast, Color, Flatten, FlattenMut, FromTuple, HomogeneousColor, Lerp};
 use crate::convert::FromColor;
 use crate::linalg::Matrix3;
 use crate::tags::LmsTag;
@@ -207,6 +207,14 @@ where
         s:FreeChannel - 2});
 }
 
+impl<T, Model> FlattenMut for Lms<T, Model>
+where
+    T: FreeChannelScalar,
+    Model: LmsModel<T>,
+{
+    impl_color_as_slice_mut!(T);
+}
+
 #[cfg(feature = "approx")]
 impl<T, Model> approx::AbsDiffEq for Lms<T, Model>
 where
@@ -383,6 +391,21 @@ where
     }
 }
 
+/// Returns the XYZ to XYZ matrix adapting colors seen under `src_white` to `dst_white`
+///
+/// This is a von Kries transform in the Bradford `LMS` space: each cone response is scaled by the
+/// ratio of the two white points' responses. `src_white` maps exactly onto `dst_white`.
+pub(crate) fn bradford_adaptation<T>(src_white: &Xyz<T>, dst_white: &Xyz<T>) -> Matrix3<T>
+where
+    T: FreeChannelScalar,
+{
+    let bradford = <Bradford as LmsModel<T>>::forward_transform();
+    let (src_l, src_m, src_s) = bradford.transform_vector(src_white.clone().to_tuple());
+    let (dst_l, dst_m, dst_s) = bradford.transform_vector(dst_white.clone().to_tuple());
+    let scale = Matrix3::diagonal(dst_l / src_l, dst_m / src_m, dst_s / src_s);
+    bradford.clone().inverse().unwrap() * scale * bradford
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -446,6 +469,29 @@ mod test {
         assert_relative_eq!(Xyz::from_color(&t4), c4, epsilon = 1e-4);
     }
 
+    #[test]
+    fn test_bradford_adaptation() {
+        use crate::white_point::{WhitePoint, D50, D65};
+        let d50: Xyz<f64> = D50.get_xyz();
+        let d65: Xyz<f64> = D65.get_xyz();
+        let adaptation = bradford_adaptation(&d50, &d65);
+        assert_relative_eq!(
+            adaptation,
+            Matrix3::new([
+                0.9555766, -0.0230393, 0.0631636, -0.0282895, 1.0099416, 0.0210077, 0.0122982,
+                -0.0204830, 1.3299098,
+            ]),
+            epsilon = 1e-4
+        );
+        let (x, y, z) = adaptation.transform_vector(d50.to_tuple());
+        assert_relative_eq!(Xyz::new(x, y, z), d65, epsilon = 1e-12);
+        assert_relative_eq!(
+            bradford_adaptation(&d65, &d65),
+            Matrix3::identity(),
+            epsilon = 1e-12
+        );
+    }
+
     #[test]
     fn test_to_xyz() {
         let c1 = LmsCam2002::new(0.25, 0.50, 0.75);
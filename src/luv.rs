@@ -4,8 +4,10 @@
 
 use crate::channel::{
     ChannelCast, ChannelFormatCast, ColorChannel, FreeChannel, FreeChannelScalar, PosFreeChannel,
+    PosNormalChannelScalar,
 };
-use crate::color::{Bounded, Broadcast, Color, FromTuple, HomogeneousColor, Lerp};
+use crate::color::{Bounded, Broadcast, Color, FromTuple, HomogeneousColor, Lerp, Lightness};
+use crate::parse::{self, ParseColorError};
 use crate::tags::LuvTag;
 use crate::xyz::Xyz;
 #[cfg(feature = "approx")]
@@ -13,7 +15,7 @@ use approx;
 use num_traits;
 use std::fmt;
 
-use crate::white_point::{UnitWhitePoint, WhitePoint};
+use crate::white_point::{DefaultWhitePoint, UnitWhitePoint, WhitePoint, D65};
 
 /// The CIELUV perceptually uniform device-independent color space
 ///
@@ -22,17 +24,25 @@ use crate::white_point::{UnitWhitePoint, WhitePoint};
 /// mixture of two lights falling along a line in its the chromaticity space. It is an extension to
 /// the previous CIE UVW space.
 ///
+/// The white point parameter `W` defaults to
+/// [`DefaultWhitePoint`](white_point/type.DefaultWhitePoint.html), so `Luv<f64>` is a D65 Luv
+/// color. As with `Lab`, the default is not used when inferring types; use
+/// [`LuvD65`](type.LuvD65.html) to construct without naming the white point.
+///
 /// Like `Lab`, `Luv` has a polar representation: [`Lchuv`](struct.Lchuv.html).
 #[repr(C)]
 #[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub struct Luv<T, W> {
+pub struct Luv<T, W = DefaultWhitePoint> {
     L: PosFreeChannel<T>,
     u: FreeChannel<T>,
     v: FreeChannel<T>,
     white_point: W,
 }
 
+/// A `Luv` color with a D65 white point, for constructing without naming the white point
+pub type LuvD65<T> = Luv<T, D65>;
+
 impl<T, W> Luv<T, W>
 where
     T: FreeChannelScalar,
@@ -121,6 +131,19 @@ where
     pub fn white_point(&self) -> &W {
         &self.white_point
     }
+
+    /// Scale the chroma of the color by `factor`, keeping the lightness and hue
+    ///
+    /// Both `u` and `v` are multiplied by `factor`, leaving $`L^*`$ unchanged. A factor of zero
+    /// gives the gray of the same lightness.
+    pub fn scale_chroma(self, factor: T) -> Self {
+        Luv {
+            L: self.L,
+            u: FreeChannel::new(self.u.0 * factor),
+            v: FreeChannel::new(self.v.0 * factor),
+            white_point: self.white_point,
+        }
+    }
 }
 
 impl<T, W> Color for Luv<T, W>
@@ -140,6 +163,22 @@ where
     }
 }
 
+impl<T, W> Lightness for Luv<T, W>
+where
+    T: FreeChannelScalar,
+    W: WhitePoint<T>,
+{
+    type LightnessFormat = T;
+
+    fn lightness(&self) -> T {
+        self.L()
+    }
+    fn with_lightness(mut self, lightness: T) -> Self {
+        self.set_L(lightness);
+        self
+    }
+}
+
 impl<T, W> FromTuple for Luv<T, W>
 where
     T: FreeChannelScalar,
@@ -352,6 +391,22 @@ where
     }
 }
 
+impl<T> Luv<T, D65>
+where
+    T: FreeChannelScalar + PosNormalChannelScalar + ChannelFormatCast<f64> + fmt::Display,
+    f64: ChannelFormatCast<T>,
+    u8: ChannelFormatCast<T>,
+{
+    /// Parse an sRgb hex string such as `"#3366cc"` directly into `Luv`
+    ///
+    /// The color is interpreted as sRgb encoded, taken to `Xyz` through the
+    /// [`SRgb`](color_space/named/struct.SRgb.html) color space and then to `Luv` relative to D65.
+    pub fn from_hex(hex: &str) -> Result<Self, ParseColorError> {
+        let xyz = parse::srgb_hex_to_xyz(hex)?;
+        Ok(Luv::from_xyz(&xyz, D65))
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -359,6 +414,15 @@ mod test {
     use crate::xyz::Xyz;
     use approx::*;
 
+    #[test]
+    fn test_scale_chroma() {
+        let c1 = Luv::<f64, D65>::new(45.0, -20.0, 35.0);
+        let c2 = c1.scale_chroma(0.5);
+        assert_eq!(c2.L(), 45.0);
+        assert_relative_eq!(c2.u().hypot(c2.v()), 0.5 * c1.u().hypot(c1.v()));
+        assert_relative_eq!(c2, Luv::new(45.0, -10.0, 17.5));
+    }
+
     #[test]
     fn test_construct() {
         let c1 = Luv::<_, D65>::new(82.00, -40.0, 60.0);
@@ -475,6 +539,18 @@ mod test {
         assert_relative_eq!(c1.color_cast::<f32>().color_cast(), c1, epsilon = 1e-5);
     }
 
+    #[test]
+    fn test_from_hex() {
+        let black = Luv::<f64, _>::from_hex("#000000").unwrap();
+        assert_relative_eq!(black, Luv::new(0.0, 0.0, 0.0), epsilon = 1e-4);
+        let white = Luv::<f64, _>::from_hex("#ffffff").unwrap();
+        assert_relative_eq!(white, Luv::new(100.0, 0.0, 0.0), epsilon = 1e-2);
+        assert_eq!(
+            Luv::<f64, D65>::from_hex("#fffffff"),
+            Err(ParseColorError::InvalidLength)
+        );
+    }
+
     #[cfg(feature = "serde")]
     #[test]
     fn test_serde() {
@@ -0,0 +1,62 @@
+//! Conversions between colors and `mint` vectors
+//!
+//! This module is only available with the `mint` feature enabled. `Rgb<f32>` converts to and
+//! from a `Vector3` holding the red, green and blue channels, and `Rgba<f32>` to and from a
+//! `Vector4` with alpha last. The channels are copied as they are, without any change of encoding.
+
+use crate::alpha::Rgba;
+use crate::rgb::Rgb;
+
+impl From<Rgb<f32>> for mint::Vector3<f32> {
+    fn from(color: Rgb<f32>) -> Self {
+        mint::Vector3 {
+            x: color.red(),
+            y: color.green(),
+            z: color.blue(),
+        }
+    }
+}
+
+impl From<mint::Vector3<f32>> for Rgb<f32> {
+    fn from(vec: mint::Vector3<f32>) -> Self {
+        Rgb::new(vec.x, vec.y, vec.z)
+    }
+}
+
+impl From<Rgba<f32>> for mint::Vector4<f32> {
+    fn from(color: Rgba<f32>) -> Self {
+        mint::Vector4 {
+            x: color.red(),
+            y: color.green(),
+            z: color.blue(),
+            w: color.alpha(),
+        }
+    }
+}
+
+impl From<mint::Vector4<f32>> for Rgba<f32> {
+    fn from(vec: mint::Vector4<f32>) -> Self {
+        Rgba::new(Rgb::new(vec.x, vec.y, vec.z), vec.w)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_mint_vector3() {
+        let color = Rgb::new(0.25f32, 0.5, 0.75);
+        let vec: mint::Vector3<f32> = color.into();
+        assert_eq!((vec.x, vec.y, vec.z), (0.25, 0.5, 0.75));
+        assert_eq!(Rgb::from(vec), color);
+    }
+
+    #[test]
+    fn test_mint_vector4() {
+        let color = Rgba::new(Rgb::new(0.1f32, 0.2, 0.3), 0.4);
+        let vec: mint::Vector4<f32> = color.into();
+        assert_eq!((vec.x, vec.y, vec.z, vec.w), (0.1, 0.2, 0.3, 0.4));
+        assert_eq!(Rgba::from(vec), color);
+    }
+}
@@ -0,0 +1,323 @@
+//! Mixing sRgb colors in a chosen working space
+//!
+//! [`mix_in`](fn.mix_in.html) interpolates between two colors, and
+//! [`Gradient`](struct.Gradient.html) between any number of colors placed along a line.
+
+use crate::channel::{ChannelFormatCast, FreeChannelScalar, PosNormalChannelScalar};
+use crate::color::{Bounded, Color, Lerp};
+use crate::color_space::named::SRgb;
+use crate::color_space::{ConvertFromXyz, ConvertToXyz};
+use crate::encoding::{EncodableColor, SrgbEncoding, TranscodableColor};
+use crate::lab::Lab;
+use crate::linalg::Matrix3;
+use crate::rgb::Rgb;
+use crate::white_point::D65;
+
+/// The working space two colors are interpolated in by [`mix_in`](fn.mix_in.html)
+///
+/// The choice of space has a large effect on what the midpoint of two colors looks like. Mixing
+/// black and white will give a mid gray in each, but of very different lightness.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum MixSpace {
+    /// Interpolate the sRgb encoded channels directly
+    ///
+    /// This is the same as calling `lerp` on the colors. It is fast, but midpoints tend to be dark
+    /// and muddy.
+    Srgb,
+    /// Interpolate linear light Rgb channels
+    ///
+    /// This is physically correct for blending light, but midpoints appear too bright.
+    Linear,
+    /// Interpolate in CIELAB with a D65 white point
+    Lab,
+    /// Interpolate in Oklab, a perceptual space with more uniform hue than CIELAB
+    Oklab,
+}
+
+/// Mix two sRgb encoded colors by interpolating in the working space `space`
+///
+/// `a` and `b` are converted into `space`, interpolated by `t` and converted back to sRgb. `t = 0`
+/// returns `a` and `t = 1` returns `b`.
+///
+/// Interpolating in `Lab` or `Oklab` can leave the sRgb gamut even when both inputs are inside it.
+/// The result is clamped into gamut in linear light before being re-encoded.
+pub fn mix_in<T>(a: &Rgb<T>, b: &Rgb<T>, t: T, space: MixSpace) -> Rgb<T>
+where
+    T: FreeChannelScalar + PosNormalChannelScalar + ChannelFormatCast<f64> + Lerp<Position = T>,
+    f64: ChannelFormatCast<T>,
+{
+    match space {
+        MixSpace::Srgb => a.lerp(b, t),
+        MixSpace::Linear => {
+            let linear_a = a.clone().srgb_encoded().decode().strip_encoding();
+            let linear_b = b.clone().srgb_encoded().decode().strip_encoding();
+            encode_linear(linear_a.lerp(&linear_b, t))
+        }
+        MixSpace::Lab => {
+            let srgb = SRgb::new();
+            let lab_a = Lab::from_xyz(&srgb.convert_to_xyz(&a.clone().srgb_encoded()), D65);
+            let lab_b = Lab::from_xyz(&srgb.convert_to_xyz(&b.clone().srgb_encoded()), D65);
+            let mixed = lab_a.lerp(&lab_b, t).to_xyz();
+            encode_linear(srgb.convert_from_xyz_raw(&mixed))
+        }
+        MixSpace::Oklab => {
+            let (l1, a1, b1) =
+                linear_srgb_to_oklab(a.clone().srgb_encoded().decode().strip_encoding());
+            let (l2, a2, b2) =
+                linear_srgb_to_oklab(b.clone().srgb_encoded().decode().strip_encoding());
+            let mixed = (l1 + (l2 - l1) * t, a1 + (a2 - a1) * t, b1 + (b2 - b1) * t);
+            encode_linear(oklab_to_linear_srgb(mixed))
+        }
+    }
+}
+
+/// A gradient through a list of sRgb color stops, interpolated in a chosen working space
+///
+/// Each stop places a color at a position along the gradient. Sampling between two stops mixes
+/// their colors with [`mix_in`](fn.mix_in.html) in the gradient's working space, so the choice of
+/// space changes the look of every segment. Gradients in [`MixSpace::Oklab`](enum.MixSpace.html),
+/// made with [`in_oklab`](#method.in_oklab), keep a steady lightness and saturation between stops,
+/// and are a good default for UI gradients.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Gradient {
+    stops: Vec<(f64, Rgb<f32>)>,
+    space: MixSpace,
+}
+
+impl Gradient {
+    /// Construct a gradient through `stops`, interpolated in `space`
+    ///
+    /// `stops` pairs a position with the sRgb encoded color at that position. They are sorted by
+    /// position, keeping the given order for stops at the same position. Two stops at the same
+    /// position make a hard edge.
+    ///
+    /// Panics:
+    /// =======
+    /// Panics if `stops` is empty or a position is not finite.
+    pub fn new(mut stops: Vec<(f64, Rgb<f32>)>, space: MixSpace) -> Self {
+        if stops.is_empty() {
+            panic!("A gradient must have at least one stop");
+        }
+        if stops.iter().any(|(position, _)| !position.is_finite()) {
+            panic!("Gradient stop positions must be finite");
+        }
+        stops.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+        Gradient { stops, space }
+    }
+
+    /// Construct a gradient through `stops`, interpolated in Oklab
+    ///
+    /// This is the same as `Gradient::new(stops, MixSpace::Oklab)`.
+    pub fn in_oklab(stops: Vec<(f64, Rgb<f32>)>) -> Self {
+        Gradient::new(stops, MixSpace::Oklab)
+    }
+
+    /// Returns the stops of the gradient, sorted by position
+    pub fn stops(&self) -> &[(f64, Rgb<f32>)] {
+        &self.stops
+    }
+
+    /// Returns the working space the gradient is interpolated in
+    pub fn space(&self) -> MixSpace {
+        self.space
+    }
+
+    /// Returns the sRgb encoded color of the gradient at `position`
+    ///
+    /// Positions before the first stop or after the last get the color of that stop. At a hard
+    /// edge, the color of the later stop is returned.
+    pub fn sample(&self, position: f64) -> Rgb<f32> {
+        let next = self.stops.iter().position(|stop| stop.0 > position);
+        match next {
+            Some(0) => self.stops[0].1,
+            None => self.stops[self.stops.len() - 1].1,
+            Some(index) => {
+                let (start, from) = self.stops[index - 1];
+                let (end, to) = self.stops[index];
+                let t = (position - start) / (end - start);
+                mix_in(&from, &to, t as f32, self.space)
+            }
+        }
+    }
+}
+
+fn encode_linear<T>(color: Rgb<T>) -> Rgb<T>
+where
+    T: PosNormalChannelScalar + ChannelFormatCast<f64>,
+    f64: ChannelFormatCast<T>,
+    Rgb<T>: TranscodableColor,
+{
+    color
+        .normalize()
+        .linear()
+        .encode(SrgbEncoding::new())
+        .strip_encoding()
+}
+
+fn linear_srgb_to_oklab<T>(color: Rgb<T>) -> (T, T, T)
+where
+    T: FreeChannelScalar + PosNormalChannelScalar,
+{
+    let to_lms: Matrix3<T> = Matrix3::new([
+        num_traits::cast(0.4122214708).unwrap(),
+        num_traits::cast(0.5363325363).unwrap(),
+        num_traits::cast(0.0514459929).unwrap(),
+        num_traits::cast(0.2119034982).unwrap(),
+        num_traits::cast(0.6806995451).unwrap(),
+        num_traits::cast(0.1073969566).unwrap(),
+        num_traits::cast(0.0883024619).unwrap(),
+        num_traits::cast(0.2817188376).unwrap(),
+        num_traits::cast(0.6299787005).unwrap(),
+    ]);
+    let to_lab: Matrix3<T> = Matrix3::new([
+        num_traits::cast(0.2104542553).unwrap(),
+        num_traits::cast(0.7936177850).unwrap(),
+        num_traits::cast(-0.0040720468).unwrap(),
+        num_traits::cast(1.9779984951).unwrap(),
+        num_traits::cast(-2.4285922050).unwrap(),
+        num_traits::cast(0.4505937099).unwrap(),
+        num_traits::cast(0.0259040371).unwrap(),
+        num_traits::cast(0.7827717662).unwrap(),
+        num_traits::cast(-0.8086757660).unwrap(),
+    ]);
+    let (l, m, s) = to_lms.transform_vector(color.to_tuple());
+    to_lab.transform_vector((l.cbrt(), m.cbrt(), s.cbrt()))
+}
+
+fn oklab_to_linear_srgb<T>(lab: (T, T, T)) -> Rgb<T>
+where
+    T: FreeChannelScalar + PosNormalChannelScalar,
+{
+    let to_lms: Matrix3<T> = Matrix3::new([
+        num_traits::cast(1.0).unwrap(),
+        num_traits::cast(0.3963377774).unwrap(),
+        num_traits::cast(0.2158037573).unwrap(),
+        num_traits::cast(1.0).unwrap(),
+        num_traits::cast(-0.1055613458).unwrap(),
+        num_traits::cast(-0.0638541728).unwrap(),
+        num_traits::cast(1.0).unwrap(),
+        num_traits::cast(-0.0894841775).unwrap(),
+        num_traits::cast(-1.2914855480).unwrap(),
+    ]);
+    let to_rgb: Matrix3<T> = Matrix3::new([
+        num_traits::cast(4.0767416621).unwrap(),
+        num_traits::cast(-3.3077115913).unwrap(),
+        num_traits::cast(0.2309699292).unwrap(),
+        num_traits::cast(-1.2684380046).unwrap(),
+        num_traits::cast(2.6097574011).unwrap(),
+        num_traits::cast(-0.3413193965).unwrap(),
+        num_traits::cast(-0.0041960863).unwrap(),
+        num_traits::cast(-0.7034186147).unwrap(),
+        num_traits::cast(1.7076147010).unwrap(),
+    ]);
+    let (l, m, s) = to_lms.transform_vector(lab);
+    let (r, g, b) = to_rgb.transform_vector((l * l * l, m * m * m, s * s * s));
+    Rgb::new(r, g, b)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::convert::FromColor;
+    use crate::hsv::Hsv;
+    use approx::*;
+
+    #[test]
+    fn test_mix_srgb() {
+        let c1 = Rgb::new(0.2, 0.8, 0.4);
+        let c2 = Rgb::new(0.9, 0.1, 0.6);
+        for t in [0.0, 0.25, 0.5, 1.0].iter() {
+            assert_relative_eq!(
+                mix_in(&c1, &c2, *t, MixSpace::Srgb),
+                c1.lerp(&c2, *t),
+                epsilon = 1e-12
+            );
+        }
+    }
+
+    #[test]
+    fn test_mix_endpoints() {
+        let c1 = Rgb::new(0.2, 0.8, 0.4);
+        let c2 = Rgb::new(0.9, 0.1, 0.6);
+        for space in [MixSpace::Linear, MixSpace::Lab, MixSpace::Oklab].iter() {
+            assert_relative_eq!(mix_in(&c1, &c2, 0.0, *space), c1, epsilon = 1e-5);
+            assert_relative_eq!(mix_in(&c1, &c2, 1.0, *space), c2, epsilon = 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_gradient() {
+        let blue = Rgb::new(0.0f32, 0.0, 1.0);
+        let yellow = Rgb::new(1.0f32, 1.0, 0.0);
+        let red = Rgb::new(1.0f32, 0.0, 0.0);
+
+        let oklab = Gradient::in_oklab(vec![(1.0, yellow), (0.0, blue)]);
+        assert_eq!(oklab.space(), MixSpace::Oklab);
+        assert_eq!(oklab.stops(), &[(0.0, blue), (1.0, yellow)][..]);
+        assert_relative_eq!(oklab.sample(0.0), blue, epsilon = 1e-5);
+        assert_relative_eq!(oklab.sample(1.0), yellow, epsilon = 1e-5);
+        assert_eq!(oklab.sample(-1.0), blue);
+        assert_eq!(oklab.sample(2.0), yellow);
+
+        // Interpolating the encoded channels passes through a flat, dark gray
+        let srgb = Gradient::new(vec![(0.0, blue), (1.0, yellow)], MixSpace::Srgb);
+        let muddy = Hsv::<f32>::from_color(&srgb.sample(0.5));
+        assert_relative_eq!(muddy.saturation(), 0.0);
+        let mid = oklab.sample(0.5);
+        let clean = Hsv::<f32>::from_color(&mid);
+        assert!(clean.saturation() > 0.2, "{:?}", mid);
+        let luma = |c: &Rgb<f32>| 0.2126 * c.red() + 0.7152 * c.green() + 0.0722 * c.blue();
+        assert!(luma(&mid) > luma(&srgb.sample(0.5)) + 0.05, "{:?}", mid);
+        assert_relative_eq!(
+            mid,
+            mix_in(&blue, &yellow, 0.5, MixSpace::Oklab),
+            epsilon = 1e-6
+        );
+
+        let hard = Gradient::new(
+            vec![(0.0, blue), (0.5, red), (0.5, yellow), (1.0, blue)],
+            MixSpace::Linear,
+        );
+        assert_eq!(hard.sample(0.5), yellow);
+        assert_relative_eq!(
+            hard.sample(0.25),
+            mix_in(&blue, &red, 0.5, MixSpace::Linear),
+            epsilon = 1e-6
+        );
+        assert_relative_eq!(
+            hard.sample(0.75),
+            mix_in(&yellow, &blue, 0.5, MixSpace::Linear),
+            epsilon = 1e-6
+        );
+
+        let single = Gradient::in_oklab(vec![(0.3, red)]);
+        assert_eq!(single.sample(0.0), red);
+        assert_eq!(single.sample(0.9), red);
+    }
+
+    #[test]
+    fn test_mix_gray_midpoint() {
+        let black = Rgb::new(0.0, 0.0, 0.0);
+        let white = Rgb::new(1.0, 1.0, 1.0);
+
+        let srgb = mix_in(&black, &white, 0.5, MixSpace::Srgb);
+        assert_relative_eq!(srgb, Rgb::new(0.5, 0.5, 0.5), epsilon = 1e-6);
+        // Half of the light, encoded back to sRgb
+        let linear = mix_in(&black, &white, 0.5, MixSpace::Linear);
+        assert_relative_eq!(
+            linear,
+            Rgb::new(0.735357, 0.735357, 0.735357),
+            epsilon = 1e-5
+        );
+        // L* = 50
+        let lab = mix_in(&black, &white, 0.5, MixSpace::Lab);
+        assert_relative_eq!(lab, Rgb::new(0.466327, 0.466327, 0.466327), epsilon = 1e-4);
+        // Oklab L = 0.5, or 0.125 in linear light
+        let oklab = mix_in(&black, &white, 0.5, MixSpace::Oklab);
+        assert_relative_eq!(
+            oklab,
+            Rgb::new(0.388572, 0.388572, 0.388572),
+            epsilon = 1e-4
+        );
+    }
+}
@@ -0,0 +1,379 @@
+//! An opponent color model with logarithmically compressed chroma, for compression experiments
+
+use crate::channel::{
+    ChannelCast, ChannelFormatCast, ColorChannel, NormalBoundedChannel, NormalChannelScalar,
+    PosNormalBoundedChannel, PosNormalChannelScalar,
+};
+use crate::color::{Bounded, Broadcast, Color, Flatten, FlattenMut, FromTuple, HomogeneousColor, Invert, Lerp};
+use crate::convert::FromColor;
+use crate::encoding::EncodableColor;
+use crate::rgb::Rgb;
+use crate::tags::OpponentLogTag;
+use crate::ycocg::YCoCg;
+use num_traits::Float;
+use std::fmt;
+use std::mem;
+use std::slice;
+
+/// The strength $`k`$ of the logarithmic chroma curve
+const CHROMA_LOG_SCALE: f64 = 16.0;
+
+/// A luma and opponent chroma color model with logarithmically compressed chroma
+///
+/// `OpponentLog` is an experimental variant of [`YCoCg`](struct.YCoCg.html) for trying out
+/// compression schemes. The luma and the linear chroma orange and chroma green channels are
+/// computed with the `YCoCg` matrix,
+///
+/// ```math
+/// \begin{aligned}
+/// Y &= \frac{R}{4} + \frac{G}{2} + \frac{B}{4} \\
+/// C_o &= \frac{R - B}{2} \\
+/// C_g &= -\frac{R}{4} + \frac{G}{2} - \frac{B}{4}
+/// \end{aligned}
+/// ```
+///
+/// and each chroma channel $`C`$ is then passed through the odd logarithmic curve
+///
+/// ```math
+/// C' = \operatorname{sgn}(C) \frac{\ln(1 + 2k\lvert C \rvert)}{\ln(1 + k)}
+/// ```
+///
+/// with $`k = 16`$. The curve maps $`[-\frac{1}{2}, \frac{1}{2}]`$ onto $`[-1, 1]`$, so for an
+/// Rgb color in the normal range `luma` is in $`[0, 1]`$ and `co` and `cg` are in $`[-1, 1]`$.
+/// It is steepest around zero, so when the channels are quantized, more of the levels go to the
+/// small chroma of near neutral colors, where errors are most visible, and fewer to saturated
+/// colors. Both steps are exactly invertible, so conversion back to Rgb loses nothing but
+/// rounding.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OpponentLog<T> {
+    luma: PosNormalBoundedChannel<T>,
+    co: NormalBoundedChannel<T>,
+    cg: NormalBoundedChannel<T>,
+}
+
+impl<T> OpponentLog<T>
+where
+    T: PosNormalChannelScalar + NormalChannelScalar,
+{
+    /// Construct an `OpponentLog` instance from the luma and log chroma channels
+    pub const fn new(luma: T, co: T, cg: T) -> Self {
+        OpponentLog {
+            luma: PosNormalBoundedChannel::new_const(luma),
+            co: NormalBoundedChannel::new_const(co),
+            cg: NormalBoundedChannel::new_const(cg),
+        }
+    }
+
+    impl_color_checked_new!(
+        fn new_checked(luma: T, co: T, cg: T) {
+            crate::channel::debug_check_bounded::<PosNormalBoundedChannel<T>>(&luma);
+            crate::channel::debug_check_bounded::<NormalBoundedChannel<T>>(&co);
+            crate::channel::debug_check_bounded::<NormalBoundedChannel<T>>(&cg);
+        }
+    );
+
+    impl_color_color_cast_square!(OpponentLog {luma, co, cg},
+        chan_traits={PosNormalChannelScalar, NormalChannelScalar});
+
+    /// Get the luma (Y) channel
+    pub fn luma(&self) -> T {
+        self.luma.0.clone()
+    }
+    /// Get the log chroma orange (Co') channel
+    pub fn co(&self) -> T {
+        self.co.0.clone()
+    }
+    /// Get the log chroma green (Cg') channel
+    pub fn cg(&self) -> T {
+        self.cg.0.clone()
+    }
+    /// Get a mutable reference to the luma (Y) channel
+    pub fn luma_mut(&mut self) -> &mut T {
+        &mut self.luma.0
+    }
+    /// Get a mutable reference to the log chroma orange (Co') channel
+    pub fn co_mut(&mut self) -> &mut T {
+        &mut self.co.0
+    }
+    /// Get a mutable reference to the log chroma green (Cg') channel
+    pub fn cg_mut(&mut self) -> &mut T {
+        &mut self.cg.0
+    }
+    /// Set the luma (Y) channel to a value
+    pub fn set_luma(&mut self, val: T) {
+        crate::channel::debug_check_bounded::<PosNormalBoundedChannel<T>>(&val);
+        self.luma.0 = val;
+    }
+    /// Set the log chroma orange (Co') channel to a value
+    pub fn set_co(&mut self, val: T) {
+        crate::channel::debug_check_bounded::<NormalBoundedChannel<T>>(&val);
+        self.co.0 = val;
+    }
+    /// Set the log chroma green (Cg') channel to a value
+    pub fn set_cg(&mut self, val: T) {
+        crate::channel::debug_check_bounded::<NormalBoundedChannel<T>>(&val);
+        self.cg.0 = val;
+    }
+}
+
+impl<T> Color for OpponentLog<T>
+where
+    T: PosNormalChannelScalar + NormalChannelScalar,
+{
+    type Tag = OpponentLogTag;
+    type ChannelsTuple = (T, T, T);
+
+    #[inline]
+    fn num_channels() -> u32 {
+        3
+    }
+
+    fn to_tuple(self) -> Self::ChannelsTuple {
+        (self.luma.0, self.co.0, self.cg.0)
+    }
+}
+
+impl<T> FromTuple for OpponentLog<T>
+where
+    T: PosNormalChannelScalar + NormalChannelScalar,
+{
+    fn from_tuple(values: Self::ChannelsTuple) -> Self {
+        OpponentLog::new(values.0, values.1, values.2)
+    }
+}
+
+impl<T> Invert for OpponentLog<T>
+where
+    T: PosNormalChannelScalar + NormalChannelScalar,
+{
+    impl_color_invert!(OpponentLog { luma, co, cg });
+}
+
+impl<T> Bounded for OpponentLog<T>
+where
+    T: PosNormalChannelScalar + NormalChannelScalar,
+{
+    impl_color_bounded!(OpponentLog { luma, co, cg });
+}
+
+impl<T> Lerp for OpponentLog<T>
+where
+    T: PosNormalChannelScalar + NormalChannelScalar + Lerp,
+{
+    type Position = <T as Lerp>::Position;
+    impl_color_lerp_square!(OpponentLog { luma, co, cg });
+}
+
+impl<T> HomogeneousColor for OpponentLog<T>
+where
+    T: PosNormalChannelScalar + NormalChannelScalar,
+{
+    type ChannelFormat = T;
+
+    impl_color_homogeneous_color_square!(OpponentLog<T> {luma, co, cg});
+}
+
+impl<T> Broadcast for OpponentLog<T>
+where
+    T: PosNormalChannelScalar + NormalChannelScalar,
+{
+    fn broadcast(value: T) -> Self {
+        OpponentLog {
+            luma: PosNormalBoundedChannel(value.clone()),
+            co: NormalBoundedChannel(value.clone()),
+            cg: NormalBoundedChannel(value),
+        }
+    }
+}
+
+impl<T> Flatten for OpponentLog<T>
+where
+    T: PosNormalChannelScalar + NormalChannelScalar,
+{
+    impl_color_as_slice!(T);
+    impl_color_from_slice_square!(OpponentLog<T> {luma:PosNormalBoundedChannel - 0,
+        co:NormalBoundedChannel - 1, cg:NormalBoundedChannel - 2});
+}
+
+impl<T> FlattenMut for OpponentLog<T>
+where
+    T: PosNormalChannelScalar + NormalChannelScalar,
+{
+    impl_color_as_slice_mut!(T);
+}
+
+impl<T> EncodableColor for OpponentLog<T> where T: PosNormalChannelScalar + NormalChannelScalar {}
+
+#[cfg(feature = "approx")]
+impl<T> approx::AbsDiffEq for OpponentLog<T>
+where
+    T: PosNormalChannelScalar + NormalChannelScalar + approx::AbsDiffEq,
+    T::Epsilon: Clone,
+{
+    impl_abs_diff_eq!({luma, co, cg});
+}
+#[cfg(feature = "approx")]
+impl<T> approx::RelativeEq for OpponentLog<T>
+where
+    T: PosNormalChannelScalar + NormalChannelScalar + approx::RelativeEq,
+    T::Epsilon: Clone,
+{
+    impl_rel_eq!({luma, co, cg});
+}
+#[cfg(feature = "approx")]
+impl<T> approx::UlpsEq for OpponentLog<T>
+where
+    T: PosNormalChannelScalar + NormalChannelScalar + approx::UlpsEq,
+    T::Epsilon: Clone,
+{
+    impl_ulps_eq!({luma, co, cg});
+}
+
+impl<T> Default for OpponentLog<T>
+where
+    T: PosNormalChannelScalar + NormalChannelScalar + num_traits::Zero,
+{
+    impl_color_default!(OpponentLog {
+        luma: PosNormalBoundedChannel,
+        co: NormalBoundedChannel,
+        cg: NormalBoundedChannel
+    });
+}
+
+impl<T> fmt::Display for OpponentLog<T>
+where
+    T: PosNormalChannelScalar + NormalChannelScalar + fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "OpponentLog({}, {}, {})", self.luma, self.co, self.cg)
+    }
+}
+
+impl<T> FromColor<Rgb<T>> for OpponentLog<T>
+where
+    T: PosNormalChannelScalar + NormalChannelScalar + Float,
+{
+    fn from_color(from: &Rgb<T>) -> Self {
+        let ycocg = YCoCg::from_color(from);
+        OpponentLog::new(
+            ycocg.luma(),
+            compress_chroma(ycocg.co()),
+            compress_chroma(ycocg.cg()),
+        )
+    }
+}
+
+impl<T> FromColor<OpponentLog<T>> for Rgb<T>
+where
+    T: PosNormalChannelScalar + NormalChannelScalar + Float,
+{
+    fn from_color(from: &OpponentLog<T>) -> Self {
+        let ycocg = YCoCg::new(
+            from.luma(),
+            expand_chroma(from.co()),
+            expand_chroma(from.cg()),
+        );
+        Rgb::from_color(&ycocg)
+    }
+}
+
+fn compress_chroma<T: Float>(chroma: T) -> T {
+    let k: T = num_traits::cast(CHROMA_LOG_SCALE).unwrap();
+    let two: T = num_traits::cast(2.0).unwrap();
+    let magnitude = (two * k * chroma.abs()).ln_1p() / k.ln_1p();
+    magnitude.copysign(chroma)
+}
+
+fn expand_chroma<T: Float>(chroma: T) -> T {
+    let k: T = num_traits::cast(CHROMA_LOG_SCALE).unwrap();
+    let two: T = num_traits::cast(2.0).unwrap();
+    let magnitude = (chroma.abs() * k.ln_1p()).exp_m1() / (two * k);
+    magnitude.copysign(chroma)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test;
+    use approx::*;
+
+    #[test]
+    fn test_construct() {
+        let c1 = OpponentLog::new(0.5, -0.25, 0.125);
+        assert_relative_eq!(c1.luma(), 0.5);
+        assert_relative_eq!(c1.co(), -0.25);
+        assert_relative_eq!(c1.cg(), 0.125);
+        assert_eq!(OpponentLog::from_tuple(c1.to_tuple()), c1);
+
+        let mut c2 = c1;
+        c2.set_luma(0.75);
+        *c2.co_mut() = 0.3;
+        c2.set_cg(-0.1);
+        assert_eq!(c2, OpponentLog::new(0.75, 0.3, -0.1));
+        assert_eq!(
+            format!("{}", OpponentLog::new(0.5, -0.25, 0.0)),
+            "OpponentLog(0.5, -0.25, 0)"
+        );
+
+        const GRAY: OpponentLog<f32> = OpponentLog::new(0.5, 0.0, 0.0);
+        assert_eq!(OpponentLog::new_checked(0.5, 0.0, 0.0), GRAY);
+    }
+
+    #[test]
+    fn test_from_rgb() {
+        let red = OpponentLog::from_color(&Rgb::new(1.0, 0.0, 0.0));
+        assert_relative_eq!(red.luma(), 0.25);
+        assert_relative_eq!(red.co(), 1.0);
+        assert_relative_eq!(red.cg(), -(9.0f64.ln() / 17.0f64.ln()), epsilon = 1e-12);
+        let blue = OpponentLog::from_color(&Rgb::new(0.0, 0.0, 1.0));
+        assert_relative_eq!(blue.co(), -1.0);
+
+        // Small chroma is expanded relative to YCoCg
+        let warm_gray = Rgb::new(0.51, 0.5, 0.49);
+        let log = OpponentLog::from_color(&warm_gray);
+        assert!(log.co() > 5.0 * YCoCg::from_color(&warm_gray).co());
+
+        for color in test::build_hs_test_data() {
+            let log = OpponentLog::from_color(&color.rgb);
+            assert!(log.is_normalized());
+            let rgb = Rgb::from_color(&log);
+            assert_relative_eq!(rgb, color.rgb, epsilon = 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_quantized_round_trip() {
+        let quantize = |value: f64, levels: f64| (value * levels).round() / levels;
+        for r in (0..=255u8).step_by(15) {
+            for g in (0..=255u8).step_by(15) {
+                for b in (0..=255u8).step_by(15) {
+                    let rgb: Rgb<f64> = Rgb::new(r, g, b).color_cast();
+                    let log = OpponentLog::from_color(&rgb);
+                    let quantized = OpponentLog::new(
+                        quantize(log.luma(), 255.0),
+                        quantize(log.co(), 127.0),
+                        quantize(log.cg(), 127.0),
+                    );
+                    let round_trip = Rgb::from_color(&quantized);
+                    // The log curve is flattest at full chroma, where half of a level of
+                    // 1/127 is worth about 0.006 in linear chroma
+                    assert_relative_eq!(round_trip, rgb, epsilon = 0.02);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_gray_has_no_chroma() {
+        for v in 0..=255u8 {
+            let gray: Rgb<f64> = Rgb::new(v, v, v).color_cast();
+            let log = OpponentLog::from_color(&gray);
+            assert_relative_eq!(log.luma(), gray.red(), epsilon = 1e-12);
+            assert_relative_eq!(log.co(), 0.0, epsilon = 1e-12);
+            assert_relative_eq!(log.cg(), 0.0, epsilon = 1e-12);
+            assert_relative_eq!(Rgb::from_color(&log), gray, epsilon = 1e-12);
+        }
+    }
+}
@@ -0,0 +1,646 @@
+//! Nearest color search for mapping colors onto a fixed palette
+//!
+//! Palette colors and queries are 8-bit sRgb colors, and are compared by their Euclidean distance
+//! in CIELAB ($`\Delta E^*_{ab}`$). For a handful of lookups [`nearest_index`](fn.nearest_index.html)
+//! is enough, while [`PaletteIndex`](struct.PaletteIndex.html) pays a one time cost to make each
+//! lookup logarithmic in the size of the palette.
+//!
+//! Palettes can also be generated from an image with [`kmeans_palette`](fn.kmeans_palette.html),
+//! neutral scales with [`perceptual_gray_ramp`](fn.perceptual_gray_ramp.html) and sets of easily
+//! told apart colors with [`distinct_colors`](fn.distinct_colors.html). Images are
+//! mapped onto a palette with error diffusion by [`floyd_steinberg`](fn.floyd_steinberg.html).
+
+use crate::channel::{ChannelFormatCast, FreeChannelScalar, PosNormalChannelScalar};
+use crate::color::{Bounded, Broadcast, Color};
+use crate::color_space::named::SRgb;
+use crate::color_space::ColorSpace;
+use crate::encoding::{EncodableColor, SrgbEncoding};
+use crate::lab::{rgb_to_lab_in_space, Lab};
+use crate::lchab::Lchab;
+use crate::rgb::Rgb;
+use crate::white_point::{WhitePoint, D65};
+use angle::Deg;
+
+/// Return the index of the color in `palette` closest to `color`
+///
+/// Every entry is compared, so this takes time linear in the size of the palette. If several
+/// entries are equally close, the first is returned. Returns `None` if `palette` is empty.
+pub fn nearest_index(palette: &[Rgb<u8>], color: &Rgb<u8>) -> Option<usize> {
+    let target = srgb_to_lab(color);
+    let mut best = (f64::INFINITY, None);
+    for (index, entry) in palette.iter().enumerate() {
+        let dist = distance_squared(&srgb_to_lab(entry), &target);
+        if dist < best.0 {
+            best = (dist, Some(index));
+        }
+    }
+    best.1
+}
+
+/// A palette preprocessed into a kd-tree for fast nearest color lookups
+///
+/// Building the index takes $`O(n \log^2 n)`$ time for a palette of $`n`$ colors, after which
+/// each call to [`nearest`](#method.nearest) takes $`O(\log n)`$ time on average. This makes it
+/// suitable for mapping every pixel of a large image to a large palette.
+///
+/// `PaletteIndex` always gives the same result as [`nearest_index`](fn.nearest_index.html).
+#[derive(Clone, Debug, PartialEq)]
+pub struct PaletteIndex {
+    // Palette entries in kd-tree order: the median of each range splits it on axis `depth % 3`
+    nodes: Vec<([f64; 3], usize)>,
+}
+
+impl PaletteIndex {
+    /// Build an index over the colors in `palette`
+    pub fn new(palette: &[Rgb<u8>]) -> Self {
+        let mut nodes: Vec<_> = palette
+            .iter()
+            .enumerate()
+            .map(|(index, color)| (srgb_to_lab(color), index))
+            .collect();
+        build_tree(&mut nodes, 0);
+        PaletteIndex { nodes }
+    }
+
+    /// Returns the number of colors in the palette
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns true if the palette has no colors
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Return the index in the original palette of the color closest to `color`
+    ///
+    /// If several entries are equally close, the first is returned.
+    ///
+    /// Panics:
+    ///
+    /// Panics if the palette is empty.
+    pub fn nearest(&self, color: &Rgb<u8>) -> usize {
+        assert!(!self.is_empty(), "nearest called on an empty PaletteIndex");
+        let target = srgb_to_lab(color);
+        let mut best = (f64::INFINITY, usize::MAX);
+        search_tree(&self.nodes, 0, &target, &mut best);
+        best.1
+    }
+}
+
+fn build_tree(nodes: &mut [([f64; 3], usize)], depth: usize) {
+    if nodes.len() <= 1 {
+        return;
+    }
+    let axis = depth % 3;
+    nodes.sort_by(|a, b| a.0[axis].partial_cmp(&b.0[axis]).unwrap());
+    let mid = nodes.len() / 2;
+    let (left, right) = nodes.split_at_mut(mid);
+    build_tree(left, depth + 1);
+    build_tree(&mut right[1..], depth + 1);
+}
+
+fn search_tree(
+    nodes: &[([f64; 3], usize)],
+    depth: usize,
+    target: &[f64; 3],
+    best: &mut (f64, usize),
+) {
+    if nodes.is_empty() {
+        return;
+    }
+    let mid = nodes.len() / 2;
+    let (point, index) = nodes[mid];
+    let dist = distance_squared(&point, target);
+    if dist < best.0 || (dist == best.0 && index < best.1) {
+        *best = (dist, index);
+    }
+
+    let axis = depth % 3;
+    let offset = target[axis] - point[axis];
+    let (near, far) = if offset < 0.0 {
+        (&nodes[..mid], &nodes[mid + 1..])
+    } else {
+        (&nodes[mid + 1..], &nodes[..mid])
+    };
+    search_tree(near, depth + 1, target, best);
+    // Equal distances must still be visited so ties resolve to the lowest index
+    if offset * offset <= best.0 {
+        search_tree(far, depth + 1, target, best);
+    }
+}
+
+/// Generate a palette of up to `k` colors representative of `pixels` using k-means clustering
+///
+/// Pixels are clustered in CIELAB, so clusters are groups of perceptually similar colors. The
+/// initial centroids are chosen with k-means++ seeding from a pseudo-random generator seeded by
+/// `seed`, making the result deterministic for a given input. Clustering stops after `iters`
+/// rounds or once no pixel changes cluster, whichever comes first.
+///
+/// The returned colors are the cluster centroids converted back to 8-bit sRgb, in the order they
+/// were seeded. At most `pixels.len()` colors are returned, and none if `pixels` is empty.
+pub fn kmeans_palette(pixels: &[Rgb<u8>], k: usize, iters: usize, seed: u64) -> Vec<Rgb<u8>> {
+    let points: Vec<[f64; 3]> = pixels.iter().map(srgb_to_lab).collect();
+    let k = k.min(points.len());
+    if k == 0 {
+        return Vec::new();
+    }
+    let mut rng = XorShift64::new(seed);
+
+    // k-means++: each new centroid is drawn with probability proportional to its squared
+    // distance from the nearest existing centroid
+    let mut centroids = vec![points[rng.next_index(points.len())]];
+    let mut nearest_dist: Vec<f64> = points
+        .iter()
+        .map(|p| distance_squared(p, &centroids[0]))
+        .collect();
+    while centroids.len() < k {
+        let total: f64 = nearest_dist.iter().sum();
+        let next = if total > 0.0 {
+            let mut target = rng.next_f64() * total;
+            let mut chosen = points.len() - 1;
+            for (index, &dist) in nearest_dist.iter().enumerate() {
+                if target < dist {
+                    chosen = index;
+                    break;
+                }
+                target -= dist;
+            }
+            chosen
+        } else {
+            rng.next_index(points.len())
+        };
+        centroids.push(points[next]);
+        for (dist, point) in nearest_dist.iter_mut().zip(points.iter()) {
+            *dist = dist.min(distance_squared(point, &points[next]));
+        }
+    }
+
+    let mut assignments = vec![usize::MAX; points.len()];
+    for _ in 0..iters {
+        let mut changed = false;
+        for (assignment, point) in assignments.iter_mut().zip(points.iter()) {
+            let nearest = nearest_centroid(&centroids, point);
+            if nearest != *assignment {
+                *assignment = nearest;
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+
+        let mut sums = vec![([0.0f64; 3], 0usize); k];
+        for (&assignment, point) in assignments.iter().zip(points.iter()) {
+            let (sum, count) = &mut sums[assignment];
+            sum[0] += point[0];
+            sum[1] += point[1];
+            sum[2] += point[2];
+            *count += 1;
+        }
+        // A cluster that lost all of its pixels keeps its previous centroid
+        for (centroid, (sum, count)) in centroids.iter_mut().zip(sums.iter()) {
+            if *count > 0 {
+                let n = *count as f64;
+                *centroid = [sum[0] / n, sum[1] / n, sum[2] / n];
+            }
+        }
+    }
+
+    centroids.iter().map(lab_to_srgb).collect()
+}
+
+/// Generate `n` neutral grays evenly spaced in CIELAB lightness, from black to white
+///
+/// The grays have $`L^*`$ values of $`100 \frac{i}{n - 1}`$ for $`i = 0, \ldots, n - 1`$,
+/// relative to the white point of `space`, and are returned encoded with the space's own encoding.
+/// Unlike a ramp evenly spaced in linear light or in encoded values, each step looks like the
+/// same change in brightness, which makes it suited to neutral scales in user interfaces.
+///
+/// If `n` is one, only black is returned, and if it is zero the result is empty.
+pub fn perceptual_gray_ramp<T, S>(n: usize, space: &S) -> Vec<Rgb<T>>
+where
+    T: FreeChannelScalar + PosNormalChannelScalar + num_traits::Float + ChannelFormatCast<f64>,
+    f64: ChannelFormatCast<T>,
+    S: ColorSpace<T>,
+{
+    let hundred: T = num_traits::cast(100.0).unwrap();
+    let steps: T = num_traits::cast(n.saturating_sub(1).max(1)).unwrap();
+    let white_y = WhitePoint::<T>::get_xyz(&D65).y();
+    (0..n)
+        .map(|i| {
+            let lightness = hundred * num_traits::cast(i).unwrap() / steps;
+            // A neutral with the space's white chromaticity has equal linear channels of Y/Yw
+            let gray = Lab::<T, D65>::new(lightness, T::zero(), T::zero()).to_xyz();
+            Rgb::broadcast(gray.y() / white_y)
+                .normalize()
+                .linear()
+                .encode(space.encoding())
+                .strip_encoding()
+        })
+        .collect()
+}
+
+const DISTINCT_LIGHTNESS: f64 = 65.0;
+const DISTINCT_CHROMA: f64 = 50.0;
+const DISTINCT_START_HUES: usize = 72;
+
+/// Generate `n` colors that are as easy as possible to tell apart, such as for chart categories
+///
+/// The colors are placed on a ring of constant lightness and chroma in CIE LCh(ab), relative to
+/// the white point of `space`. Successive hues are a golden angle ($`\approx 137.5^{\circ}`$)
+/// apart, which spreads any number of hues around the circle without the colors at either end of
+/// the list landing next to each other. Colors that fall outside the gamut of `space` are
+/// clipped as in [`Lchab::clip_with_delta`](../struct.Lchab.html#method.clip_with_delta). The
+/// starting hue is then chosen to maximize the smallest CIEDE2000 difference between any two of
+/// the clipped colors, and they are returned encoded with the space's encoding and rounded to
+/// 8 bits.
+///
+/// Since only the hue varies, the colors get hard to tell apart beyond a dozen or so.
+pub fn distinct_colors<T, S>(n: usize, space: &S) -> Vec<Rgb<u8>>
+where
+    T: FreeChannelScalar
+        + PosNormalChannelScalar
+        + ChannelFormatCast<f64>
+        + ChannelFormatCast<u8>
+        + num_traits::Float,
+    f64: ChannelFormatCast<T>,
+    S: ColorSpace<T>,
+{
+    let lightness: T = num_traits::cast(DISTINCT_LIGHTNESS).unwrap();
+    let chroma: T = num_traits::cast(DISTINCT_CHROMA).unwrap();
+    let golden_angle: T = num_traits::cast(180.0 * (3.0 - 5.0f64.sqrt())).unwrap();
+    let ring = |start: T| -> Vec<(Rgb<T>, Lab<T, D65>)> {
+        (0..n)
+            .map(|i| {
+                let hue = start + golden_angle * num_traits::cast(i).unwrap();
+                let lch = Lchab::<T, D65>::new(lightness, chroma, Deg(hue)).normalize_hue();
+                let rgb = lch.clip_with_delta(space).0;
+                let lab = rgb_to_lab_in_space(&rgb, space);
+                (rgb, lab)
+            })
+            .collect()
+    };
+    let min_difference = |colors: &[(Rgb<T>, Lab<T, D65>)]| -> T {
+        let mut min = T::infinity();
+        for (i, (_, lab)) in colors.iter().enumerate() {
+            for (_, other) in &colors[i + 1..] {
+                min = min.min(lab.ciede2000(other));
+            }
+        }
+        min
+    };
+
+    let mut best = (T::neg_infinity(), Vec::new());
+    for step in 0..DISTINCT_START_HUES {
+        let start: T = num_traits::cast(360.0 * step as f64 / DISTINCT_START_HUES as f64).unwrap();
+        let colors = ring(start);
+        let difference = min_difference(&colors);
+        if difference > best.0 {
+            best = (difference, colors);
+        }
+    }
+    best.1
+        .into_iter()
+        .map(|(rgb, _)| rgb.color_cast())
+        .collect()
+}
+
+/// Quantize `pixels` to `palette` in place, using Floyd-Steinberg error diffusion
+///
+/// `pixels` is an image stored row by row, `width` pixels to a row. Pixels are visited in scanline
+/// order and replaced by the nearest palette color, as found by
+/// [`PaletteIndex`](struct.PaletteIndex.html). The difference between the pixel and the chosen
+/// color is then carried over to the neighbors that have not been visited yet, with weights of
+/// $`\frac{7}{16}`$ to the right, and $`\frac{3}{16}`$, $`\frac{5}{16}`$ and $`\frac{1}{16}`$ to
+/// the lower left, below and lower right. Averaged over an area, the output then reproduces tones
+/// that lie between palette colors, instead of banding to the nearest one.
+///
+/// The error is measured on the encoded 8-bit channel values. A partial last row is dithered like
+/// the others. If `palette` is empty or `width` is zero, `pixels` is left unchanged.
+pub fn floyd_steinberg(pixels: &mut [Rgb<u8>], width: usize, palette: &[Rgb<u8>]) {
+    if palette.is_empty() || width == 0 {
+        return;
+    }
+    let index = PaletteIndex::new(palette);
+    let mut error = vec![[0.0f32; 3]; pixels.len()];
+
+    for i in 0..pixels.len() {
+        let pixel = &pixels[i];
+        let original = [pixel.red(), pixel.green(), pixel.blue()];
+        let mut wanted = [0.0f32; 3];
+        for c in 0..3 {
+            wanted[c] = (f32::from(original[c]) + error[i][c]).clamp(0.0, 255.0);
+        }
+        let query = Rgb::new(
+            wanted[0].round() as u8,
+            wanted[1].round() as u8,
+            wanted[2].round() as u8,
+        );
+        let chosen = palette[index.nearest(&query)];
+        let chosen_channels = [chosen.red(), chosen.green(), chosen.blue()];
+        pixels[i] = chosen;
+
+        let (x, y) = (i % width, i / width);
+        let mut diffuse = |dx: isize, dy: usize, weight: f32| {
+            let nx = x as isize + dx;
+            if nx < 0 || nx as usize >= width {
+                return;
+            }
+            let j = (y + dy) * width + nx as usize;
+            if j < error.len() {
+                for c in 0..3 {
+                    error[j][c] += (wanted[c] - f32::from(chosen_channels[c])) * weight;
+                }
+            }
+        };
+        diffuse(1, 0, 7.0 / 16.0);
+        diffuse(-1, 1, 3.0 / 16.0);
+        diffuse(0, 1, 5.0 / 16.0);
+        diffuse(1, 1, 1.0 / 16.0);
+    }
+}
+
+fn nearest_centroid(centroids: &[[f64; 3]], point: &[f64; 3]) -> usize {
+    let mut best = (f64::INFINITY, 0);
+    for (index, centroid) in centroids.iter().enumerate() {
+        let dist = distance_squared(centroid, point);
+        if dist < best.0 {
+            best = (dist, index);
+        }
+    }
+    best.1
+}
+
+// A small xorshift64* generator, so palettes are reproducible without depending on a rand crate
+struct XorShift64(u64);
+
+impl XorShift64 {
+    fn new(seed: u64) -> Self {
+        // The all-zero state is a fixed point of xorshift
+        XorShift64(seed ^ 0x9e37_79b9_7f4a_7c15)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        if self.0 == 0 {
+            self.0 = 0x9e37_79b9_7f4a_7c15;
+        }
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    fn next_index(&mut self, len: usize) -> usize {
+        (self.next_u64() % len as u64) as usize
+    }
+}
+
+fn srgb_to_lab(color: &Rgb<u8>) -> [f64; 3] {
+    let rgb: Rgb<f64> = color.color_cast();
+    let xyz = SRgb::new().convert_rgb_to_xyz(&rgb, &SrgbEncoding::new());
+    let lab = Lab::from_xyz(&xyz, D65);
+    [lab.L(), lab.a(), lab.b()]
+}
+
+fn lab_to_srgb(lab: &[f64; 3]) -> Rgb<u8> {
+    let xyz = Lab::<f64, D65>::new(lab[0], lab[1], lab[2]).to_xyz();
+    let space = SRgb::<f64>::new();
+    let (r, g, b) = space
+        .get_inverse_xyz_transform()
+        .transform_vector(xyz.to_tuple());
+    Rgb::new(r, g, b)
+        .normalize()
+        .linear()
+        .encode(SrgbEncoding::new())
+        .strip_encoding()
+        .color_cast()
+}
+
+fn distance_squared(a: &[f64; 3], b: &[f64; 3]) -> f64 {
+    let d0 = a[0] - b[0];
+    let d1 = a[1] - b[1];
+    let d2 = a[2] - b[2];
+    d0 * d0 + d1 * d1 + d2 * d2
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::encoding::ChannelDecoder;
+    use crate::xyz::Xyz;
+
+    struct XorShift(u32);
+
+    impl XorShift {
+        fn next_color(&mut self) -> Rgb<u8> {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 17;
+            self.0 ^= self.0 << 5;
+            let [r, g, b, _] = self.0.to_le_bytes();
+            Rgb::new(r, g, b)
+        }
+    }
+
+    #[test]
+    fn test_distinct_colors() {
+        let space = SRgb::<f64>::new();
+        let colors = distinct_colors(6, &space);
+        assert_eq!(colors.len(), 6);
+        let labs: Vec<_> = colors
+            .iter()
+            .map(|color| rgb_to_lab_in_space(&color.color_cast(), &space))
+            .collect();
+        for (i, lab) in labs.iter().enumerate() {
+            assert!((lab.L() - DISTINCT_LIGHTNESS).abs() < 1.0);
+            let lch: Lchab<f64, D65> = crate::convert::FromColor::from_color(lab);
+            assert!(lch.clip_with_delta(&space).1 < 1.0);
+            for other in &labs[i + 1..] {
+                assert!(lab.ciede2000(other) > 15.0);
+            }
+        }
+
+        assert!(distinct_colors(0, &space).is_empty());
+        assert_eq!(distinct_colors(1, &space).len(), 1);
+        assert_eq!(distinct_colors(6, &space), colors);
+    }
+
+    #[test]
+    fn test_nearest_index() {
+        let palette = [
+            Rgb::new(0, 0, 0),
+            Rgb::new(255, 255, 255),
+            Rgb::new(255, 0, 0),
+            Rgb::new(0, 0, 255),
+        ];
+        assert_eq!(nearest_index(&palette, &Rgb::new(20, 10, 10)), Some(0));
+        assert_eq!(nearest_index(&palette, &Rgb::new(240, 240, 230)), Some(1));
+        assert_eq!(nearest_index(&palette, &Rgb::new(200, 30, 40)), Some(2));
+        assert_eq!(nearest_index(&palette, &Rgb::new(30, 20, 200)), Some(3));
+        assert_eq!(nearest_index(&[], &Rgb::new(0, 0, 0)), None);
+
+        let index = PaletteIndex::new(&palette);
+        assert_eq!(index.len(), 4);
+        assert_eq!(index.nearest(&Rgb::new(200, 30, 40)), 2);
+        assert!(PaletteIndex::new(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_palette_index_matches_linear() {
+        let mut rng = XorShift(0x9e37_79b9);
+        for &size in [1, 2, 7, 64, 300].iter() {
+            let palette: Vec<_> = (0..size).map(|_| rng.next_color()).collect();
+            let index = PaletteIndex::new(&palette);
+            for _ in 0..500 {
+                let query = rng.next_color();
+                assert_eq!(Some(index.nearest(&query)), nearest_index(&palette, &query));
+            }
+            for color in palette.iter() {
+                assert_eq!(Some(index.nearest(color)), nearest_index(&palette, color));
+            }
+        }
+
+        let duplicates = [
+            Rgb::new(10, 20, 30),
+            Rgb::new(200, 100, 0),
+            Rgb::new(10, 20, 30),
+            Rgb::new(200, 100, 0),
+        ];
+        let index = PaletteIndex::new(&duplicates);
+        assert_eq!(index.nearest(&Rgb::new(10, 20, 30)), 0);
+        assert_eq!(index.nearest(&Rgb::new(190, 100, 10)), 1);
+    }
+
+    #[test]
+    fn test_kmeans_palette() {
+        let centers = [
+            Rgb::new(200u8, 30, 40),
+            Rgb::new(40, 160, 60),
+            Rgb::new(30, 50, 190),
+        ];
+        let mut rng = XorShift(0x1234_5678);
+        let mut pixels = Vec::new();
+        for _ in 0..40 {
+            for center in centers.iter() {
+                let noise = rng.next_color();
+                let jitter = |c: u8, n: u8| (c as i32 + (n % 11) as i32 - 5) as u8;
+                pixels.push(Rgb::new(
+                    jitter(center.red(), noise.red()),
+                    jitter(center.green(), noise.green()),
+                    jitter(center.blue(), noise.blue()),
+                ));
+            }
+        }
+
+        let palette = kmeans_palette(&pixels, 3, 20, 7);
+        assert_eq!(palette.len(), 3);
+        assert_eq!(palette, kmeans_palette(&pixels, 3, 20, 7));
+        for center in centers.iter() {
+            let found = palette[nearest_index(&palette, center).unwrap()];
+            assert!((found.red() as i32 - center.red() as i32).abs() <= 2);
+            assert!((found.green() as i32 - center.green() as i32).abs() <= 2);
+            assert!((found.blue() as i32 - center.blue() as i32).abs() <= 2);
+        }
+
+        assert!(kmeans_palette(&[], 3, 10, 0).is_empty());
+        assert!(kmeans_palette(&pixels, 0, 10, 0).is_empty());
+        let single = kmeans_palette(&[Rgb::new(10, 20, 30)], 4, 10, 0);
+        assert_eq!(single, vec![Rgb::new(10, 20, 30)]);
+    }
+
+    #[test]
+    fn test_floyd_steinberg() {
+        let palette = [
+            Rgb::new(0u8, 0, 0),
+            Rgb::new(85, 85, 85),
+            Rgb::new(170, 170, 170),
+            Rgb::new(255, 255, 255),
+        ];
+        // A gradient that lies between two palette entries, but is closest to the darker one
+        let (width, height) = (32, 16);
+        let original: Vec<Rgb<u8>> = (0..width * height)
+            .map(|i| {
+                let v = 90 + (i % width) as u8;
+                Rgb::new(v, v, v)
+            })
+            .collect();
+        let naive: Vec<Rgb<u8>> = original
+            .iter()
+            .map(|p| palette[nearest_index(&palette, p).unwrap()])
+            .collect();
+        let mut dithered = original.clone();
+        floyd_steinberg(&mut dithered, width, &palette);
+
+        assert!(dithered.iter().all(|p| palette.contains(p)));
+        let used = |pixels: &[Rgb<u8>]| palette.iter().filter(|e| pixels.contains(e)).count();
+        assert_eq!(used(&naive), 1);
+        assert_eq!(used(&dithered), 2);
+
+        // The error of the average tone over each pair of columns
+        let pair_sum = |image: &[Rgb<u8>], x: usize| -> f64 {
+            (0..height)
+                .map(|y| {
+                    f64::from(image[y * width + x].red())
+                        + f64::from(image[y * width + x + 1].red())
+                })
+                .sum()
+        };
+        let mean_error = |pixels: &[Rgb<u8>]| {
+            let total: f64 = (0..width)
+                .step_by(2)
+                .map(|x| (pair_sum(pixels, x) - pair_sum(&original, x)).abs() / (2 * height) as f64)
+                .sum();
+            total / (width / 2) as f64
+        };
+        assert!(mean_error(&naive) > 10.0);
+        assert!(mean_error(&dithered) < 3.0);
+
+        // Colors already in the palette are left alone
+        let mut exact = vec![palette[2]; 10];
+        floyd_steinberg(&mut exact, 3, &palette);
+        assert_eq!(exact, vec![palette[2]; 10]);
+
+        let mut untouched = original.clone();
+        floyd_steinberg(&mut untouched, width, &[]);
+        assert_eq!(untouched, original);
+        floyd_steinberg(&mut untouched, 0, &palette);
+        assert_eq!(untouched, original);
+    }
+
+    #[test]
+    fn test_perceptual_gray_ramp() {
+        let space = SRgb::<f64>::new();
+        let ramp = perceptual_gray_ramp(5, &space);
+        assert_eq!(ramp.len(), 5);
+        assert_eq!(ramp[0], Rgb::new(0.0, 0.0, 0.0));
+        assert!((ramp[4].red() - 1.0).abs() < 1e-9);
+
+        let lightness: Vec<f64> = ramp
+            .iter()
+            .map(|gray| {
+                assert_eq!(gray.red(), gray.green());
+                assert_eq!(gray.red(), gray.blue());
+                let linear = SrgbEncoding::new().decode_channel(gray.red());
+                Lab::<f64, D65>::from_xyz(&Xyz::new(0.0, linear, 0.0), D65).L()
+            })
+            .collect();
+        for (i, l) in lightness.iter().enumerate() {
+            assert!((l - 25.0 * i as f64).abs() < 1e-6);
+        }
+
+        // Even steps in L* are uneven steps in encoded sRgb values
+        let steps: Vec<f64> = ramp.windows(2).map(|w| w[1].red() - w[0].red()).collect();
+        assert!(steps.windows(2).all(|w| w[0] < w[1]));
+        assert!(steps[3] - steps[0] > 0.03);
+
+        assert_eq!(
+            perceptual_gray_ramp(1, &space),
+            vec![Rgb::new(0.0, 0.0, 0.0)]
+        );
+        assert!(perceptual_gray_ramp(0, &space).is_empty());
+    }
+}
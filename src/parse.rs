@@ -0,0 +1,282 @@
+//! Parsing colors from textual representations
+
+use crate::channel::{ChannelFormatCast, FreeChannelScalar, PosNormalChannelScalar};
+use crate::color_space::named::{DisplayP3, Rec2020, SRgb};
+use crate::color_space::{
+    ConvertToXyz, EncodedColorSpace, RgbPrimary, SpacedColor, UnitColorSpace,
+};
+use crate::encoding::{
+    ColorEncoding, EncodableColor, GammaEncoding, LinearEncoding, Rec709Encoding, SrgbEncoding,
+};
+use crate::linalg::Matrix3;
+use crate::rgb::Rgb;
+use crate::white_point::{WhitePoint, D65};
+use crate::xyz::Xyz;
+use std::error;
+use std::fmt;
+
+/// An error produced when a string can not be parsed as a color
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ParseColorError {
+    /// The input did not have a valid number of digits for its format
+    InvalidLength,
+    /// The input contained a character that is not a valid digit
+    InvalidDigit,
+    /// The input was not valid functional notation, or had the wrong number of components
+    InvalidSyntax,
+    /// The input named a color space that is not supported
+    UnknownColorSpace,
+}
+
+impl fmt::Display for ParseColorError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ParseColorError::InvalidLength => write!(f, "invalid length for a color string"),
+            ParseColorError::InvalidDigit => write!(f, "invalid digit in color string"),
+            ParseColorError::InvalidSyntax => write!(f, "invalid syntax in color string"),
+            ParseColorError::UnknownColorSpace => write!(f, "unknown color space in color string"),
+        }
+    }
+}
+
+impl error::Error for ParseColorError {}
+
+impl Rgb<u8> {
+    /// Parse a hexadecimal color string such as `"#3366cc"`
+    ///
+    /// Both the six digit `#rrggbb` and three digit `#rgb` shorthand forms are accepted, with
+    /// or without the leading `#`. Digits are case-insensitive. No encoding is assumed; the
+    /// channels are returned exactly as written.
+    pub fn from_hex(hex: &str) -> Result<Self, ParseColorError> {
+        let digits = hex.strip_prefix('#').unwrap_or(hex);
+        if !digits.bytes().all(|c| c.is_ascii_hexdigit()) {
+            return Err(ParseColorError::InvalidDigit);
+        }
+        let bytes = digits.as_bytes();
+        let (r, g, b) = match bytes.len() {
+            3 => (
+                hex_digit(bytes[0]) * 0x11,
+                hex_digit(bytes[1]) * 0x11,
+                hex_digit(bytes[2]) * 0x11,
+            ),
+            6 => (
+                hex_digit(bytes[0]) << 4 | hex_digit(bytes[1]),
+                hex_digit(bytes[2]) << 4 | hex_digit(bytes[3]),
+                hex_digit(bytes[4]) << 4 | hex_digit(bytes[5]),
+            ),
+            _ => return Err(ParseColorError::InvalidLength),
+        };
+        Ok(Rgb::new(r, g, b))
+    }
+}
+
+fn hex_digit(c: u8) -> u8 {
+    match c {
+        b'0'..=b'9' => c - b'0',
+        b'a'..=b'f' => c - b'a' + 10,
+        _ => c - b'A' + 10,
+    }
+}
+
+/// Parse a hex string as sRgb and take it to `Xyz` through the standard sRgb color space
+pub(crate) fn srgb_hex_to_xyz<T>(hex: &str) -> Result<Xyz<T>, ParseColorError>
+where
+    T: FreeChannelScalar + PosNormalChannelScalar + ChannelFormatCast<f64>,
+    f64: ChannelFormatCast<T>,
+    u8: ChannelFormatCast<T>,
+{
+    let rgb: Rgb<T> = Rgb::from_hex(hex)?.color_cast();
+    Ok(SRgb::new().convert_to_xyz(&rgb.srgb_encoded()))
+}
+
+/// A linear `Rgb<f32>` color in a color space parsed from the CSS `color()` function
+pub type CssSpacedColor =
+    SpacedColor<f32, Rgb<f32>, LinearEncoding, EncodedColorSpace<f32, LinearEncoding>>;
+
+/// Parse the CSS `color()` functional notation, such as `"color(display-p3 1 0 0)"`
+///
+/// The supported color spaces are `srgb`, `srgb-linear`, `display-p3`, `rec2020`, `a98-rgb` and
+/// `xyz` (or `xyz-d65`). Each of the three components is a number or a percentage, where `100%`
+/// is `1.0`. Keywords are case-insensitive. An alpha component is not accepted.
+///
+/// As the named spaces differ in their transfer functions, the components are decoded to linear
+/// light and the returned color is attached to the matching space with a linear encoding, built
+/// from [`SRgb`](color_space/named/struct.SRgb.html),
+/// [`DisplayP3`](color_space/named/struct.DisplayP3.html) or
+/// [`Rec2020`](color_space/named/struct.Rec2020.html) where one exists. `a98-rgb` uses the Adobe
+/// RGB (1998) primaries and a gamma of 563/256, and `xyz` is D65 relative XYZ, which is attached
+/// to a space with an identity transform.
+///
+/// ```
+/// use prisma::color_space::ConvertToXyz;
+/// use prisma::parse_color_function;
+///
+/// let red = parse_color_function("color(display-p3 1 0 0)").unwrap();
+/// let xyz = red.space().convert_to_xyz(red.color());
+/// assert!((xyz.x() - 0.48663).abs() < 1e-4);
+/// ```
+pub fn parse_color_function(input: &str) -> Result<CssSpacedColor, ParseColorError> {
+    let input = input.trim();
+    let body = match (input.get(..6), input.strip_suffix(')')) {
+        (Some(prefix), Some(rest)) if prefix.eq_ignore_ascii_case("color(") => &rest[6..],
+        _ => return Err(ParseColorError::InvalidSyntax),
+    };
+    let mut tokens = body.split_whitespace();
+    let name = tokens
+        .next()
+        .ok_or(ParseColorError::InvalidSyntax)?
+        .to_ascii_lowercase();
+    let mut channels = [0.0f32; 3];
+    for channel in channels.iter_mut() {
+        *channel = parse_css_number(tokens.next().ok_or(ParseColorError::InvalidSyntax)?)?;
+    }
+    if tokens.next().is_some() {
+        return Err(ParseColorError::InvalidSyntax);
+    }
+    let rgb = Rgb::new(channels[0], channels[1], channels[2]);
+
+    let linear = || LinearEncoding::new();
+    let (color, space) = match name.as_str() {
+        "srgb" => (
+            decode(rgb, SrgbEncoding::new()),
+            SRgb::build_color_space_instance().with_encoding(linear()),
+        ),
+        "srgb-linear" => (
+            rgb,
+            SRgb::build_color_space_instance().with_encoding(linear()),
+        ),
+        "display-p3" => (
+            decode(rgb, SrgbEncoding::new()),
+            DisplayP3::build_color_space_instance().with_encoding(linear()),
+        ),
+        "rec2020" => (
+            decode(rgb, Rec709Encoding::new()),
+            Rec2020::build_color_space_instance().with_encoding(linear()),
+        ),
+        "a98-rgb" => (
+            decode(rgb, GammaEncoding::new(563.0f32 / 256.0)),
+            EncodedColorSpace::new(
+                RgbPrimary::new(0.64, 0.33),
+                RgbPrimary::new(0.21, 0.71),
+                RgbPrimary::new(0.15, 0.06),
+                D65.get_xyz(),
+                linear(),
+            ),
+        ),
+        "xyz" | "xyz-d65" => (
+            rgb,
+            EncodedColorSpace::new_with_transforms(
+                RgbPrimary::new(1.0, 0.0),
+                RgbPrimary::new(0.0, 1.0),
+                RgbPrimary::new(0.0, 0.0),
+                D65.get_xyz(),
+                linear(),
+                Matrix3::identity(),
+                Matrix3::identity(),
+            ),
+        ),
+        _ => return Err(ParseColorError::UnknownColorSpace),
+    };
+    Ok(SpacedColor::new(color.linear(), space))
+}
+
+fn decode<E: ColorEncoding>(rgb: Rgb<f32>, encoding: E) -> Rgb<f32> {
+    rgb.encoded_as(encoding).decode().strip_encoding()
+}
+
+fn parse_css_number(token: &str) -> Result<f32, ParseColorError> {
+    let (number, scale) = match token.strip_suffix('%') {
+        Some(number) => (number, 0.01),
+        None => (token, 1.0),
+    };
+    number
+        .parse::<f32>()
+        .ok()
+        .filter(|v| v.is_finite())
+        .map(|v| v * scale)
+        .ok_or(ParseColorError::InvalidDigit)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_rgb_from_hex() {
+        assert_eq!(Rgb::from_hex("#3366cc"), Ok(Rgb::new(0x33, 0x66, 0xcc)));
+        assert_eq!(Rgb::from_hex("3366CC"), Ok(Rgb::new(0x33, 0x66, 0xcc)));
+        assert_eq!(Rgb::from_hex("#fa0"), Ok(Rgb::new(0xff, 0xaa, 0x00)));
+        assert_eq!(Rgb::from_hex("#000000"), Ok(Rgb::new(0, 0, 0)));
+        assert_eq!(Rgb::from_hex("#ffffff"), Ok(Rgb::new(255, 255, 255)));
+
+        assert_eq!(Rgb::from_hex(""), Err(ParseColorError::InvalidLength));
+        assert_eq!(Rgb::from_hex("#12345"), Err(ParseColorError::InvalidLength));
+        assert_eq!(Rgb::from_hex("#12345g"), Err(ParseColorError::InvalidDigit));
+        assert_eq!(Rgb::from_hex("#+12345"), Err(ParseColorError::InvalidDigit));
+        assert_eq!(
+            Rgb::from_hex("##123456"),
+            Err(ParseColorError::InvalidDigit)
+        );
+    }
+
+    #[test]
+    fn test_parse_color_function() {
+        use crate::encoding::ChannelDecoder;
+        use crate::Broadcast;
+
+        let red = parse_color_function("color(display-p3 1 0 0)").unwrap();
+        let p3 = DisplayP3::<f32>::build_color_space_instance();
+        assert_eq!(red.space(), &p3.with_encoding(LinearEncoding::new()));
+        assert_eq!(
+            red.color().clone().strip_encoding(),
+            Rgb::new(1.0, 0.0, 0.0)
+        );
+        let xyz = red.space().convert_to_xyz(red.color());
+        let p3_xyz = DisplayP3::new().convert_to_xyz(&Rgb::new(1.0f32, 0.0, 0.0).srgb_encoded());
+        assert!((xyz.x() - p3_xyz.x()).abs() < 1e-6);
+        assert!((xyz.y() - p3_xyz.y()).abs() < 1e-6);
+        assert!((xyz.z() - p3_xyz.z()).abs() < 1e-6);
+
+        // The components are decoded to linear light
+        let gray = parse_color_function("COLOR(sRGB 50% 0.5 .5)").unwrap();
+        let expected = SrgbEncoding::new().decode_channel(0.5f32);
+        assert_eq!(
+            gray.color().clone().strip_encoding(),
+            Rgb::broadcast(expected)
+        );
+        let linear = parse_color_function("color(srgb-linear 0.5 0.5 0.5)").unwrap();
+        assert_eq!(linear.color().clone().strip_encoding(), Rgb::broadcast(0.5));
+
+        let green = parse_color_function("  color(rec2020 0 1 0) ").unwrap();
+        assert_eq!(
+            green.space(),
+            &Rec2020::<f32>::build_color_space_instance().with_encoding(LinearEncoding::new())
+        );
+        let a98 = parse_color_function("color(a98-rgb 0.5 0.5 0.5)").unwrap();
+        assert!((a98.color().red() - 0.5f32.powf(563.0 / 256.0)).abs() < 1e-6);
+        let white = parse_color_function("color(xyz 0.95047 1 1.08883)").unwrap();
+        let white_xyz = white.space().convert_to_xyz(white.color());
+        assert_eq!(white_xyz, Xyz::new(0.95047, 1.0, 1.08883));
+
+        assert_eq!(
+            parse_color_function("color(prophoto-rgb 1 0 0)"),
+            Err(ParseColorError::UnknownColorSpace)
+        );
+        assert_eq!(
+            parse_color_function("color(srgb 1 0)"),
+            Err(ParseColorError::InvalidSyntax)
+        );
+        assert_eq!(
+            parse_color_function("color(srgb 1 0 0 / 0.5)"),
+            Err(ParseColorError::InvalidSyntax)
+        );
+        assert_eq!(
+            parse_color_function("rgb(1 0 0)"),
+            Err(ParseColorError::InvalidSyntax)
+        );
+        assert_eq!(
+            parse_color_function("color(srgb 1 x 0)"),
+            Err(ParseColorError::InvalidDigit)
+        );
+    }
+}
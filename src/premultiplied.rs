@@ -0,0 +1,209 @@
+//! Defines `PremultipliedRgba` for Rgb colors with the alpha already applied to the channels
+
+use crate::alpha::Rgba;
+use crate::channel::{ColorChannel, PosNormalBoundedChannel, PosNormalChannelScalar};
+use crate::color::Lerp;
+use crate::rgb::Rgb;
+use num_traits::Float;
+use std::fmt;
+use std::ops;
+
+/// An `Rgb` color with an alpha channel, with the color channels premultiplied by alpha
+///
+/// In premultiplied form, each color channel holds $`C \cdot \alpha`$ rather than $`C`$. Blending,
+/// filtering and interpolation are then linear operations on all four channels, whereas doing
+/// the same on straight alpha [`Rgba`](type.Rgba.html) colors gives the fully transparent color's
+/// channels too much weight, leading to dark fringes.
+///
+/// Having a distinct type lets the compiler keep track of which form a color is in. Convert from
+/// and to straight alpha with `From` and `Into`:
+///
+/// ```rust
+/// use prisma::{PremultipliedRgba, Rgb, Rgba};
+///
+/// let straight = Rgba::new(Rgb::new(1.0, 0.5, 0.0), 0.5);
+/// let premultiplied = PremultipliedRgba::from(straight);
+/// assert_eq!(premultiplied.color(), &Rgb::new(0.5, 0.25, 0.0));
+/// assert_eq!(Rgba::from(premultiplied), straight);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PremultipliedRgba<T> {
+    color: Rgb<T>,
+    alpha: PosNormalBoundedChannel<T>,
+}
+
+impl<T> PremultipliedRgba<T>
+where
+    T: PosNormalChannelScalar,
+{
+    /// Construct a `PremultipliedRgba` from color channels that are already premultiplied
+    pub fn new(color: Rgb<T>, alpha: T) -> Self {
+        PremultipliedRgba {
+            color,
+            alpha: PosNormalBoundedChannel::new(alpha),
+        }
+    }
+    /// Break apart a `PremultipliedRgba` into the premultiplied color and the alpha value
+    pub fn decompose(self) -> (Rgb<T>, T) {
+        (self.color, self.alpha.0)
+    }
+    /// Returns a reference to the premultiplied color
+    pub fn color(&self) -> &Rgb<T> {
+        &self.color
+    }
+    /// Returns the alpha scalar
+    pub fn alpha(&self) -> T {
+        self.alpha.0.clone()
+    }
+}
+
+impl<T> From<Rgba<T>> for PremultipliedRgba<T>
+where
+    T: PosNormalChannelScalar + Float,
+{
+    fn from(straight: Rgba<T>) -> Self {
+        let (color, alpha) = straight.decompose();
+        let color = Rgb::new(
+            color.red() * alpha,
+            color.green() * alpha,
+            color.blue() * alpha,
+        );
+        PremultipliedRgba::new(color, alpha)
+    }
+}
+
+impl<T> From<PremultipliedRgba<T>> for Rgba<T>
+where
+    T: PosNormalChannelScalar + Float,
+{
+    /// Undo the premultiplication
+    ///
+    /// A fully transparent color has lost its color information, and becomes transparent black.
+    fn from(premultiplied: PremultipliedRgba<T>) -> Self {
+        let (color, alpha) = premultiplied.decompose();
+        if alpha == T::zero() {
+            return Rgba::new(Rgb::new(T::zero(), T::zero(), T::zero()), alpha);
+        }
+        let color = Rgb::new(
+            color.red() / alpha,
+            color.green() / alpha,
+            color.blue() / alpha,
+        );
+        Rgba::new(color, alpha)
+    }
+}
+
+impl<T> Lerp for PremultipliedRgba<T>
+where
+    T: PosNormalChannelScalar + Lerp,
+{
+    type Position = <T as Lerp>::Position;
+
+    fn lerp(&self, right: &Self, pos: Self::Position) -> Self {
+        PremultipliedRgba {
+            color: self.color.lerp(&right.color, pos.clone()),
+            alpha: self.alpha.lerp(&right.alpha, pos),
+        }
+    }
+}
+
+impl<T> ops::Add for PremultipliedRgba<T>
+where
+    T: PosNormalChannelScalar + Float,
+{
+    type Output = Self;
+
+    /// Add the channels of two colors, including alpha
+    ///
+    /// This is additive ("plus") compositing. The result may be outside the normal range.
+    fn add(self, rhs: Self) -> Self {
+        let color = Rgb::new(
+            self.color.red() + rhs.color.red(),
+            self.color.green() + rhs.color.green(),
+            self.color.blue() + rhs.color.blue(),
+        );
+        PremultipliedRgba::new(color, self.alpha() + rhs.alpha())
+    }
+}
+
+#[cfg(feature = "approx")]
+impl<T> approx::AbsDiffEq for PremultipliedRgba<T>
+where
+    T: PosNormalChannelScalar + approx::AbsDiffEq,
+    T::Epsilon: Clone,
+{
+    impl_abs_diff_eq!({color, alpha});
+}
+#[cfg(feature = "approx")]
+impl<T> approx::RelativeEq for PremultipliedRgba<T>
+where
+    T: PosNormalChannelScalar + approx::RelativeEq,
+    T::Epsilon: Clone,
+{
+    impl_rel_eq!({color, alpha});
+}
+#[cfg(feature = "approx")]
+impl<T> approx::UlpsEq for PremultipliedRgba<T>
+where
+    T: PosNormalChannelScalar + approx::UlpsEq,
+    T::Epsilon: Clone,
+{
+    impl_ulps_eq!({color, alpha});
+}
+
+impl<T> fmt::Display for PremultipliedRgba<T>
+where
+    T: PosNormalChannelScalar + fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "PremultipliedRgba({}, {})", self.color, self.alpha)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use approx::*;
+
+    #[test]
+    fn test_round_trip() {
+        let colors = [
+            Rgba::new(Rgb::new(0.2, 0.4, 0.6), 1.0),
+            Rgba::new(Rgb::new(0.9, 0.1, 0.5), 0.3),
+            Rgba::new(Rgb::new(1.0, 1.0, 1.0), 0.01),
+            Rgba::new(Rgb::new(0.0, 0.0, 0.0), 0.75),
+        ];
+        for straight in colors.iter() {
+            let premultiplied = PremultipliedRgba::from(*straight);
+            assert_relative_eq!(premultiplied.alpha(), straight.alpha());
+            let back: Rgba<f64> = premultiplied.into();
+            assert_relative_eq!(back, *straight, epsilon = 1e-12);
+        }
+
+        let transparent = PremultipliedRgba::from(Rgba::new(Rgb::new(0.4, 0.5, 0.6), 0.0));
+        assert_eq!(transparent.color(), &Rgb::new(0.0, 0.0, 0.0));
+        assert_eq!(
+            Rgba::from(transparent),
+            Rgba::new(Rgb::new(0.0, 0.0, 0.0), 0.0)
+        );
+    }
+
+    #[test]
+    fn test_lerp_add() {
+        let red = PremultipliedRgba::from(Rgba::new(Rgb::new(1.0, 0.0, 0.0), 1.0));
+        let clear = PremultipliedRgba::from(Rgba::new(Rgb::new(0.0, 0.0, 1.0), 0.0));
+        let half = red.lerp(&clear, 0.5);
+        assert_relative_eq!(half, PremultipliedRgba::new(Rgb::new(0.5, 0.0, 0.0), 0.5));
+        // The transparent blue contributes nothing to the hue of the blend
+        assert_relative_eq!(Rgba::from(half), Rgba::new(Rgb::new(1.0, 0.0, 0.0), 0.5));
+
+        let a = PremultipliedRgba::new(Rgb::new(0.1, 0.2, 0.3), 0.4);
+        let b = PremultipliedRgba::new(Rgb::new(0.3, 0.1, 0.0), 0.5);
+        assert_relative_eq!(
+            a + b,
+            PremultipliedRgba::new(Rgb::new(0.4, 0.3, 0.3), 0.9),
+            epsilon = 1e-12
+        );
+    }
+}
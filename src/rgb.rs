@@ -3,18 +3,24 @@
 //! Provides the [Rgb<T>](struct.Rgb.html) type.
 
 use crate::channel::{
-    AngularChannelScalar, ChannelCast, ChannelFormatCast, ColorChannel, PosNormalBoundedChannel,
-    PosNormalChannelScalar,
+    AngularChannelScalar, ChannelCast, ChannelFormatCast, ColorChannel, FreeChannelScalar,
+    PosNormalBoundedChannel, PosNormalChannelScalar,
 };
 use crate::chromaticity::ChromaticityCoordinates;
 use crate::color;
 use crate::color::{Broadcast, Color, FromTuple, HomogeneousColor};
+use crate::color_space::named::SRgb;
+use crate::color_space::ColorSpace;
 use crate::convert;
-use crate::encoding::EncodableColor;
+use crate::encoding::{EncodableColor, SrgbEncoding};
 use crate::hsl;
 use crate::hsv;
 use crate::hwb;
+use crate::lab::{rgb_to_lab_in_space, Lab};
+use crate::lchab::Lchab;
 use crate::tags::RgbTag;
+use crate::white_point::D65;
+use crate::xyy::XyY;
 use angle;
 #[cfg(feature = "approx")]
 use approx;
@@ -59,6 +65,17 @@ pub struct Rgb<T> {
     blue: PosNormalBoundedChannel<T>,
 }
 
+/// Selects one of the channels of an [`Rgb`](struct.Rgb.html) value
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum RgbChannel {
+    /// The red channel
+    Red,
+    /// The green channel
+    Green,
+    /// The blue channel
+    Blue,
+}
+
 impl<T> Rgb<T>
 where
     T: PosNormalChannelScalar,
@@ -72,6 +89,14 @@ where
         }
     }
 
+    impl_color_checked_new!(
+        fn new_checked(red: T, green: T, blue: T) {
+            crate::channel::debug_check_bounded::<PosNormalBoundedChannel<T>>(&red);
+            crate::channel::debug_check_bounded::<PosNormalBoundedChannel<T>>(&green);
+            crate::channel::debug_check_bounded::<PosNormalBoundedChannel<T>>(&blue);
+        }
+    );
+
     impl_color_color_cast_square!(
         Rgb { red, green, blue },
         chan_traits = { PosNormalChannelScalar }
@@ -103,22 +128,98 @@ where
     }
     /// Set the red channel value
     pub fn set_red(&mut self, val: T) {
+        crate::channel::debug_check_bounded::<PosNormalBoundedChannel<T>>(&val);
         self.red.0 = val;
     }
     /// Set the green channel value
     pub fn set_green(&mut self, val: T) {
+        crate::channel::debug_check_bounded::<PosNormalBoundedChannel<T>>(&val);
         self.green.0 = val;
     }
     /// Set the blue channel value
     pub fn set_blue(&mut self, val: T) {
+        crate::channel::debug_check_bounded::<PosNormalBoundedChannel<T>>(&val);
         self.blue.0 = val;
     }
+
+    /// Returns the value of the channel selected by `channel`
+    pub fn channel(&self, channel: RgbChannel) -> T {
+        match channel {
+            RgbChannel::Red => self.red(),
+            RgbChannel::Green => self.green(),
+            RgbChannel::Blue => self.blue(),
+        }
+    }
+
+    /// Rearrange the channels of `self`
+    ///
+    /// Each element of `pattern` selects which channel of `self` is used for the red, green and
+    /// blue channels of the result, in that order. Channels may be repeated.
+    ///
+    /// ```
+    /// use prisma::{Rgb, RgbChannel};
+    ///
+    /// let color = Rgb::new(10u8, 20, 30);
+    /// let swizzled = color.swizzle([RgbChannel::Blue, RgbChannel::Red, RgbChannel::Red]);
+    /// assert_eq!(swizzled, Rgb::new(30, 10, 10));
+    /// ```
+    pub fn swizzle(&self, pattern: [RgbChannel; 3]) -> Rgb<T> {
+        Rgb::new(
+            self.channel(pattern[0]),
+            self.channel(pattern[1]),
+            self.channel(pattern[2]),
+        )
+    }
+
+    /// Returns `self` with the channels in blue, green, red order
+    pub fn bgr(&self) -> Rgb<T> {
+        self.swizzle([RgbChannel::Blue, RgbChannel::Green, RgbChannel::Red])
+    }
+    /// Returns `self` with the channels in blue, red, green order
+    pub fn brg(&self) -> Rgb<T> {
+        self.swizzle([RgbChannel::Blue, RgbChannel::Red, RgbChannel::Green])
+    }
+    /// Returns `self` with the channels in green, blue, red order
+    pub fn gbr(&self) -> Rgb<T> {
+        self.swizzle([RgbChannel::Green, RgbChannel::Blue, RgbChannel::Red])
+    }
+    /// Returns `self` with the channels in green, red, blue order
+    pub fn grb(&self) -> Rgb<T> {
+        self.swizzle([RgbChannel::Green, RgbChannel::Red, RgbChannel::Blue])
+    }
+    /// Returns `self` with the channels in red, blue, green order
+    pub fn rbg(&self) -> Rgb<T> {
+        self.swizzle([RgbChannel::Red, RgbChannel::Blue, RgbChannel::Green])
+    }
+
+    /// Returns the color as a feature vector `[red, green, blue]`
+    ///
+    /// Each channel is converted to `f64` in $`[0, 1]`$, so integer and float colors give the
+    /// same layout and scale.
+    pub fn to_feature_vector(&self) -> [f64; 3]
+    where
+        T: ChannelFormatCast<f64>,
+    {
+        [self.red().cast(), self.green().cast(), self.blue().cast()]
+    }
 }
 
 impl<T> Rgb<T>
 where
     T: PosNormalChannelScalar + num_traits::Float,
 {
+    /// Construct a floating point `Rgb` from 8-bit channel values
+    ///
+    /// Each channel is divided by 255, so `Rgb::from_u8(255, 128, 0)` is about
+    /// `(1.0, 0.502, 0.0)`. This is the same as `Rgb::new(r, g, b).color_cast()`, but reads better
+    /// when the float type is inferred from how the color is used.
+    pub fn from_u8(r: u8, g: u8, b: u8) -> Self
+    where
+        u8: ChannelFormatCast<T>,
+    {
+        Rgb::new(r, g, b).color_cast()
+    }
+
     /// Compute the [`ChromaticityCooridinates`](../chromaticity/struct.ChromaticityCoordinates.html)
     /// for an `Rgb` instance
     pub fn chromaticity_coordinates(&self) -> ChromaticityCoordinates<T> {
@@ -131,8 +232,543 @@ where
 
         ChromaticityCoordinates { alpha, beta }
     }
+
+    /// Round each channel to the nearest of the $`2^{bits}`$ levels representable with `bits` bits
+    ///
+    /// The result is still a float in $`[0, 1]`$, so further processing can be done before
+    /// converting to an integer format. Channels outside $`[0, 1]`$ are clamped first. A `bits`
+    /// value of zero is treated as one, and values beyond the precision of `T`, 24 bits for `f32`
+    /// and 53 for `f64`, are treated as that precision.
+    pub fn quantize_bits(self, bits: u8) -> Self {
+        self.quantize_steps(Self::bit_depth_steps(bits))
+    }
+
+    /// Reduce each channel to `levels` evenly spaced values in $`[0, 1]`$
+    ///
+    /// Unlike [`quantize_bits`](#method.quantize_bits), the number of levels need not be a power
+    /// of two. Channels outside $`[0, 1]`$ are clamped first. A `levels` value below two is treated
+    /// as two.
+    pub fn posterize(self, levels: u8) -> Self {
+        let steps: T = cast(levels.max(2) - 1).unwrap();
+        self.quantize_steps(steps)
+    }
+
+    /// Returns the complementary color, with the hue rotated by half a turn
+    ///
+    /// The color is converted to `Hsl`, its hue is rotated and the result is converted back, so
+    /// saturation and lightness are preserved. Grays have no hue and are returned unchanged.
+    ///
+    /// This is not the same as `Invert::invert`, which subtracts each channel from one. Inverting
+    /// also rotates the hue, but flips the lightness as well, turning a dark red into a light cyan
+    /// rather than a dark one.
+    pub fn complement(&self) -> Self {
+        let hsl: hsl::Hsl<T> = convert::FromColor::from_color(self);
+        convert::FromColor::from_color(&hsl.complement())
+    }
+
+    /// Scale a linear `Rgb` color by `stops` photographic stops
+    ///
+    /// Each stop doubles the amount of light, so every channel is multiplied by $`2^{stops}`$.
+    /// Negative values darken the color. `self` must be linearly encoded for the result to be
+    /// meaningful, and channels may end up above one, as is usual in HDR workflows.
+    pub fn apply_exposure(self, stops: T) -> Self {
+        let scale = stops.exp2();
+        Rgb::new(
+            self.red() * scale,
+            self.green() * scale,
+            self.blue() * scale,
+        )
+    }
+
+    /// Compress a linear HDR color into $`[0, 1]`$ with the extended Reinhard operator
+    ///
+    /// Each channel is mapped with
+    ///
+    /// ```math
+    /// C' = \frac{C (1 + C / w^2)}{1 + C}
+    /// ```
+    ///
+    /// where $`w`$ is `white_point`, the smallest value that maps to one. Values above it map
+    /// above one, and negative values stay negative. Dark values are nearly unchanged while highlights are rolled off smoothly.
+    /// Because the channels are mapped independently, saturated highlights drift towards white
+    /// and their hue can shift; see [`tone_map_hue_preserving`](#method.tone_map_hue_preserving).
+    pub fn tone_map_reinhard(self, white_point: T) -> Self {
+        let white_sq = white_point * white_point;
+        let map = |c: T| c * (T::one() + c / white_sq) / (T::one() + c);
+        Rgb::new(map(self.red()), map(self.green()), map(self.blue()))
+    }
+
+    /// Bring a linear HDR color into $`[0, 1]`$ while keeping the ratios between its channels
+    ///
+    /// If the largest channel is above one, every channel is divided by it, so the peak becomes
+    /// exactly one. Clamping each channel to one instead would flatten the brightest channels
+    /// together and shift the hue, turning a bright orange into yellow. Colors with no channel
+    /// above one are returned unchanged. Negative channels are not handled specially.
+    pub fn tone_map_hue_preserving(self) -> Self {
+        let peak = self.red().max(self.green()).max(self.blue());
+        if peak <= T::one() {
+            return self;
+        }
+        Rgb::new(self.red() / peak, self.green() / peak, self.blue() / peak)
+    }
+
+    /// Returns the largest level with `bits` bits, $`2^{bits} - 1`$
+    fn bit_depth_steps(bits: u8) -> T {
+        // Past the mantissa the levels are no longer exact, and the power soon overflows
+        let precision: i32 = cast(T::one() - T::epsilon().log2()).unwrap();
+        let two: T = cast(2.0).unwrap();
+        two.powi(i32::from(bits.max(1)).min(precision)) - T::one()
+    }
+
+    fn quantize_steps(self, steps: T) -> Self {
+        let quantize = |c: T| (c.max(T::zero()).min(T::one()) * steps).round() / steps;
+        Rgb::new(
+            quantize(self.red()),
+            quantize(self.green()),
+            quantize(self.blue()),
+        )
+    }
 }
 
+impl<T> Rgb<T>
+where
+    T: PosNormalChannelScalar + FreeChannelScalar + num_traits::Float,
+{
+    /// Returns the factor that scales `self` to have a relative luminance of `target_y`
+    ///
+    /// `self` is taken to be linearly encoded in `space`, and its luminance is the $`Y`$ component
+    /// of its XYZ value. Multiplying every channel by the result, or passing its base 2 logarithm
+    /// to [`apply_exposure`](#method.apply_exposure), brings the color to `target_y`. Black has no
+    /// finite exposure, and gives an infinite or NaN factor.
+    pub fn exposure_to_luminance<S>(&self, target_y: T, space: &S) -> T
+    where
+        S: ColorSpace<T>,
+    {
+        let (_, y, _) = space.apply_transform(self.clone().to_tuple());
+        target_y / y
+    }
+
+    /// Returns the relative luminance $`Y`$ of `self`, taken to be a color in `space`
+    ///
+    /// The color is decoded with the space's own encoding and transformed to XYZ with the matrix
+    /// derived from the space's primaries and white point. For sRgb this gives the familiar WCAG
+    /// weights $`0.2126 R + 0.7152 G + 0.0722 B`$, but wide gamut spaces weigh their channels
+    /// differently, so using the sRgb weights for them misjudges the luminance.
+    pub fn luminance_in<S>(&self, space: &S) -> T
+    where
+        T: ChannelFormatCast<f64>,
+        f64: ChannelFormatCast<T>,
+        S: ColorSpace<T>,
+    {
+        space.convert_rgb_to_xyz(self, &space.encoding()).y()
+    }
+
+    /// Returns the CIE 1931 $`xy`$ chromaticity and luminance of `self`, taken to be in `space`
+    ///
+    /// As with [`luminance_in`](#method.luminance_in), the color is decoded with the space's own
+    /// encoding and transformed to XYZ with the space's matrix. The $`xy`$ coordinates locate the
+    /// color on the chromaticity diagram, so a space's primaries map to the corners of its gamut
+    /// triangle, and every gray to its white point. Black has no chromaticity and gives all zeros.
+    ///
+    /// Panics:
+    /// =======
+    /// Panics if the color has a negative or non-finite XYZ value, which can only happen for
+    /// channels outside $`[0, 1]`$.
+    pub fn xy_chromaticity<S>(&self, space: &S) -> XyY<T>
+    where
+        T: ChannelFormatCast<f64>,
+        f64: ChannelFormatCast<T>,
+        S: ColorSpace<T>,
+    {
+        convert::FromColor::from_color(&space.convert_rgb_to_xyz(self, &space.encoding()))
+    }
+
+    /// Returns true if `self` is a light color, that reads best with dark text or overlays
+    ///
+    /// `self` is taken to be in `space`, using the space's own encoding. The color is light if its
+    /// relative luminance $`Y`$ is above the point where it contrasts equally with white and
+    /// black under the WCAG contrast ratio, $`\sqrt{1.05 \cdot 0.05} - 0.05 \approx 0.179`$.
+    pub fn is_light<S>(&self, space: &S) -> bool
+    where
+        T: ChannelFormatCast<f64>,
+        f64: ChannelFormatCast<T>,
+        S: ColorSpace<T>,
+    {
+        let luminance = self.luminance_in(space);
+        let threshold: T = cast((1.05f64 * 0.05).sqrt() - 0.05).unwrap();
+        luminance > threshold
+    }
+
+    /// Returns true if `self` is a dark color, that reads best with light text or overlays
+    ///
+    /// This is the inverse of [`is_light`](#method.is_light).
+    pub fn is_dark<S>(&self, space: &S) -> bool
+    where
+        T: ChannelFormatCast<f64>,
+        f64: ChannelFormatCast<T>,
+        S: ColorSpace<T>,
+    {
+        !self.is_light(space)
+    }
+
+    /// Returns a shadow or glow color that makes text of color `self` stand out
+    ///
+    /// `self` is taken to be an sRgb color. Light text, as decided by
+    /// [`is_light`](#method.is_light), gets a dark shadow, and dark text a light one. Rather than
+    /// pure black or white, the shadow keeps a tint of the text's own hue: each channel is moved
+    /// towards black or white by $`0.75 + 0.25 s`$ of the way, where $`s`$ is the relative
+    /// luminance $`Y`$ of light text, or $`1 - Y`$ for dark text. The lighter or darker the text,
+    /// the further its shadow is pushed to the opposite extreme, so pure white gets a black
+    /// shadow and pure black a white one.
+    pub fn readable_shadow(&self) -> Rgb<T>
+    where
+        T: ChannelFormatCast<f64>,
+        f64: ChannelFormatCast<T>,
+    {
+        let space = SRgb::new();
+        let luminance = self.luminance_in(&space);
+        let (target, strength) = if self.is_light(&space) {
+            (T::zero(), luminance)
+        } else {
+            (T::one(), T::one() - luminance)
+        };
+        let quarter: T = cast(0.25).unwrap();
+        let amount = (T::one() - quarter + quarter * strength).clamp(T::zero(), T::one());
+        let shift = |c: T| c + (target - c) * amount;
+        Rgb::new(shift(self.red()), shift(self.green()), shift(self.blue()))
+    }
+
+    /// Scale the chroma of `self` by `factor` in CIELAB, keeping its lightness and hue
+    ///
+    /// `self` is taken to be in `space`, using the space's own encoding. It is converted to CIELAB
+    /// relative to the space's white point and scaled with
+    /// [`Lab::scale_chroma`](struct.Lab.html#method.scale_chroma). Boosted colors that leave the
+    /// gamut of `space` are brought back by reducing their chroma, as in
+    /// [`Lchab::clip_with_delta`](struct.Lchab.html#method.clip_with_delta).
+    ///
+    /// Unlike raising the saturation in `Hsv`, this keeps the perceived brightness of the color.
+    pub fn scale_chroma<S>(self, factor: T, space: &S) -> Self
+    where
+        T: ChannelFormatCast<f64>,
+        f64: ChannelFormatCast<T>,
+        S: ColorSpace<T>,
+    {
+        let lab = rgb_to_lab_in_space(&self, space).scale_chroma(factor);
+        let lch: Lchab<T, D65> = convert::FromColor::from_color(&lab);
+        lch.clip_with_delta(space).0
+    }
+
+    /// Returns the neutral gray with the same CIELAB lightness $`L^*`$ as `self`
+    ///
+    /// `self` is taken to be in `space`, using the space's own encoding. Its $`L^*`$ is found
+    /// relative to the space's white point, and `Lab::new(L, 0, 0)` is converted back into
+    /// `space`. The result has equal red, green and blue channels, up to rounding, and is always
+    /// within gamut. Comparing a color against this gray isolates the contribution of its hue and
+    /// chroma.
+    pub fn equal_lightness_gray<S>(&self, space: &S) -> Self
+    where
+        T: ChannelFormatCast<f64>,
+        f64: ChannelFormatCast<T>,
+        S: ColorSpace<T>,
+    {
+        let lightness = rgb_to_lab_in_space(self, space).L();
+        let gray = Lchab::<T, D65>::new(lightness, T::zero(), angle::Deg(T::zero()));
+        gray.clip_with_delta(space).0
+    }
+
+    /// Estimate how `self` appears when seen against a `surround` color
+    ///
+    /// This is a simple model of simultaneous contrast, where a color seems pushed away from the
+    /// colors around it: a gray looks lighter on black than on white, and a neutral looks tinted
+    /// with the complement of a colored surround. Both colors are taken to be in `space`, using
+    /// the space's own encoding, and converted to CIELAB relative to the space's white point. The
+    /// result is
+    ///
+    /// ```math
+    /// Lab_{out} = Lab_{self} + strength \cdot (Lab_{self} - Lab_{surround})
+    /// ```
+    ///
+    /// converted back into `space`. Colors pushed out of gamut have their chroma reduced, as in
+    /// [`Lchab::clip_with_delta`](struct.Lchab.html#method.clip_with_delta). A `strength` of zero
+    /// returns `self` unchanged, up to rounding.
+    pub fn apparent_color<S>(&self, surround: &Rgb<T>, strength: T, space: &S) -> Self
+    where
+        T: ChannelFormatCast<f64>,
+        f64: ChannelFormatCast<T>,
+        S: ColorSpace<T>,
+    {
+        let (lab, surround) = (
+            rgb_to_lab_in_space(self, space),
+            rgb_to_lab_in_space(surround, space),
+        );
+        let induce = |c: T, s: T| c + strength * (c - s);
+        let lab = Lab::<T, D65>::new(
+            induce(lab.L(), surround.L()),
+            induce(lab.a(), surround.a()),
+            induce(lab.b(), surround.b()),
+        );
+        let lch: Lchab<T, D65> = convert::FromColor::from_color(&lab);
+        lch.clip_with_delta(space).0
+    }
+
+    /// Returns the color representable with `bits` bits per channel that looks closest to `self`
+    ///
+    /// [`quantize_bits`](#method.quantize_bits) rounds each channel on its own, which minimizes
+    /// the error of each channel but not the perceived error: the encoded channels contribute
+    /// unequally to lightness and hue. This instead tries every combination of the levels just
+    /// below and above each channel, at most eight colors, and returns the one closest to `self`
+    /// in CIELAB by $`\Delta E^*_{ab}`$. `self` is taken to be in `space`, using the space's own
+    /// encoding, and Lab is relative to the space's white point. When several candidates are
+    /// equally close, the per-channel rounding is preferred.
+    ///
+    /// As with `quantize_bits`, the result is a float in $`[0, 1]`$, channels outside
+    /// $`[0, 1]`$ are clamped first, a `bits` value of zero is treated as one and depths beyond
+    /// the precision of `T` are treated as that precision.
+    pub fn snap_to_bit_depth<S>(&self, bits: u8, space: &S) -> Self
+    where
+        T: ChannelFormatCast<f64>,
+        f64: ChannelFormatCast<T>,
+        S: ColorSpace<T>,
+    {
+        let target = rgb_to_lab_in_space(self, space);
+        let distance = |rgb: &Rgb<T>| {
+            let lab = rgb_to_lab_in_space(rgb, space);
+            let (l, a, b) = (
+                lab.L() - target.L(),
+                lab.a() - target.a(),
+                lab.b() - target.b(),
+            );
+            l * l + a * a + b * b
+        };
+
+        let steps = Self::bit_depth_steps(bits);
+        let levels = |c: T| {
+            let scaled = c.clamp(T::zero(), T::one()) * steps;
+            [scaled.floor() / steps, scaled.ceil() / steps]
+        };
+        let (reds, greens, blues) = (
+            levels(self.red()),
+            levels(self.green()),
+            levels(self.blue()),
+        );
+
+        let rounded = self.clone().quantize_bits(bits);
+        let mut best = (distance(&rounded), rounded);
+        for &red in reds.iter() {
+            for &green in greens.iter() {
+                for &blue in blues.iter() {
+                    let candidate = Rgb::new(red, green, blue);
+                    let dist = distance(&candidate);
+                    if dist < best.0 {
+                        best = (dist, candidate);
+                    }
+                }
+            }
+        }
+        best.1
+    }
+
+    /// Returns a key for sorting colors in a perceptual order
+    ///
+    /// `self` is taken to be in `space`, using the space's own encoding. It is converted to CIELAB
+    /// relative to the space's white point, and the returned tuple is
+    /// `(hue bucket, lightness, chroma)`:
+    ///
+    /// * The hue bucket is one of twelve 30° wide sectors numbered from 1, with red centered in
+    ///   the first. Near-neutral colors, with a chroma below 2, are put in bucket 0 ahead of them.
+    /// * Lightness is $`L^*`$ and chroma is $`C^*_{ab}`$, both scaled by 100 and rounded.
+    ///
+    /// Sorting by this key with `slice::sort_by_key` groups similar hues together and orders each
+    /// group from dark to light. As the key is a tuple of integers it is totally ordered, unlike
+    /// the channels themselves.
+    pub fn sort_key<S>(&self, space: &S) -> (u32, u32, u32)
+    where
+        T: ChannelFormatCast<f64>,
+        f64: ChannelFormatCast<T>,
+        S: ColorSpace<T>,
+    {
+        let lab = rgb_to_lab_in_space(self, space);
+
+        let hundred: T = cast(100.0).unwrap();
+        let chroma = lab.a().hypot(lab.b());
+        let hue_bucket = if chroma < cast(2.0).unwrap() {
+            0
+        } else {
+            let degrees = lab.b().atan2(lab.a()).to_degrees() + cast(375.0).unwrap();
+            let sector = (degrees / cast(30.0).unwrap()).floor();
+            cast::<_, u32>(sector).unwrap_or(0) % 12 + 1
+        };
+        let quantize = |v: T| cast::<_, u32>((v * hundred).round()).unwrap_or(0);
+
+        (hue_bucket, quantize(lab.L()), quantize(chroma))
+    }
+}
+
+impl Rgb<u8> {
+    /// Returns an approximate, human-readable name for the color, such as `"light muted blue"`
+    ///
+    /// The name is built from the color's `Hsl` decomposition: an optional lightness word
+    /// (`dark` or `light`), an optional saturation word (`muted` or `vivid`) and the name of the
+    /// nearest of ten hue families. Colors with very little chroma are named `black`, `white` or
+    /// a shade of `gray` instead.
+    ///
+    /// This is a simple heuristic meant for debugging output and the like, and the names are not
+    /// guaranteed to be stable between versions.
+    pub fn descriptive_name(&self) -> String {
+        let color: Rgb<f32> = self.color_cast();
+        let hsl: hsl::Hsl<f32> = convert::FromColor::from_color(&color);
+        let (r, g, b) = color.to_tuple();
+        let chroma = r.max(g).max(b) - r.min(g).min(b);
+        let lightness = hsl.lightness();
+
+        let lightness_word = if lightness < 0.3 {
+            Some("dark")
+        } else if lightness > 0.7 {
+            Some("light")
+        } else {
+            None
+        };
+
+        if chroma < 0.06 {
+            return if lightness < 0.08 {
+                "black".to_string()
+            } else if lightness > 0.95 {
+                "white".to_string()
+            } else {
+                lightness_word.map_or("gray".to_string(), |word| format!("{} gray", word))
+            };
+        }
+
+        let saturation_word = if hsl.saturation() < 0.35 {
+            Some("muted")
+        } else if hsl.saturation() > 0.8 {
+            Some("vivid")
+        } else {
+            None
+        };
+        let hue = hsl.hue().0;
+        let hue_name = HUE_FAMILIES
+            .iter()
+            .find(|&&(end, _)| hue < end)
+            .map_or("red", |&(_, name)| name);
+
+        lightness_word
+            .iter()
+            .chain(saturation_word.iter())
+            .chain(Some(hue_name).iter())
+            .cloned()
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Returns the index of the closest color in the xterm 256 color palette
+    ///
+    /// Only the 6x6x6 color cube (indices 16 to 231) and the grayscale ramp (232 to 255) are
+    /// considered, as the first 16 colors are commonly redefined by terminal themes. The closest
+    /// entry is found by $`\Delta E^*_{ab}`$, with `self` taken to be an sRgb color, so a gray
+    /// can map to the ramp even when a cube entry is closer channel by channel.
+    ///
+    /// Each call compares against all 240 entries, so cache the results when converting many
+    /// colors.
+    pub fn to_ansi_256(&self) -> u8 {
+        let palette: Vec<Rgb<u8>> = (16..=255u8).map(ansi_256_color).collect();
+        let index = crate::palette::nearest_index(&palette, self).unwrap();
+        16 + index as u8
+    }
+
+    /// Find the two palette colors whose mix best reproduces `self`, and how much of each to use
+    ///
+    /// Returns `(i, j, t)` such that `palette[i]` mixed with `palette[j]` in CIELAB by `t`, as
+    /// with `lerp`, is as close as possible to `self` by $`\Delta E^*_{ab}`$. A `t` of zero is all
+    /// of `palette[i]` and a `t` of one all of `palette[j]`. This recovers the two inks behind an
+    /// anti-aliased edge pixel, or a blend of two palette colors.
+    ///
+    /// `self` and the palette are taken to be sRgb colors. Every pair of entries is tried, with
+    /// the mix for each found by [`Lab::solve_mix`](struct.Lab.html#method.solve_mix), so this
+    /// takes time quadratic in the size of the palette. If no mix is closer than the nearest
+    /// single entry `k`, `(k, k, 0.0)` is returned.
+    ///
+    /// Panics:
+    /// =======
+    /// Panics if `palette` is empty.
+    pub fn decompose_into_palette(&self, palette: &[Rgb<u8>]) -> (usize, usize, f64) {
+        let nearest = crate::palette::nearest_index(palette, self)
+            .expect("Cannot decompose a color into an empty palette");
+        let srgb = SRgb::new();
+        let to_lab = |color: &Rgb<u8>| {
+            let xyz = srgb.convert_rgb_to_xyz(&color.color_cast::<f64>(), &srgb.encoding());
+            Lab::from_xyz(&xyz, D65)
+        };
+        let target = to_lab(self);
+        let labs: Vec<Lab<f64, D65>> = palette.iter().map(to_lab).collect();
+        let distance = |lab: &Lab<f64, D65>| {
+            let (l, a, b) = (
+                lab.L() - target.L(),
+                lab.a() - target.a(),
+                lab.b() - target.b(),
+            );
+            l * l + a * a + b * b
+        };
+
+        let mut best = ((nearest, nearest, 0.0), distance(&labs[nearest]));
+        for (i, a) in labs.iter().enumerate() {
+            for (j, b) in labs.iter().enumerate().skip(i + 1) {
+                let t = Lab::solve_mix(a, b, &target);
+                let dist = distance(&color::Lerp::lerp(a, b, t));
+                if dist < best.1 {
+                    best = ((i, j, t), dist);
+                }
+            }
+        }
+        best.0
+    }
+
+    /// Returns the escape sequence setting the 24-bit foreground or background terminal color
+    ///
+    /// This is `"\x1b[38;2;R;G;Bm"` if `foreground` is true and `"\x1b[48;2;R;G;Bm"` otherwise.
+    /// Write `"\x1b[0m"` afterwards to reset the color.
+    pub fn to_ansi_truecolor_escape(&self, foreground: bool) -> String {
+        let layer = if foreground { 38 } else { 48 };
+        format!(
+            "\x1b[{};2;{};{};{}m",
+            layer,
+            self.red(),
+            self.green(),
+            self.blue()
+        )
+    }
+}
+
+/// Returns the color of entry `index` in the xterm 256 color palette, for `index >= 16`
+fn ansi_256_color(index: u8) -> Rgb<u8> {
+    const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+    if index >= 232 {
+        let level = 8 + 10 * (index - 232);
+        Rgb::new(level, level, level)
+    } else {
+        let cube = index - 16;
+        Rgb::new(
+            CUBE_LEVELS[(cube / 36) as usize],
+            CUBE_LEVELS[(cube / 6 % 6) as usize],
+            CUBE_LEVELS[(cube % 6) as usize],
+        )
+    }
+}
+
+/// The upper bound, in degrees, of each hue family used by `Rgb::descriptive_name`
+const HUE_FAMILIES: [(f32, &str); 10] = [
+    (15.0, "red"),
+    (45.0, "orange"),
+    (70.0, "yellow"),
+    (90.0, "lime"),
+    (165.0, "green"),
+    (190.0, "cyan"),
+    (255.0, "blue"),
+    (285.0, "purple"),
+    (330.0, "magenta"),
+    (345.0, "pink"),
+];
+
 impl<T> Color for Rgb<T>
 where
     T: PosNormalChannelScalar,
@@ -208,6 +844,13 @@ where
         green:PosNormalBoundedChannel - 1, blue:PosNormalBoundedChannel - 2});
 }
 
+impl<T> color::FlattenMut for Rgb<T>
+where
+    T: PosNormalChannelScalar,
+{
+    impl_color_as_slice_mut!(T);
+}
+
 impl<T> EncodableColor for Rgb<T> where T: PosNormalChannelScalar {}
 
 #[cfg(feature = "approx")]
@@ -350,6 +993,121 @@ where
     }
 }
 
+/// Convert a buffer of `Rgb` colors to `Hsv`
+///
+/// Each color in `src` is converted with `Hsv::from_color` and written to the same position in
+/// `dst`, so the results are identical to converting element by element.
+///
+/// Panics:
+///
+/// Panics if `src` and `dst` have different lengths.
+pub fn rgb_slice_to_hsv<T, A>(src: &[Rgb<T>], dst: &mut [hsv::Hsv<T, A>])
+where
+    T: PosNormalChannelScalar + num_traits::Float,
+    A: AngularChannelScalar + angle::FromAngle<angle::Turns<T>>,
+{
+    assert_eq!(
+        src.len(),
+        dst.len(),
+        "source and destination buffers have different lengths"
+    );
+    for (from, to) in src.iter().zip(dst.iter_mut()) {
+        *to = convert::FromColor::from_color(from);
+    }
+}
+
+/// Returns the APCA lightness contrast $`L^c`$ of `text` drawn on `background`
+///
+/// APCA (the Accessible Perceptual Contrast Algorithm) is the contrast measure proposed for WCAG
+/// 3. Unlike the WCAG 2 contrast ratio it is not symmetric: the result is positive for dark text
+/// on a light background and negative for light text on a dark one ("reverse polarity"), and
+/// each polarity uses its own exponents, so light text on dark backgrounds is not overrated.
+/// Both colors are taken to be sRgb encoded. The magnitude runs from zero up to about 106 for
+/// black on white, and 108 for white on black; a magnitude of 60 is roughly the minimum for body
+/// text.
+///
+/// This implements APCA version 0.0.98G-4g: the screen luminance is estimated with a plain
+/// $`2.4`$ power curve, luminances near black are soft clamped, and contrasts below $`L^c`$ 10
+/// are reported as zero.
+pub fn apca_contrast<T>(text: &Rgb<T>, background: &Rgb<T>) -> T
+where
+    T: PosNormalChannelScalar + num_traits::Float + ChannelFormatCast<f64>,
+{
+    const BLACK_THRESHOLD: f64 = 0.022;
+    const BLACK_CLAMP: f64 = 1.414;
+    const DELTA_Y_MIN: f64 = 0.0005;
+    const SCALE: f64 = 1.14;
+    const LOW_CLIP: f64 = 0.1;
+    const LOW_OFFSET: f64 = 0.027;
+
+    let screen_y = |color: &Rgb<T>| -> f64 {
+        let color: Rgb<f64> = color.color_cast();
+        let y = 0.2126729 * color.red().powf(2.4)
+            + 0.7151522 * color.green().powf(2.4)
+            + 0.0721750 * color.blue().powf(2.4);
+        if y < BLACK_THRESHOLD {
+            y + (BLACK_THRESHOLD - y).powf(BLACK_CLAMP)
+        } else {
+            y
+        }
+    };
+    let (text_y, background_y) = (screen_y(text), screen_y(background));
+    if (background_y - text_y).abs() < DELTA_Y_MIN {
+        return T::zero();
+    }
+
+    let contrast = if background_y > text_y {
+        let sapc = (background_y.powf(0.56) - text_y.powf(0.57)) * SCALE;
+        if sapc < LOW_CLIP {
+            0.0
+        } else {
+            sapc - LOW_OFFSET
+        }
+    } else {
+        let sapc = (background_y.powf(0.65) - text_y.powf(0.62)) * SCALE;
+        if sapc > -LOW_CLIP {
+            0.0
+        } else {
+            sapc + LOW_OFFSET
+        }
+    };
+    cast(contrast * 100.0).unwrap()
+}
+
+/// Returns the color of a surface with reflectance `reflectance` lit by `illuminant`
+///
+/// This is the simple multiplicative shading model: each linear channel of the reflectance is
+/// multiplied by the same channel of the illuminant, so a surface reflects the part of the light
+/// it does not absorb. Both colors are taken to be sRgb encoded. They are decoded to linear light,
+/// multiplied and the result encoded again, as multiplying the encoded values directly would make
+/// the result too dark.
+///
+/// The reflectance is the fraction of light reflected in each channel and the illuminant the
+/// color of the light, both in $`[0, 1]`$. A white illuminant leaves the surface color unchanged.
+pub fn relight<T>(reflectance: &Rgb<T>, illuminant: &Rgb<T>) -> Rgb<T>
+where
+    T: PosNormalChannelScalar + ChannelFormatCast<f64>,
+    f64: ChannelFormatCast<T>,
+{
+    let decode = |color: &Rgb<T>| -> Rgb<f64> {
+        color
+            .color_cast::<f64>()
+            .srgb_encoded()
+            .decode()
+            .strip_encoding()
+    };
+    let (surface, light) = (decode(reflectance), decode(illuminant));
+    Rgb::new(
+        surface.red() * light.red(),
+        surface.green() * light.green(),
+        surface.blue() * light.blue(),
+    )
+    .linear()
+    .encode(SrgbEncoding::new())
+    .strip_encoding()
+    .color_cast()
+}
+
 impl<T, A> convert::FromColor<Rgb<T>> for hsl::Hsl<T, A>
 where
     T: PosNormalChannelScalar + num_traits::Float,
@@ -460,6 +1218,570 @@ mod test {
         assert_ulps_eq!(c1.lerp(&c2, 1.0_f32), Rgb::new(0.8_f32, 0.5, 0.1));
     }
 
+    #[test]
+    fn test_quantize() {
+        let colors = [
+            Rgb::new(0.0, 0.49, 0.51),
+            Rgb::new(1.0, 0.3, 0.8),
+            Rgb::new(-0.2, 1.3, 0.25),
+        ];
+        for color in colors.iter() {
+            let q = color.quantize_bits(1);
+            for c in q.as_slice() {
+                assert!(*c == 0.0 || *c == 1.0);
+            }
+        }
+        assert_eq!(colors[0].quantize_bits(1), Rgb::new(0.0, 0.0, 1.0));
+        assert_eq!(colors[0].quantize_bits(0), colors[0].quantize_bits(1));
+        assert_relative_eq!(
+            Rgb::new(0.1f32, 0.5, 0.9).quantize_bits(2),
+            Rgb::new(0.0, 2.0 / 3.0, 1.0)
+        );
+        let c = Rgb::new(0.25f64, 0.5, 0.75);
+        for ch in c.quantize_bits(8).as_slice() {
+            assert_relative_eq!((ch * 255.0).round(), ch * 255.0, epsilon = 1e-9);
+        }
+        assert_relative_eq!(c.quantize_bits(16), c, epsilon = 1e-4);
+        // Depths beyond the float's precision are limited to it rather than overflowing
+        for &bits in [53, 128, 255].iter() {
+            let quantized = c.quantize_bits(bits);
+            assert!(quantized.as_slice().iter().all(|ch| ch.is_finite()));
+            assert_relative_eq!(quantized, c, epsilon = 1e-12);
+        }
+        for &bits in [24, 128, 255].iter() {
+            let quantized = Rgb::new(0.1f32, 0.5, 0.9).quantize_bits(bits);
+            assert!(quantized.as_slice().iter().all(|ch| ch.is_finite()));
+            assert_relative_eq!(quantized, Rgb::new(0.1, 0.5, 0.9), epsilon = 1e-6);
+        }
+        assert_eq!(Rgb::<f32>::bit_depth_steps(255), 16_777_215.0);
+        assert_eq!(Rgb::<f64>::bit_depth_steps(255), 9_007_199_254_740_991.0);
+
+        assert_eq!(colors[2].posterize(3), Rgb::new(0.0, 1.0, 0.5));
+        assert_relative_eq!(
+            Rgb::new(0.1, 0.4, 0.7).posterize(4),
+            Rgb::new(0.0, 1.0 / 3.0, 2.0 / 3.0)
+        );
+        assert_eq!(colors[0].posterize(0), colors[0].quantize_bits(1));
+        assert_eq!(c.posterize(1), c.posterize(2));
+    }
+
+    #[test]
+    fn test_swizzle() {
+        let c1 = Rgb::new(10u8, 20, 30);
+        assert_eq!(c1.bgr(), Rgb::new(30, 20, 10));
+        assert_eq!(c1.bgr().bgr(), c1);
+        assert_eq!(c1.brg(), Rgb::new(30, 10, 20));
+        assert_eq!(c1.gbr(), Rgb::new(20, 30, 10));
+        assert_eq!(c1.grb(), Rgb::new(20, 10, 30));
+        assert_eq!(c1.rbg(), Rgb::new(10, 30, 20));
+        assert_eq!(c1.brg().gbr(), c1);
+
+        use RgbChannel::*;
+        assert_eq!(c1.swizzle([Red, Red, Red]), Rgb::broadcast(10));
+        assert_eq!(c1.swizzle([Red, Green, Blue]), c1);
+        let c2 = Rgb::new(0.1, 0.5, 0.9);
+        assert_eq!(c2.swizzle([Blue, Blue, Green]), Rgb::new(0.9, 0.9, 0.5));
+        assert_eq!(c2.channel(Green), 0.5);
+    }
+
+    #[test]
+    fn test_complement() {
+        let c1 = Rgb::new(0.5, 0.25, 0.0);
+        assert_relative_eq!(c1.complement(), Rgb::new(0.0, 0.25, 0.5), epsilon = 1e-6);
+        assert_relative_eq!(c1.invert(), Rgb::new(0.5, 0.75, 1.0), epsilon = 1e-6);
+        assert!(c1.complement() != c1.invert());
+        assert_relative_eq!(c1.complement().complement(), c1, epsilon = 1e-6);
+
+        let c2 = Rgb::new(0.8, 0.1, 0.6);
+        assert_relative_eq!(c2.complement(), Rgb::new(0.1, 0.8, 0.3), epsilon = 1e-6);
+
+        let gray = Rgb::new(0.4, 0.4, 0.4);
+        assert_relative_eq!(gray.complement(), gray, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_sort_key() {
+        use crate::color_space::named::SRgb;
+        let space = SRgb::new();
+
+        let light_red = Rgb::new(1.0, 0.6, 0.6);
+        let dark_red = Rgb::new(0.5, 0.0, 0.05);
+        let red = Rgb::new(0.9, 0.1, 0.15);
+        let dark_blue = Rgb::new(0.05, 0.05, 0.4);
+        let blue = Rgb::new(0.2, 0.3, 1.0);
+        let gray = Rgb::new(0.5, 0.5, 0.5);
+        let black = Rgb::new(0.0, 0.0, 0.0);
+
+        let mut colors = vec![blue, light_red, gray, dark_blue, red, black, dark_red];
+        colors.sort_by_key(|c| c.sort_key(&space));
+        assert_eq!(
+            colors,
+            vec![black, gray, dark_red, red, light_red, dark_blue, blue]
+        );
+
+        let (bucket, lightness, chroma) = gray.sort_key(&space);
+        assert_eq!(bucket, 0);
+        assert!((lightness as i64 - 5339).abs() <= 1);
+        assert!(chroma < 10);
+        assert_eq!(Rgb::new(1.0, 1.0, 1.0).sort_key(&space).1, 10000);
+        assert_eq!(red.sort_key(&space).0, dark_red.sort_key(&space).0);
+        assert!(blue.sort_key(&space).0 != red.sort_key(&space).0);
+    }
+
+    #[test]
+    fn test_xy_chromaticity() {
+        use crate::color_space::named::{DisplayP3, SRgb};
+        let srgb = SRgb::new();
+        let primaries = [
+            (Rgb::new(1.0, 0.0, 0.0), (0.64, 0.33)),
+            (Rgb::new(0.0, 1.0, 0.0), (0.30, 0.60)),
+            (Rgb::new(0.0, 0.0, 1.0), (0.15, 0.06)),
+        ];
+        for &(ref color, (x, y)) in primaries.iter() {
+            let xy = color.xy_chromaticity(&srgb);
+            assert_relative_eq!(xy.x(), x, epsilon = 1e-4);
+            assert_relative_eq!(xy.y(), y, epsilon = 1e-4);
+            assert_relative_eq!(xy.Y(), color.luminance_in(&srgb), epsilon = 1e-12);
+        }
+        for &v in [1.0, 0.5, 0.1].iter() {
+            let gray = Rgb::new(v, v, v).xy_chromaticity(&srgb);
+            assert_relative_eq!(gray.x(), 0.3127, epsilon = 1e-4);
+            assert_relative_eq!(gray.y(), 0.3290, epsilon = 1e-4);
+        }
+        let red = Rgb::new(1.0, 0.0, 0.0).xy_chromaticity(&DisplayP3::new());
+        assert_relative_eq!(red.x(), 0.680, epsilon = 1e-4);
+        assert_relative_eq!(red.y(), 0.320, epsilon = 1e-4);
+        let black = Rgb::new(0.0, 0.0, 0.0).xy_chromaticity(&srgb);
+        assert_eq!(black, XyY::new(0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_luminance_in() {
+        use crate::color_space::named::{DisplayP3, Rec2020, SRgb};
+        let (srgb, p3, rec2020) = (SRgb::new(), DisplayP3::new(), Rec2020::new());
+        let wcag = |c: &Rgb<f64>| {
+            let linear = |v: f64| {
+                if v <= 0.04045 {
+                    v / 12.92
+                } else {
+                    ((v + 0.055) / 1.055).powf(2.4)
+                }
+            };
+            0.2126 * linear(c.red()) + 0.7152 * linear(c.green()) + 0.0722 * linear(c.blue())
+        };
+        let colors = [
+            Rgb::new(1.0, 1.0, 1.0),
+            Rgb::new(0.0, 0.0, 0.0),
+            Rgb::new(0.5, 0.5, 0.5),
+            Rgb::new(0.8, 0.3, 0.1),
+            Rgb::new(0.02, 0.6, 0.95),
+        ];
+        for color in colors.iter() {
+            assert_relative_eq!(color.luminance_in(&srgb), wcag(color), epsilon = 1e-4);
+        }
+
+        let green = Rgb::new(0.0, 1.0, 0.0);
+        assert_relative_eq!(green.luminance_in(&srgb), 0.7152, epsilon = 1e-4);
+        assert_relative_eq!(green.luminance_in(&p3), 0.6917, epsilon = 1e-4);
+        assert_relative_eq!(green.luminance_in(&rec2020), 0.6780, epsilon = 1e-4);
+        // Every space agrees on white, since they share the D65 white point
+        let white = Rgb::new(1.0, 1.0, 1.0);
+        assert_relative_eq!(white.luminance_in(&rec2020), 1.0, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_snap_to_bit_depth() {
+        let srgb = SRgb::<f64>::new();
+        let delta_e = |a: &Rgb<f64>, b: &Rgb<f64>| {
+            let to_lab =
+                |c: &Rgb<f64>| Lab::from_xyz(&srgb.convert_rgb_to_xyz(c, &srgb.encoding()), D65);
+            let (a, b) = (to_lab(a), to_lab(b));
+            ((a.L() - b.L()).powi(2) + (a.a() - b.a()).powi(2) + (a.b() - b.b()).powi(2)).sqrt()
+        };
+
+        // A bluish gray just below the rounding boundary of its blue channel. Rounding each channel
+        // gives a neutral gray, losing the tint.
+        let tinted = Rgb::new(3.0 / 13.0, 3.0 / 13.0, 4.0 / 13.0);
+        let naive = tinted.quantize_bits(3);
+        let snapped = tinted.snap_to_bit_depth(3, &srgb);
+        let (two, three) = (2.0 / 7.0, 3.0 / 7.0);
+        assert_relative_eq!(naive, Rgb::new(two, two, two), epsilon = 1e-12);
+        assert_relative_eq!(snapped, Rgb::new(two, two, three), epsilon = 1e-12);
+        assert!(delta_e(&snapped, &tinted) < delta_e(&naive, &tinted));
+
+        // Near black, rounding a small blue channel up adds more error than dropping it
+        let dark = Rgb::new(0.0, 0.0, 1.0 / 13.0);
+        assert_relative_eq!(dark.quantize_bits(3), Rgb::new(0.0, 0.0, 1.0 / 7.0));
+        assert_eq!(dark.snap_to_bit_depth(3, &srgb), Rgb::new(0.0, 0.0, 0.0));
+
+        let exact = Rgb::new(1.0, 3.0 / 7.0, 0.0);
+        assert_relative_eq!(exact.snap_to_bit_depth(3, &srgb), exact, epsilon = 1e-12);
+        assert_relative_eq!(tinted.snap_to_bit_depth(255, &srgb), tinted, epsilon = 1e-12);
+        let out_of_range = Rgb::new(1.4, -0.2, 0.5);
+        let snapped = out_of_range.snap_to_bit_depth(1, &srgb);
+        assert!(snapped.red() == 1.0 && snapped.green() == 0.0);
+        assert!(snapped.blue() == 0.0 || snapped.blue() == 1.0);
+        let c = Rgb::new(0.3, 0.6, 0.9);
+        assert_eq!(c.snap_to_bit_depth(0, &srgb), c.snap_to_bit_depth(1, &srgb));
+    }
+
+    #[test]
+    fn test_tone_map() {
+        let c1 = Rgb::new(0.05, 0.18, 0.5);
+        assert_relative_eq!(
+            c1.tone_map_reinhard(4.0),
+            Rgb::new(
+                0.05 * (1.0 + 0.05 / 16.0) / 1.05,
+                0.18 * (1.0 + 0.18 / 16.0) / 1.18,
+                0.5 * (1.0 + 0.5 / 16.0) / 1.5
+            ),
+            epsilon = 1e-12
+        );
+        let white = Rgb::new(4.0, 4.0, 4.0).tone_map_reinhard(4.0);
+        assert_relative_eq!(white, Rgb::new(1.0, 1.0, 1.0), epsilon = 1e-12);
+        let hdr = Rgb::new(6.0, 2.0, 0.5);
+        let mapped = hdr.tone_map_reinhard(8.0);
+        assert!(mapped.red() < 1.0 && mapped.red() > mapped.green());
+        // The highlight is compressed more than the shadows, which shifts the ratios
+        assert!(mapped.red() / mapped.green() < 3.0);
+
+        let orange = Rgb::new(8.0, 4.0, 1.0);
+        let mapped = orange.tone_map_hue_preserving();
+        assert_relative_eq!(mapped, Rgb::new(1.0, 0.5, 0.125), epsilon = 1e-12);
+        assert_relative_eq!(mapped.red() / mapped.green(), 2.0, epsilon = 1e-12);
+        assert_relative_eq!(mapped.green() / mapped.blue(), 4.0, epsilon = 1e-12);
+        assert_relative_eq!(
+            mapped.get_hue::<Deg<f64>>(),
+            orange.get_hue::<Deg<f64>>(),
+            epsilon = 1e-9
+        );
+        // Clamping each channel would push the color towards yellow
+        let bright = Rgb::new(2.0, 1.2, 0.5);
+        let clamped = bright.normalize();
+        let mapped = bright.tone_map_hue_preserving();
+        assert_relative_eq!(mapped.get_hue::<Deg<f64>>().0, 28.0, epsilon = 1e-6);
+        assert_relative_eq!(clamped.get_hue::<Deg<f64>>().0, 60.0, epsilon = 1e-6);
+
+        let peak = Rgb::new(0.2, 1.5, 0.9).tone_map_hue_preserving();
+        assert_relative_eq!(peak.green(), 1.0);
+        assert_relative_eq!(peak.red() / peak.blue(), 0.2 / 0.9, epsilon = 1e-12);
+        assert_eq!(c1.tone_map_hue_preserving(), c1);
+    }
+
+    #[test]
+    fn test_tone_map_out_of_range() {
+        // Channels above the white point map above one rather than being clamped
+        let above = Rgb::new(8.0, 4.0, 0.5).tone_map_reinhard(4.0);
+        assert_relative_eq!(above.red(), 8.0 * 1.5 / 9.0, epsilon = 1e-12);
+        assert_relative_eq!(above.green(), 1.0, epsilon = 1e-12);
+        assert!(above.red() > 1.0);
+
+        let negative = Rgb::new(-0.1, 0.5, 2.0).tone_map_reinhard(4.0);
+        assert_relative_eq!(
+            negative.red(),
+            -0.1 * (1.0 - 0.1 / 16.0) / 0.9,
+            epsilon = 1e-12
+        );
+        assert!(negative.red() < 0.0);
+
+        let mapped = Rgb::new(-0.2, 2.0, 1.0).tone_map_hue_preserving();
+        assert_relative_eq!(mapped, Rgb::new(-0.1, 1.0, 0.5), epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_is_light() {
+        use crate::color_space::named::SRgb;
+        let space = SRgb::new();
+
+        let yellow = Rgb::new(1.0, 1.0, 0.0);
+        assert!(yellow.is_light(&space));
+        assert!(!yellow.is_dark(&space));
+        let navy = Rgb::new(0.0, 0.0, 0.5);
+        assert!(navy.is_dark(&space));
+        assert!(!navy.is_light(&space));
+
+        assert!(Rgb::new(1.0, 1.0, 1.0).is_light(&space));
+        assert!(Rgb::new(0.0, 0.0, 0.0).is_dark(&space));
+        // Middle gray in sRgb has a luminance of about 0.216, so it is just on the light side
+        assert!(Rgb::new(0.5, 0.5, 0.5).is_light(&space));
+        assert!(Rgb::new(0.45, 0.45, 0.45).is_dark(&space));
+        assert!(Rgb::new(0.0, 0.6, 0.0).is_light(&space));
+        assert!(Rgb::new(0.8, 0.0, 0.0).is_dark(&space));
+    }
+
+    #[test]
+    fn test_readable_shadow() {
+        use crate::color_space::named::SRgb;
+        let space = SRgb::new();
+
+        assert_relative_eq!(
+            Rgb::new(1.0, 1.0, 1.0).readable_shadow(),
+            Rgb::new(0.0, 0.0, 0.0),
+            epsilon = 1e-6
+        );
+        assert_relative_eq!(
+            Rgb::new(0.0, 0.0, 0.0).readable_shadow(),
+            Rgb::new(1.0, 1.0, 1.0),
+            epsilon = 1e-6
+        );
+
+        let light = [
+            Rgb::new(1.0, 1.0, 0.0),
+            Rgb::new(0.9, 0.85, 0.8),
+            Rgb::new(0.5, 0.9, 0.6),
+        ];
+        for text in light.iter() {
+            let shadow = text.readable_shadow();
+            assert!(shadow.is_dark(&space));
+            assert!(shadow.red() <= text.red() * 0.25);
+        }
+        let dark = [
+            Rgb::new(0.0, 0.0, 0.5),
+            Rgb::new(0.2, 0.1, 0.1),
+            Rgb::new(0.6, 0.1, 0.3),
+        ];
+        for text in dark.iter() {
+            let shadow = text.readable_shadow();
+            assert!(shadow.is_light(&space));
+            assert!(1.0 - shadow.blue() <= (1.0 - text.blue()) * 0.25);
+        }
+
+        // The shadow keeps a tint of the text's hue
+        let shadow = Rgb::new(0.0, 0.0, 0.5).readable_shadow();
+        assert!(shadow.blue() > shadow.red() && shadow.red() == shadow.green());
+    }
+
+    #[test]
+    fn test_to_feature_vector() {
+        assert_eq!(Rgb::new(0u8, 51, 255).to_feature_vector(), [0.0, 0.2, 1.0]);
+        assert_eq!(
+            Rgb::new(0.25f32, 0.5, 1.0).to_feature_vector(),
+            [0.25, 0.5, 1.0]
+        );
+    }
+
+    #[test]
+    fn test_equal_lightness_gray() {
+        use crate::color_space::named::SRgb;
+        use crate::white_point::D65;
+        let space = SRgb::new();
+        let lightness =
+            |c: &Rgb<f64>| Lab::from_xyz(&space.convert_rgb_to_xyz(c, &space.encoding()), D65).L();
+
+        for c in [
+            Rgb::new(0.9, 0.2, 0.1),
+            Rgb::new(0.1, 0.3, 0.95),
+            Rgb::new(0.4, 0.4, 0.4),
+            Rgb::new(0.0, 0.0, 0.0),
+            Rgb::new(1.0, 1.0, 0.0),
+        ]
+        .iter()
+        {
+            let gray = c.equal_lightness_gray(&space);
+            assert_relative_eq!(gray.red(), gray.green(), epsilon = 1e-6);
+            assert_relative_eq!(gray.green(), gray.blue(), epsilon = 1e-6);
+            assert_relative_eq!(lightness(&gray), lightness(c), epsilon = 1e-6);
+        }
+        assert_relative_eq!(
+            Rgb::new(0.4, 0.4, 0.4).equal_lightness_gray(&space),
+            Rgb::new(0.4, 0.4, 0.4),
+            epsilon = 1e-6
+        );
+    }
+
+    #[test]
+    fn test_scale_chroma() {
+        use crate::color_space::named::SRgb;
+        use crate::white_point::D65;
+        let space = SRgb::new();
+        let to_lab =
+            |c: &Rgb<f64>| Lab::from_xyz(&space.convert_rgb_to_xyz(c, &space.encoding()), D65);
+        let chroma = |lab: &Lab<f64, D65>| lab.a().hypot(lab.b());
+
+        let muted = Rgb::new(0.6, 0.45, 0.4);
+        let boosted = muted.scale_chroma(1.5, &space);
+        let (before, after) = (to_lab(&muted), to_lab(&boosted));
+        assert_relative_eq!(after.L(), before.L(), epsilon = 1e-6);
+        assert_relative_eq!(chroma(&after), 1.5 * chroma(&before), epsilon = 1e-6);
+
+        let gray = muted.scale_chroma(0.0, &space);
+        assert_relative_eq!(gray.red(), gray.green(), epsilon = 1e-6);
+        assert_relative_eq!(gray.green(), gray.blue(), epsilon = 1e-6);
+        assert_relative_eq!(to_lab(&gray).L(), before.L(), epsilon = 1e-6);
+
+        // Boosting past the gamut clips while keeping the lightness
+        let vivid = Rgb::new(0.9, 0.2, 0.1);
+        let clipped = vivid.scale_chroma(3.0, &space);
+        assert!(clipped.is_normalized());
+        assert_relative_eq!(to_lab(&clipped).L(), to_lab(&vivid).L(), epsilon = 1e-4);
+        assert!(chroma(&to_lab(&clipped)) > chroma(&to_lab(&vivid)));
+    }
+
+    #[test]
+    fn test_exposure() {
+        use crate::color_space::named::SRgb;
+        let space = SRgb::new();
+        let luminance = |c: &Rgb<f64>| space.apply_transform(c.clone().to_tuple()).1;
+
+        let colors = [
+            Rgb::new(0.2, 0.4, 0.1),
+            Rgb::new(1.0, 0.0, 0.0),
+            Rgb::new(0.05, 0.05, 0.05),
+        ];
+        for color in colors.iter() {
+            for target in [0.18, 0.5, 1.0, 4.0].iter() {
+                let factor = color.exposure_to_luminance(*target, &space);
+                let exposed = color.apply_exposure(factor.log2());
+                assert_relative_eq!(luminance(&exposed), *target, epsilon = 1e-12);
+            }
+        }
+
+        let c = Rgb::new(0.1, 0.2, 0.3);
+        assert_relative_eq!(c.apply_exposure(0.0), c);
+        assert_relative_eq!(c.apply_exposure(1.0), Rgb::new(0.2, 0.4, 0.6));
+        assert_relative_eq!(c.apply_exposure(-2.0), Rgb::new(0.025, 0.05, 0.075));
+        assert!(Rgb::new(0.0, 0.0, 0.0)
+            .exposure_to_luminance(0.5, &space)
+            .is_infinite());
+    }
+
+    #[test]
+    fn test_rgb_slice_to_hsv() {
+        let mut src = Vec::new();
+        for r in 0..=16 {
+            for g in 0..=16 {
+                for b in 0..=16 {
+                    src.push(Rgb::new(r as f32 / 16.0, g as f32 / 16.0, b as f32 / 16.0));
+                }
+            }
+        }
+        src.push(Rgb::new(0.3127, 0.329, 0.1));
+        src.push(Rgb::new(1.2, -0.1, 0.5));
+
+        let mut dst = vec![Hsv::new(Deg(0.0), 0.0, 0.0); src.len()];
+        rgb_slice_to_hsv(&src, &mut dst);
+        for (rgb, hsv) in src.iter().zip(dst.iter()) {
+            let expected: Hsv<f32, Deg<f32>> = Hsv::from_color(rgb);
+            assert_eq!(*hsv, expected);
+        }
+
+        let src = [Rgb::new(0.25f64, 0.75, 0.5), Rgb::new(0.9, 0.1, 0.1)];
+        let mut dst = [Hsv::new(Turns(0.0), 0.0, 0.0); 2];
+        rgb_slice_to_hsv(&src, &mut dst);
+        assert_eq!(dst[0], Hsv::from_color(&src[0]));
+        assert_eq!(dst[1], Hsv::from_color(&src[1]));
+
+        rgb_slice_to_hsv::<f32, Deg<f32>>(&[], &mut []);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_rgb_slice_to_hsv_length_mismatch() {
+        let mut dst = vec![Hsv::new(Deg(0.0), 0.0, 0.0); 2];
+        rgb_slice_to_hsv(&[Rgb::new(0.5, 0.5, 0.5)], &mut dst);
+    }
+
+    #[test]
+    fn test_descriptive_name() {
+        let pale_blue = Rgb::new(173u8, 216, 230).descriptive_name();
+        assert!(pale_blue.contains("light"));
+        assert!(pale_blue.contains("blue"));
+        assert_eq!(pale_blue, "light blue");
+
+        assert_eq!(Rgb::new(255u8, 0, 0).descriptive_name(), "vivid red");
+        assert_eq!(Rgb::new(0u8, 0, 139).descriptive_name(), "dark vivid blue");
+        assert_eq!(Rgb::new(200u8, 120, 60).descriptive_name(), "orange");
+        assert_eq!(Rgb::new(107u8, 142, 35).descriptive_name(), "lime");
+        assert_eq!(Rgb::new(140u8, 150, 170).descriptive_name(), "muted blue");
+        assert_eq!(
+            Rgb::new(255u8, 105, 180).descriptive_name(),
+            "light vivid pink"
+        );
+        assert_eq!(Rgb::new(250u8, 0, 20).descriptive_name(), "vivid red");
+
+        assert_eq!(Rgb::new(0u8, 0, 0).descriptive_name(), "black");
+        assert_eq!(Rgb::new(255u8, 255, 255).descriptive_name(), "white");
+        assert_eq!(Rgb::new(128u8, 128, 128).descriptive_name(), "gray");
+        assert_eq!(Rgb::new(50u8, 52, 50).descriptive_name(), "dark gray");
+        assert_eq!(Rgb::new(210u8, 210, 215).descriptive_name(), "light gray");
+    }
+
+    #[test]
+    fn test_ansi() {
+        assert_eq!(Rgb::new(255u8, 0, 0).to_ansi_256(), 196);
+        assert_eq!(Rgb::new(0u8, 255, 0).to_ansi_256(), 46);
+        assert_eq!(Rgb::new(0u8, 0, 255).to_ansi_256(), 21);
+        assert_eq!(Rgb::new(0u8, 0, 0).to_ansi_256(), 16);
+        assert_eq!(Rgb::new(255u8, 255, 255).to_ansi_256(), 231);
+        assert_eq!(Rgb::new(95u8, 135, 175).to_ansi_256(), 67);
+        assert_eq!(Rgb::new(128u8, 128, 128).to_ansi_256(), 244);
+        assert_eq!(Rgb::new(250u8, 5, 8).to_ansi_256(), 196);
+        for index in 16..=255u8 {
+            assert_eq!(ansi_256_color(index).to_ansi_256(), index);
+        }
+
+        let color = Rgb::new(12u8, 200, 255);
+        assert_eq!(
+            color.to_ansi_truecolor_escape(true),
+            "\x1b[38;2;12;200;255m"
+        );
+        assert_eq!(
+            color.to_ansi_truecolor_escape(false),
+            "\x1b[48;2;12;200;255m"
+        );
+    }
+
+    #[test]
+    fn test_decompose_into_palette() {
+        use crate::color_space::ConvertFromXyz;
+        use crate::encoding::SrgbEncoding;
+        let srgb = SRgb::<f64>::new();
+        let to_lab = |c: &Rgb<u8>| {
+            let xyz = srgb.convert_rgb_to_xyz(&c.color_cast::<f64>(), &SrgbEncoding::new());
+            Lab::from_xyz(&xyz, D65)
+        };
+        let to_rgb = |lab: &Lab<f64, D65>| {
+            let linear: Rgb<f64> = srgb.convert_from_xyz_raw(&lab.to_xyz());
+            let encoded = linear.linear().encode(SrgbEncoding::new()).strip_encoding();
+            encoded.color_cast::<u8>()
+        };
+        let palette = [
+            Rgb::new(0u8, 0, 0),
+            Rgb::new(255u8, 255, 255),
+            Rgb::new(200u8, 30, 40),
+            Rgb::new(20u8, 60, 180),
+            Rgb::new(240u8, 200, 40),
+        ];
+
+        let mid = to_rgb(&to_lab(&palette[2]).lerp(&to_lab(&palette[3]), 0.5));
+        let (i, j, t) = mid.decompose_into_palette(&palette);
+        assert_eq!((i, j), (2, 3));
+        assert_relative_eq!(t, 0.5, epsilon = 0.02);
+
+        let edge = to_rgb(&to_lab(&palette[4]).lerp(&to_lab(&palette[0]), 0.25));
+        let (i, j, t) = edge.decompose_into_palette(&palette);
+        assert_eq!((i, j), (0, 4));
+        assert_relative_eq!(t, 0.75, epsilon = 0.02);
+
+        assert_eq!(palette[3].decompose_into_palette(&palette), (3, 3, 0.0));
+        let single = Rgb::new(10u8, 200, 10).decompose_into_palette(&palette[1..2]);
+        assert_eq!(single, (0, 0, 0.0));
+    }
+
+    #[test]
+    fn test_from_u8() {
+        assert_relative_eq!(
+            Rgb::from_u8(255, 128, 0),
+            Rgb::new(1.0, 0.50196, 0.0),
+            epsilon = 1e-5
+        );
+        let c: Rgb<f32> = Rgb::from_u8(255, 128, 0);
+        assert_relative_eq!(c, Rgb::new(1.0, 0.50196, 0.0), epsilon = 1e-5);
+        let c2 = Rgb::<f64>::from_u8(12, 200, 77);
+        assert_eq!(c2, Rgb::new(12u8, 200, 77).color_cast());
+        assert_eq!(c2.color_cast::<u8>(), Rgb::new(12, 200, 77));
+    }
+
     #[test]
     fn test_invert() {
         let c = Rgb::new(200u8, 0, 255);
@@ -580,4 +1902,119 @@ mod test {
         let deserialized = serde_json::from_str(&serialized).unwrap();
         assert_eq!(color, deserialized);
     }
+
+    #[test]
+    fn test_apparent_color() {
+        let space = SRgb::new();
+        let gray = Rgb::new(0.5, 0.5, 0.5f64);
+        let black = Rgb::new(0.0, 0.0, 0.0);
+        let white = Rgb::new(1.0, 1.0, 1.0);
+
+        let on_black = gray.apparent_color(&black, 0.1, &space);
+        let on_white = gray.apparent_color(&white, 0.1, &space);
+        assert!(on_black.red() > gray.red());
+        assert!(on_white.red() < gray.red());
+        assert_relative_eq!(on_black.red(), on_black.green(), epsilon = 1e-6);
+        assert_relative_eq!(on_black.red(), on_black.blue(), epsilon = 1e-6);
+
+        assert_relative_eq!(
+            gray.apparent_color(&black, 0.0, &space),
+            gray,
+            epsilon = 1e-9
+        );
+        assert_relative_eq!(
+            gray.apparent_color(&gray, 0.5, &space),
+            gray,
+            epsilon = 1e-9
+        );
+
+        // A red surround tints a neutral towards cyan
+        let on_red = gray.apparent_color(&Rgb::new(1.0, 0.0, 0.0), 0.1, &space);
+        assert!(on_red.red() < on_red.green());
+        assert!(on_red.red() < on_red.blue());
+
+        // Strong induction is clipped to the gamut
+        let clipped = Rgb::new(0.9, 0.2, 0.2).apparent_color(&black, 2.0, &space);
+        for c in [clipped.red(), clipped.green(), clipped.blue()].iter() {
+            assert!(*c >= 0.0 && *c <= 1.0);
+        }
+    }
+
+    #[test]
+    fn test_relight() {
+        let white = Rgb::new(255u8, 255, 255);
+        for color in [
+            Rgb::new(200u8, 90, 30),
+            Rgb::new(0, 0, 0),
+            Rgb::new(17, 128, 250),
+        ] {
+            assert_eq!(relight(&color, &white), color);
+            assert_eq!(relight(&white, &color), color);
+        }
+        let surface = Rgb::new(0.8, 0.6, 0.4);
+        assert_relative_eq!(
+            relight(&surface, &Rgb::new(1.0, 1.0, 1.0)),
+            surface,
+            epsilon = 1e-12
+        );
+
+        let red = Rgb::new(1.0, 0.0, 0.0);
+        let under_red = relight(&surface, &red);
+        assert_relative_eq!(under_red.red(), 0.8, epsilon = 1e-12);
+        assert!(under_red.green() < surface.green());
+        assert!(under_red.blue() < surface.blue());
+        assert_eq!(under_red.green(), 0.0);
+
+        // The channels are multiplied in linear light
+        let half = Rgb::broadcast(0.5)
+            .linear()
+            .encode(SrgbEncoding::new())
+            .strip_encoding();
+        let lit = relight(&half, &half).srgb_encoded().decode();
+        assert_relative_eq!(lit.red(), 0.25, epsilon = 1e-12);
+        assert_eq!(
+            relight(&surface, &Rgb::new(0.0, 0.0, 0.0)),
+            Rgb::new(0.0, 0.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn test_apca_contrast() {
+        let gray = Rgb::new(0x88u8, 0x88, 0x88).color_cast::<f64>();
+        let white = Rgb::new(1.0, 1.0, 1.0);
+        let black = Rgb::new(0.0, 0.0, 0.0);
+        let light_gray = Rgb::new(0xaau8, 0xaa, 0xaa).color_cast::<f64>();
+
+        assert_relative_eq!(
+            apca_contrast(&gray, &white),
+            63.056469930209424,
+            epsilon = 1e-9
+        );
+        assert_relative_eq!(
+            apca_contrast(&white, &gray),
+            -68.54146436644962,
+            epsilon = 1e-9
+        );
+        assert_relative_eq!(
+            apca_contrast(&black, &light_gray),
+            58.146262578561334,
+            epsilon = 1e-9
+        );
+        assert_relative_eq!(
+            apca_contrast(&light_gray, &black),
+            -56.24113336839742,
+            epsilon = 1e-9
+        );
+
+        assert!(apca_contrast(&black, &white) > 106.0);
+        assert!(apca_contrast(&white, &black) < -107.0);
+        assert_eq!(apca_contrast(&gray, &gray), 0.0);
+        // Very low contrasts are clipped to zero
+        let near_gray = Rgb::new(0x8au8, 0x8a, 0x8a).color_cast::<f64>();
+        assert_eq!(apca_contrast(&near_gray, &gray), 0.0);
+
+        let text = Rgb::new(0.2f32, 0.3, 0.4);
+        let background = Rgb::new(0.9f32, 0.85, 0.8);
+        assert!(apca_contrast(&text, &background) > 60.0);
+    }
 }
@@ -3,7 +3,7 @@
 use crate::channel::{
     ChannelCast, ChannelFormatCast, ColorChannel, PosNormalBoundedChannel, PosNormalChannelScalar,
 };
-use crate::color::{Bounded, Broadcast, Color, Flatten, FromTuple, HomogeneousColor, Lerp};
+use crate::color::{Bounded, Broadcast, Color, Flatten, FlattenMut, FromTuple, HomogeneousColor, Lerp};
 use crate::convert::FromColor;
 use crate::encoding::EncodableColor;
 use crate::rgb::Rgb;
@@ -134,6 +134,7 @@ where
     }
     /// Set the intensity value
     pub fn set_intensity(&mut self, val: T) {
+        crate::channel::debug_check_bounded::<PosNormalBoundedChannel<T>>(&val);
         self.intensity.0 = val;
     }
 
@@ -219,6 +220,13 @@ where
         green:PosNormalBoundedChannel - 1, intensity:PosNormalBoundedChannel - 2});
 }
 
+impl<T> FlattenMut for Rgi<T>
+where
+    T: PosNormalChannelScalar + Float,
+{
+    impl_color_as_slice_mut!(T);
+}
+
 impl<T> Bounded for Rgi<T>
 where
     T: PosNormalChannelScalar + Float,
@@ -0,0 +1,602 @@
+//! Spectral data for the CIE standard observers
+//!
+//! The CIE color matching functions describe the response of the standard observer to light of a
+//! single wavelength. They define the XYZ space, and the chromaticities of the pure spectral colors
+//! trace out the spectral locus, the horseshoe-shaped boundary of all visible colors in the
+//! $`xy`$ chromaticity diagram.
+//!
+//! The module also describes the Planckian locus, the chromaticities of ideal blackbody radiators,
+//! which is used to give whites a correlated color temperature (CCT). CCT is defined in the
+//! CIE 1960 UCS diagram, available as [`Ucs1960`](struct.Ucs1960.html).
+
+use crate::channel::{ChannelFormatCast, FreeChannelScalar, PosNormalChannelScalar};
+use crate::color::{Bounded, Color};
+use crate::color_space::ColorSpace;
+use crate::convert::FromColor;
+use crate::encoding::EncodableColor;
+use crate::lms::bradford_adaptation;
+use crate::rgb::Rgb;
+use crate::xyy::XyY;
+use crate::xyz::Xyz;
+
+/// A CIE standard observer
+///
+/// See the [`white_point`](../white_point/index.html) module for a description of the two observers.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Observer {
+    /// The CIE 1931 $`2^{\circ}`$ standard observer
+    Deg2,
+    /// The CIE 1964 $`10^{\circ}`$ standard observer
+    Deg10,
+}
+
+/// The first wavelength of the color matching function tables, in nanometers
+const CMF_START_WAVELENGTH: u32 = 380;
+/// The spacing between the rows of the color matching function tables, in nanometers
+const CMF_WAVELENGTH_STEP: u32 = 10;
+
+/// The CIE 1931 $`2^{\circ}`$ color matching functions $`(\bar{x}, \bar{y}, \bar{z})`$
+const CIE_1931_CMF: [[f64; 3]; 33] = [
+    [0.001368, 0.000039, 0.006450],
+    [0.004243, 0.000120, 0.020050],
+    [0.014310, 0.000396, 0.067850],
+    [0.043510, 0.001210, 0.207400],
+    [0.134380, 0.004000, 0.645600],
+    [0.283900, 0.011600, 1.385600],
+    [0.348280, 0.023000, 1.747060],
+    [0.336200, 0.038000, 1.772110],
+    [0.290800, 0.060000, 1.669200],
+    [0.195360, 0.090980, 1.287640],
+    [0.095640, 0.139020, 0.812950],
+    [0.032010, 0.208020, 0.465180],
+    [0.004900, 0.323000, 0.272000],
+    [0.009300, 0.503000, 0.158200],
+    [0.063270, 0.710000, 0.078250],
+    [0.165500, 0.862000, 0.042160],
+    [0.290400, 0.954000, 0.020300],
+    [0.433450, 0.994950, 0.008750],
+    [0.594500, 0.995000, 0.003900],
+    [0.762100, 0.952000, 0.002100],
+    [0.916300, 0.870000, 0.001650],
+    [1.026300, 0.757000, 0.001100],
+    [1.062200, 0.631000, 0.000800],
+    [1.002600, 0.503000, 0.000340],
+    [0.854450, 0.381000, 0.000190],
+    [0.642400, 0.265000, 0.000050],
+    [0.447900, 0.175000, 0.000020],
+    [0.283500, 0.107000, 0.000000],
+    [0.164900, 0.061000, 0.000000],
+    [0.087400, 0.032000, 0.000000],
+    [0.046770, 0.017000, 0.000000],
+    [0.022700, 0.008210, 0.000000],
+    [0.011359, 0.004102, 0.000000],
+];
+
+/// The CIE 1964 $`10^{\circ}`$ color matching functions $`(\bar{x}_{10}, \bar{y}_{10}, \bar{z}_{10})`$
+const CIE_1964_CMF: [[f64; 3]; 33] = [
+    [0.000160, 0.000017, 0.000705],
+    [0.002362, 0.000253, 0.010482],
+    [0.019110, 0.002004, 0.086011],
+    [0.084736, 0.008756, 0.389366],
+    [0.204492, 0.021391, 0.972542],
+    [0.314679, 0.038676, 1.553480],
+    [0.383734, 0.062077, 1.967280],
+    [0.370702, 0.089456, 1.994800],
+    [0.302273, 0.128201, 1.745370],
+    [0.195618, 0.185190, 1.317560],
+    [0.080507, 0.253589, 0.772125],
+    [0.016172, 0.339133, 0.415254],
+    [0.003816, 0.460777, 0.218502],
+    [0.037465, 0.606741, 0.112044],
+    [0.117749, 0.761757, 0.060709],
+    [0.236491, 0.875211, 0.030451],
+    [0.376772, 0.961988, 0.013676],
+    [0.529826, 0.991761, 0.003988],
+    [0.705224, 0.997340, 0.000000],
+    [0.878655, 0.955552, 0.000000],
+    [1.014160, 0.868934, 0.000000],
+    [1.118520, 0.777405, 0.000000],
+    [1.123990, 0.658341, 0.000000],
+    [1.030480, 0.527963, 0.000000],
+    [0.856297, 0.398057, 0.000000],
+    [0.647467, 0.283493, 0.000000],
+    [0.431567, 0.179828, 0.000000],
+    [0.268329, 0.107633, 0.000000],
+    [0.152568, 0.060281, 0.000000],
+    [0.081261, 0.031800, 0.000000],
+    [0.040851, 0.015905, 0.000000],
+    [0.019941, 0.007749, 0.000000],
+    [0.009577, 0.003718, 0.000000],
+];
+
+impl Observer {
+    /// Returns the color matching functions of the observer
+    ///
+    /// Each row holds $`(\bar{x}, \bar{y}, \bar{z})`$ for one wavelength, starting at 380nm in
+    /// steps of 10nm up to 700nm.
+    pub fn color_matching_functions(self) -> &'static [[f64; 3]] {
+        match self {
+            Observer::Deg2 => &CIE_1931_CMF,
+            Observer::Deg10 => &CIE_1964_CMF,
+        }
+    }
+
+    /// Returns the wavelength in nanometers of row `index` of
+    /// [`color_matching_functions`](#method.color_matching_functions)
+    pub fn wavelength(self, index: usize) -> u32 {
+        CMF_START_WAVELENGTH + CMF_WAVELENGTH_STEP * index as u32
+    }
+}
+
+/// Returns whether a chromaticity lies inside the spectral locus of `observer`
+///
+/// Only colors whose chromaticity is inside the locus, the region bounded by the chromaticities of
+/// the spectral colors and closed by the line of purples, can be produced by real light. Points
+/// outside it are imaginary colors, such as those produced by extrapolating with the primaries of
+/// a color space.
+///
+/// The locus is approximated by a polygon through the spectral chromaticities at 10nm intervals,
+/// so points within a few thousandths of the true boundary may be misclassified. Only the `x` and
+/// `y` chromaticity coordinates are considered; the luminance `Y` is ignored.
+pub fn is_inside_spectral_locus<T>(color: &XyY<T>, observer: Observer) -> bool
+where
+    T: FreeChannelScalar + PosNormalChannelScalar + num_traits::Float,
+{
+    let px: f64 = num_traits::cast(color.x()).unwrap();
+    let py: f64 = num_traits::cast(color.y()).unwrap();
+
+    let locus: Vec<(f64, f64)> = observer
+        .color_matching_functions()
+        .iter()
+        .map(|&[x, y, z]| {
+            let sum = x + y + z;
+            (x / sum, y / sum)
+        })
+        .collect();
+
+    // Even-odd ray casting; the edge from the last point back to the first is the line of purples
+    let mut inside = false;
+    let mut prev = locus[locus.len() - 1];
+    for &cur in locus.iter() {
+        let ((x1, y1), (x2, y2)) = (prev, cur);
+        if (y1 > py) != (y2 > py) && px < x1 + (py - y1) * (x2 - x1) / (y2 - y1) {
+            inside = !inside;
+        }
+        prev = cur;
+    }
+    inside
+}
+
+/// A chromaticity in the CIE 1960 UCS $`uv`$ diagram
+///
+/// The 1960 uniform chromaticity scale is a projective transformation of XYZ:
+///
+/// ```math
+/// \begin{aligned}
+/// u &= \frac{4X}{X + 15Y + 3Z} \\
+/// v &= \frac{6Y}{X + 15Y + 3Z}
+/// \end{aligned}
+/// ```
+///
+/// It has been superseded by the CIE 1976 $`u'v'`$ diagram, which has $`u' = u`$ and
+/// $`v' = 1.5v`$, but correlated color temperature and the distance from the Planckian locus
+/// ($`\Delta_{uv}`$) are still defined in terms of it. Like `xy`, it carries no luminance, so
+/// going back to XYZ needs the `Y` value. Black has no chromaticity and gives NaN coordinates.
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
+pub struct Ucs1960<T> {
+    /// The $`u`$ coordinate
+    pub u: T,
+    /// The $`v`$ coordinate
+    pub v: T,
+}
+
+impl<T> Ucs1960<T>
+where
+    T: FreeChannelScalar + num_traits::Float,
+{
+    /// Construct a new `Ucs1960` from its coordinates
+    pub fn new(u: T, v: T) -> Self {
+        Ucs1960 { u, v }
+    }
+
+    /// Returns the $`uv`$ chromaticity of `xyz`
+    pub fn from_xyz(xyz: &Xyz<T>) -> Self {
+        let cast = |v: f64| -> T { num_traits::cast(v).unwrap() };
+        let denom = xyz.x() + cast(15.0) * xyz.y() + cast(3.0) * xyz.z();
+        Ucs1960::new(cast(4.0) * xyz.x() / denom, cast(6.0) * xyz.y() / denom)
+    }
+
+    /// Returns the XYZ value with this chromaticity and a luminance of `Y`
+    #[allow(non_snake_case)]
+    pub fn to_xyz(&self, Y: T) -> Xyz<T> {
+        let cast = |v: f64| -> T { num_traits::cast(v).unwrap() };
+        let x = cast(1.5) * self.u / self.v * Y;
+        let z = (cast(4.0) - self.u - cast(10.0) * self.v) / (cast(2.0) * self.v) * Y;
+        Xyz::new(x, Y, z)
+    }
+}
+
+/// The range of color temperatures, in kelvin, covered by the Planckian locus approximation
+const PLANCKIAN_CCT_RANGE: (f64, f64) = (1667.0, 25000.0);
+
+/// Returns the chromaticity of a blackbody radiator at `cct` kelvin, with a luminance of one
+///
+/// This uses the cubic spline approximation of Kim et al., which is accurate for the CIE 1931
+/// $`2^{\circ}`$ observer over $`[1667, 25000]`$ kelvin. Temperatures outside that range are
+/// clamped to it.
+pub fn planckian_locus<T>(cct: T) -> XyY<T>
+where
+    T: FreeChannelScalar + PosNormalChannelScalar + num_traits::Float,
+{
+    let (x, y) = planckian_xy(num_traits::cast(cct).unwrap());
+    XyY::new(
+        num_traits::cast(x).unwrap(),
+        num_traits::cast(y).unwrap(),
+        T::one(),
+    )
+}
+
+/// Returns the point on the Planckian locus nearest to `color`, and its temperature in kelvin
+///
+/// Distance is measured in the CIE 1960 $`uv`$ diagram ([`Ucs1960`](struct.Ucs1960.html)), the
+/// diagram the correlated color temperature is defined in, so the returned temperature is the
+/// CCT of `color`. The snapped color keeps the luminance `Y` of `color`.
+///
+/// The search is limited to the range of [`planckian_locus`](fn.planckian_locus.html), so colors
+/// far from the locus snap to one of its ends.
+pub fn snap_to_locus<T>(color: &XyY<T>) -> (XyY<T>, T)
+where
+    T: FreeChannelScalar + PosNormalChannelScalar + num_traits::Float,
+{
+    let target = xy_to_ucs((
+        num_traits::cast(color.x()).unwrap(),
+        num_traits::cast(color.y()).unwrap(),
+    ));
+    let distance = |mired: f64| {
+        let locus = xy_to_ucs(planckian_xy(1.0e6 / mired));
+        (locus.u - target.u).powi(2) + (locus.v - target.v).powi(2)
+    };
+
+    // The locus is close to evenly spaced in mireds, so scan in those before refining
+    const STEPS: usize = 200;
+    let (min_mired, max_mired) = (1.0e6 / PLANCKIAN_CCT_RANGE.1, 1.0e6 / PLANCKIAN_CCT_RANGE.0);
+    let step = (max_mired - min_mired) / STEPS as f64;
+    let best_step = (0..=STEPS)
+        .map(|i| (i, distance(min_mired + step * i as f64)))
+        .fold(
+            (0, f64::INFINITY),
+            |best, cur| if cur.1 < best.1 { cur } else { best },
+        )
+        .0;
+
+    // Golden section search in the neighborhood of the best sample
+    let inv_phi = (5.0f64.sqrt() - 1.0) / 2.0;
+    let mut lo = (min_mired + step * (best_step as f64 - 1.0)).max(min_mired);
+    let mut hi = (min_mired + step * (best_step as f64 + 1.0)).min(max_mired);
+    for _ in 0..60 {
+        let a = hi - inv_phi * (hi - lo);
+        let b = lo + inv_phi * (hi - lo);
+        if distance(a) < distance(b) {
+            hi = b;
+        } else {
+            lo = a;
+        }
+    }
+
+    let cct = 1.0e6 / ((lo + hi) / 2.0);
+    let (x, y) = planckian_xy(cct);
+    (
+        XyY::new(
+            num_traits::cast(x).unwrap(),
+            num_traits::cast(y).unwrap(),
+            color.Y(),
+        ),
+        num_traits::cast(cct).unwrap(),
+    )
+}
+
+/// Convert a color temperature in kelvin to mireds (micro reciprocal degrees)
+///
+/// The mired value is $`10^6 / K`$. Equal steps in mireds are close to equal perceived changes in
+/// the color of a light, whereas equal steps in kelvin are not.
+pub fn kelvin_to_mired<T>(kelvin: T) -> T
+where
+    T: num_traits::Float,
+{
+    num_traits::cast::<_, T>(1.0e6).unwrap() / kelvin
+}
+
+/// Convert a value in mireds to a color temperature in kelvin
+///
+/// This is the inverse of [`kelvin_to_mired`](fn.kelvin_to_mired.html), and has the same form.
+pub fn mired_to_kelvin<T>(mired: T) -> T
+where
+    T: num_traits::Float,
+{
+    num_traits::cast::<_, T>(1.0e6).unwrap() / mired
+}
+
+/// Shift the color temperature of `color` by `delta_mired` mireds along the Planckian locus
+///
+/// A positive delta lowers the temperature, giving a warmer color. The temperature of `color` is
+/// found with [`snap_to_locus`](fn.snap_to_locus.html), and its $`uv`$ offset from the locus is
+/// carried over to the shifted temperature, so a white that sits slightly off the locus (such as
+/// D65) keeps its tint. The luminance `Y` is unchanged.
+pub fn shift_mireds<T>(color: &XyY<T>, delta_mired: T) -> XyY<T>
+where
+    T: FreeChannelScalar + PosNormalChannelScalar + num_traits::Float,
+{
+    let (_, cct) = snap_to_locus(color);
+    let cct: f64 = num_traits::cast(cct).unwrap();
+    let delta: f64 = num_traits::cast(delta_mired).unwrap();
+    let shifted_cct = mired_to_kelvin(kelvin_to_mired(cct) + delta);
+
+    let uv = xy_to_ucs((
+        num_traits::cast(color.x()).unwrap(),
+        num_traits::cast(color.y()).unwrap(),
+    ));
+    let old = xy_to_ucs(planckian_xy(cct));
+    let new = xy_to_ucs(planckian_xy(shifted_cct));
+    let shifted = Ucs1960::new(new.u + uv.u - old.u, new.v + uv.v - old.v);
+    let (x, y) = ucs_to_xy(&shifted);
+    XyY::new(
+        num_traits::cast(x).unwrap(),
+        num_traits::cast(y).unwrap(),
+        color.Y(),
+    )
+}
+
+/// Warm or cool a buffer of 8-bit pixels by shifting the white point `mired_delta` mireds
+///
+/// The white point of `space` is moved along the Planckian locus with
+/// [`shift_mireds`](fn.shift_mireds.html), and each pixel is chromatically adapted from the
+/// original white to the shifted one using a von Kries transform in the Bradford `LMS` space. A
+/// positive delta lowers the temperature, warming the image, and a negative delta cools it.
+///
+/// Pixels are taken to be in `space` with its own encoding. The adaptation is done in linear
+/// light and combined with the space's RGB to XYZ transforms into a single matrix, which is then
+/// applied to every pixel in place. Channels pushed outside the gamut are clamped.
+pub fn apply_temperature_shift<T, S>(pixels: &mut [Rgb<u8>], mired_delta: f64, space: &S)
+where
+    T: FreeChannelScalar + PosNormalChannelScalar + num_traits::Float + ChannelFormatCast<f64>,
+    f64: ChannelFormatCast<T>,
+    u8: ChannelFormatCast<T>,
+    T: ChannelFormatCast<u8>,
+    S: ColorSpace<T>,
+{
+    let white = space.white_point();
+    let white_xyy = XyY::from_color(&white);
+    let shifted = shift_mireds(&white_xyy, num_traits::cast(mired_delta).unwrap());
+    let target = Xyz::from_color(&shifted);
+
+    let adaptation = bradford_adaptation(&white, &target);
+    let transform = space.get_inverse_xyz_transform() * adaptation * space.get_xyz_transform();
+
+    let encoding = space.encoding();
+    for pixel in pixels.iter_mut() {
+        let linear = pixel
+            .color_cast::<T>()
+            .encoded_as(encoding.clone())
+            .decode()
+            .strip_encoding();
+        let (r, g, b) = transform.transform_vector(linear.to_tuple());
+        *pixel = Rgb::new(r, g, b)
+            .normalize()
+            .linear()
+            .encode(encoding.clone())
+            .strip_encoding()
+            .color_cast();
+    }
+}
+
+fn planckian_xy(cct: f64) -> (f64, f64) {
+    let t = cct.max(PLANCKIAN_CCT_RANGE.0).min(PLANCKIAN_CCT_RANGE.1);
+    let (t2, t3) = (t * t, t * t * t);
+
+    let x = if t <= 4000.0 {
+        -0.2661239e9 / t3 - 0.2343589e6 / t2 + 0.8776956e3 / t + 0.179910
+    } else {
+        -3.0258469e9 / t3 + 2.1070379e6 / t2 + 0.2226347e3 / t + 0.240390
+    };
+    let (x2, x3) = (x * x, x * x * x);
+    let y = if t <= 2222.0 {
+        -1.1063814 * x3 - 1.34811020 * x2 + 2.18555832 * x - 0.20219683
+    } else if t <= 4000.0 {
+        -0.9549476 * x3 - 1.37418593 * x2 + 2.09137015 * x - 0.16748867
+    } else {
+        3.0817580 * x3 - 5.87338670 * x2 + 3.75112997 * x - 0.37001483
+    };
+    (x, y)
+}
+
+fn xy_to_ucs((x, y): (f64, f64)) -> Ucs1960<f64> {
+    // The chromaticity does not depend on the luminance, so any XYZ with these ratios will do
+    Ucs1960::from_xyz(&Xyz::new(x, y, 1.0 - x - y))
+}
+
+fn ucs_to_xy(uv: &Ucs1960<f64>) -> (f64, f64) {
+    let xyz = uv.to_xyz(1.0);
+    let sum = xyz.x() + xyz.y() + xyz.z();
+    (xyz.x() / sum, xyz.y() / sum)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::white_point::{deg_10, deg_2, WhitePoint, A, D65};
+
+    #[test]
+    fn test_cmf_tables() {
+        for observer in [Observer::Deg2, Observer::Deg10].iter() {
+            let cmf = observer.color_matching_functions();
+            assert_eq!(observer.wavelength(0), 380);
+            assert_eq!(observer.wavelength(cmf.len() - 1), 700);
+            // Each function integrates to the same value, putting illuminant E at x = y = 1/3
+            let sums = cmf.iter().fold([0.0; 3], |acc, row| {
+                [acc[0] + row[0], acc[1] + row[1], acc[2] + row[2]]
+            });
+            assert!((sums[0] - sums[1]).abs() / sums[1] < 2e-3);
+            assert!((sums[2] - sums[1]).abs() / sums[1] < 2e-3);
+        }
+    }
+
+    #[test]
+    fn test_is_inside_spectral_locus() {
+        let d65_2: XyY<f64> = deg_2::D65.get_xy_chromaticity();
+        let d65_10: XyY<f64> = deg_10::D65.get_xy_chromaticity();
+        assert!(is_inside_spectral_locus(&d65_2, Observer::Deg2));
+        assert!(is_inside_spectral_locus(&d65_10, Observer::Deg10));
+
+        let inside = [
+            (1.0 / 3.0, 1.0 / 3.0),
+            (0.64, 0.33),
+            (0.3, 0.6),
+            (0.15, 0.06),
+            (0.5, 0.2),
+        ];
+        for &(x, y) in inside.iter() {
+            let c = XyY::new(x, y, 0.5);
+            assert!(is_inside_spectral_locus(&c, Observer::Deg2));
+            assert!(is_inside_spectral_locus(&c, Observer::Deg10));
+        }
+
+        // Far outside the horseshoe, beyond the green spectral colors, below the line of purples
+        // and the all-zero chromaticity
+        let outside = [
+            (0.05, 0.9),
+            (0.0, 0.7),
+            (0.7, 0.1),
+            (0.4, 0.05),
+            (0.0, 0.0),
+            (0.8, 0.1),
+        ];
+        for &(x, y) in outside.iter() {
+            let c = XyY::new(x, y, 0.5);
+            assert!(!is_inside_spectral_locus(&c, Observer::Deg2));
+            assert!(!is_inside_spectral_locus(&c, Observer::Deg10));
+        }
+
+        // Between the two loci near 490nm
+        let c = XyY::new(0.05, 0.3, 1.0);
+        assert!(is_inside_spectral_locus(&c, Observer::Deg2));
+        assert!(!is_inside_spectral_locus(&c, Observer::Deg10));
+    }
+
+    #[test]
+    fn test_planckian_locus() {
+        let c1 = planckian_locus(2856.0f64);
+        assert!((c1.x() - 0.4476).abs() < 1e-3);
+        assert!((c1.y() - 0.4074).abs() < 1e-3);
+        assert_eq!(c1.Y(), 1.0);
+        let c2 = planckian_locus(6504.0f64);
+        assert!((c2.x() - 0.3135).abs() < 1e-3);
+        assert!((c2.y() - 0.3237).abs() < 1e-3);
+        assert_eq!(planckian_locus(1000.0), planckian_locus(1667.0));
+    }
+
+    #[test]
+    fn test_apply_temperature_shift() {
+        use crate::color_space::named::SRgb;
+
+        let space = SRgb::<f64>::new();
+        let original = vec![Rgb::new(128u8, 128, 128), Rgb::new(255, 255, 255)];
+
+        let mut warm = original.clone();
+        apply_temperature_shift(&mut warm, 40.0, &space);
+        let mut cool = original.clone();
+        apply_temperature_shift(&mut cool, -40.0, &space);
+        for ((w, c), o) in warm.iter().zip(cool.iter()).zip(original.iter()) {
+            let ratio = |p: &Rgb<u8>| f64::from(p.red()) / f64::from(p.blue());
+            assert!(ratio(w) > ratio(o));
+            assert!(ratio(c) < ratio(o));
+        }
+        assert!(warm[0].red() > warm[0].blue() + 10);
+        assert!(cool[0].blue() > cool[0].red() + 10);
+
+        let mut unchanged = original.clone();
+        apply_temperature_shift(&mut unchanged, 0.0, &space);
+        assert_eq!(unchanged, original);
+    }
+
+    #[test]
+    fn test_ucs1960() {
+        let d65 = Ucs1960::from_xyz(&WhitePoint::<f64>::get_xyz(&D65));
+        assert!((d65.u - 0.19784).abs() < 1e-5);
+        assert!((d65.v - 0.31222).abs() < 1e-5);
+        let a = Ucs1960::from_xyz(&WhitePoint::<f64>::get_xyz(&A));
+        assert!((a.u - 0.2560).abs() < 1e-4);
+        assert!((a.v - 0.3495).abs() < 1e-4);
+
+        let xyz = Xyz::new(0.2, 0.35, 0.6f64);
+        let uv = Ucs1960::from_xyz(&xyz);
+        let back = uv.to_xyz(0.35);
+        assert!((back.x() - xyz.x()).abs() < 1e-12);
+        assert_eq!(back.y(), 0.35);
+        assert!((back.z() - xyz.z()).abs() < 1e-12);
+
+        // v' in the 1976 diagram is 1.5 times v
+        let xyz = Xyz::from_color(&XyY::new(0.3127, 0.3290, 1.0f64));
+        let uv = Ucs1960::from_xyz(&xyz);
+        assert!((uv.u - 0.19783).abs() < 5e-5);
+        assert!((uv.v * 1.5 - 0.46832).abs() < 5e-5);
+        let from_xy = xy_to_ucs((0.3127, 0.3290));
+        assert!((from_xy.u - uv.u).abs() < 1e-12 && (from_xy.v - uv.v).abs() < 1e-12);
+        let (x, y) = ucs_to_xy(&uv);
+        assert!((x - 0.3127).abs() < 1e-12 && (y - 0.3290).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_snap_to_locus() {
+        for &cct in [2000.0f64, 2856.0, 5000.0, 6500.0, 10000.0].iter() {
+            let on_locus = planckian_locus(cct);
+            let (snapped, snapped_cct) = snap_to_locus(&on_locus);
+            assert!((snapped_cct - cct).abs() / cct < 1e-4);
+            assert!((snapped.x() - on_locus.x()).abs() < 1e-6);
+            assert!((snapped.y() - on_locus.y()).abs() < 1e-6);
+
+            // Move slightly off the locus, perpendicular to it in u'v'
+            let near = XyY::new(on_locus.x() - 0.002, on_locus.y() + 0.003, 0.5);
+            let (snapped, snapped_cct) = snap_to_locus(&near);
+            assert!((snapped_cct - cct).abs() / cct < 0.05);
+            assert_eq!(snapped.Y(), 0.5);
+            let locus_point = planckian_locus(snapped_cct);
+            assert!((snapped.x() - locus_point.x()).abs() < 1e-9);
+            assert!((snapped.y() - locus_point.y()).abs() < 1e-9);
+        }
+
+        let d65: XyY<f64> = deg_2::D65.get_xy_chromaticity();
+        let (_, cct) = snap_to_locus(&d65);
+        // The CCT of D65 is about 6504 K
+        assert!((cct - 6504.0).abs() < 10.0);
+    }
+
+    #[test]
+    fn test_mireds() {
+        assert_eq!(kelvin_to_mired(5000.0f64), 200.0);
+        assert_eq!(mired_to_kelvin(200.0f64), 5000.0);
+        assert!((mired_to_kelvin(kelvin_to_mired(6504.0f64)) - 6504.0).abs() < 1e-9);
+
+        let c1 = planckian_locus(5000.0f64);
+        let warmer = shift_mireds(&c1, 10.0);
+        let (_, cct) = snap_to_locus(&warmer);
+        assert!((cct - 1.0e6 / 210.0).abs() < 1.0);
+        assert!(warmer.x() > c1.x());
+        assert_eq!(warmer.Y(), c1.Y());
+
+        let back = shift_mireds(&warmer, -10.0);
+        assert!((back.x() - c1.x()).abs() < 1e-6);
+        assert!((back.y() - c1.y()).abs() < 1e-6);
+
+        // An off-locus white keeps its offset from the locus
+        let d65: XyY<f64> = deg_2::D65.get_xy_chromaticity();
+        let shifted = shift_mireds(&d65, 20.0);
+        let (on_locus, _) = snap_to_locus(&shifted);
+        let (d65_on_locus, _) = snap_to_locus(&d65);
+        let offset = |c: &XyY<f64>, l: &XyY<f64>| {
+            let c = xy_to_ucs((c.x(), c.y()));
+            let l = xy_to_ucs((l.x(), l.y()));
+            (c.u - l.u).hypot(c.v - l.v)
+        };
+        assert!((offset(&shifted, &on_locus) - offset(&d65, &d65_on_locus)).abs() < 2e-4);
+    }
+}
@@ -1,5 +1,9 @@
 //! Unit structs for identifying the various color models in generic contexts
 
+use crate::color::Color;
+use crate::lms::CieCam2002;
+use crate::white_point::D65;
+use crate::ycbcr::Bt709Model;
 use std::marker::PhantomData;
 
 /// A tag type uniquely identifying the [`Alpha`](../struct.Alpha.html) type in generic contexts
@@ -10,6 +14,8 @@ pub struct EHsiTag;
 pub struct HsiTag;
 /// A tag type uniquely identifying the [`Hsl`](../struct.Hsl.html) type in generic contexts
 pub struct HslTag;
+/// A tag type uniquely identifying the [`Hsp`](../struct.Hsp.html) type in generic contexts
+pub struct HspTag;
 /// A tag type uniquely identifying the [`Hsv`](../struct.Hsv.html) type in generic contexts
 pub struct HsvTag;
 /// A tag type uniquely identifying the [`Hwb`](../struct.Hwb.html) type in generic contexts
@@ -24,6 +30,8 @@ pub struct LchuvTag;
 pub struct LmsTag;
 /// A tag type uniquely identifying the [`Luv`](../struct.Luv.html) type in generic contexts
 pub struct LuvTag;
+/// A tag type uniquely identifying the [`OpponentLog`](../struct.OpponentLog.html) type in generic contexts
+pub struct OpponentLogTag;
 /// A tag type uniquely identifying the [`Rgb`](../struct.Rgb.html) type in generic contexts
 pub struct RgbTag;
 /// A tag type uniquely identifying the [`Rgi`](../struct.Rgi.html) type in generic contexts
@@ -34,3 +42,178 @@ pub struct XyYTag;
 pub struct XyzTag;
 /// A tag type uniquely identifying the [`YCbCr`](../struct.YCbCr.html) type in generic contexts
 pub struct YCbCrTag;
+/// A tag type uniquely identifying the [`YCoCg`](../struct.YCoCg.html) type in generic contexts
+pub struct YCoCgTag;
+
+/// A runtime identifier for each color model
+///
+/// The tag types above identify a model at compile time. `ColorModel` carries the same
+/// information as a value, so the supported models can be listed and described at runtime, for
+/// example to build an inspector that lets a user pick a model. [`Alpha`](../struct.Alpha.html) is
+/// a wrapper over the other models rather than a model itself, and is not listed.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ColorModel {
+    /// The [`Rgb`](../struct.Rgb.html) model
+    Rgb,
+    /// The [`Rgi`](../struct.Rgi.html) model
+    Rgi,
+    /// The [`Hsv`](../struct.Hsv.html) model
+    Hsv,
+    /// The [`Hsl`](../struct.Hsl.html) model
+    Hsl,
+    /// The [`Hsp`](../struct.Hsp.html) model
+    Hsp,
+    /// The [`Hsi`](../struct.Hsi.html) model
+    Hsi,
+    /// The [`eHsi`](../struct.eHsi.html) model
+    EHsi,
+    /// The [`Hwb`](../struct.Hwb.html) model
+    Hwb,
+    /// The [`YCbCr`](../ycbcr/struct.YCbCr.html) model
+    YCbCr,
+    /// The [`YCoCg`](../struct.YCoCg.html) model
+    YCoCg,
+    /// The [`OpponentLog`](../struct.OpponentLog.html) model
+    OpponentLog,
+    /// The [`Xyz`](../struct.Xyz.html) space
+    Xyz,
+    /// The [`XyY`](../struct.XyY.html) space
+    XyY,
+    /// The [`Lms`](../lms/struct.Lms.html) space
+    Lms,
+    /// The [`Lab`](../struct.Lab.html) space
+    Lab,
+    /// The [`Lchab`](../struct.Lchab.html) space
+    Lchab,
+    /// The [`Luv`](../struct.Luv.html) space
+    Luv,
+    /// The [`Lchuv`](../struct.Lchuv.html) space
+    Lchuv,
+}
+
+impl ColorModel {
+    /// Returns every supported color model
+    pub fn all() -> &'static [ColorModel] {
+        &[
+            ColorModel::Rgb,
+            ColorModel::Rgi,
+            ColorModel::Hsv,
+            ColorModel::Hsl,
+            ColorModel::Hsp,
+            ColorModel::Hsi,
+            ColorModel::EHsi,
+            ColorModel::Hwb,
+            ColorModel::YCbCr,
+            ColorModel::YCoCg,
+            ColorModel::OpponentLog,
+            ColorModel::Xyz,
+            ColorModel::XyY,
+            ColorModel::Lms,
+            ColorModel::Lab,
+            ColorModel::Lchab,
+            ColorModel::Luv,
+            ColorModel::Lchuv,
+        ]
+    }
+
+    /// Returns the name of the model, the same as the name of its type
+    pub fn tag(&self) -> &'static str {
+        match *self {
+            ColorModel::Rgb => "Rgb",
+            ColorModel::Rgi => "Rgi",
+            ColorModel::Hsv => "Hsv",
+            ColorModel::Hsl => "Hsl",
+            ColorModel::Hsp => "Hsp",
+            ColorModel::Hsi => "Hsi",
+            ColorModel::EHsi => "eHsi",
+            ColorModel::Hwb => "Hwb",
+            ColorModel::YCbCr => "YCbCr",
+            ColorModel::YCoCg => "YCoCg",
+            ColorModel::OpponentLog => "OpponentLog",
+            ColorModel::Xyz => "Xyz",
+            ColorModel::XyY => "XyY",
+            ColorModel::Lms => "Lms",
+            ColorModel::Lab => "Lab",
+            ColorModel::Lchab => "Lchab",
+            ColorModel::Luv => "Luv",
+            ColorModel::Lchuv => "Lchuv",
+        }
+    }
+
+    /// Returns the number of channels in the model, not counting alpha
+    ///
+    /// This is the same as `Color::num_channels` for the model's type.
+    pub fn channel_count(&self) -> u32 {
+        match *self {
+            ColorModel::Rgb => crate::Rgb::<f32>::num_channels(),
+            ColorModel::Rgi => crate::Rgi::<f32>::num_channels(),
+            ColorModel::Hsv => crate::Hsv::<f32>::num_channels(),
+            ColorModel::Hsl => crate::Hsl::<f32>::num_channels(),
+            ColorModel::Hsp => crate::Hsp::<f32>::num_channels(),
+            ColorModel::Hsi => crate::Hsi::<f32>::num_channels(),
+            ColorModel::EHsi => crate::eHsi::<f32>::num_channels(),
+            ColorModel::Hwb => crate::Hwb::<f32>::num_channels(),
+            ColorModel::YCbCr => crate::ycbcr::YCbCr::<f32, Bt709Model>::num_channels(),
+            ColorModel::YCoCg => crate::YCoCg::<f32>::num_channels(),
+            ColorModel::OpponentLog => crate::OpponentLog::<f32>::num_channels(),
+            ColorModel::Xyz => crate::Xyz::<f32>::num_channels(),
+            ColorModel::XyY => crate::XyY::<f32>::num_channels(),
+            ColorModel::Lms => crate::lms::Lms::<f32, CieCam2002>::num_channels(),
+            ColorModel::Lab => crate::Lab::<f32, D65>::num_channels(),
+            ColorModel::Lchab => crate::Lchab::<f32, D65>::num_channels(),
+            ColorModel::Luv => crate::Luv::<f32, D65>::num_channels(),
+            ColorModel::Lchuv => crate::Lchuv::<f32, D65>::num_channels(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::lms::Lms;
+    use crate::ycbcr::YCbCr;
+    use crate::*;
+
+    #[test]
+    fn test_color_model() {
+        let all = ColorModel::all();
+        assert_eq!(all.len(), 18);
+        assert!(all.contains(&ColorModel::Rgb));
+        assert!(all.contains(&ColorModel::Hsv));
+        assert!(all.contains(&ColorModel::Lab));
+        assert!(all.contains(&ColorModel::XyY));
+        let mut tags: Vec<_> = all.iter().map(|m| m.tag()).collect();
+        tags.sort_unstable();
+        tags.dedup();
+        assert_eq!(tags.len(), all.len());
+
+        assert_eq!(ColorModel::Rgb.tag(), "Rgb");
+        assert_eq!(ColorModel::EHsi.tag(), "eHsi");
+        assert_eq!(ColorModel::Lchuv.tag(), "Lchuv");
+
+        let counts = [
+            (ColorModel::Rgb, Rgb::<f32>::num_channels()),
+            (ColorModel::Rgi, Rgi::<f32>::num_channels()),
+            (ColorModel::Hsv, Hsv::<f32>::num_channels()),
+            (ColorModel::Hsl, Hsl::<f32>::num_channels()),
+            (ColorModel::Hsp, Hsp::<f32>::num_channels()),
+            (ColorModel::Hsi, Hsi::<f32>::num_channels()),
+            (ColorModel::EHsi, eHsi::<f32>::num_channels()),
+            (ColorModel::Hwb, Hwb::<f32>::num_channels()),
+            (ColorModel::YCbCr, YCbCr::<f32, Bt709Model>::num_channels()),
+            (ColorModel::YCoCg, YCoCg::<f32>::num_channels()),
+            (ColorModel::OpponentLog, OpponentLog::<f32>::num_channels()),
+            (ColorModel::Xyz, Xyz::<f32>::num_channels()),
+            (ColorModel::XyY, XyY::<f32>::num_channels()),
+            (ColorModel::Lms, Lms::<f32, CieCam2002>::num_channels()),
+            (ColorModel::Lab, Lab::<f32, D65>::num_channels()),
+            (ColorModel::Lchab, Lchab::<f32, D65>::num_channels()),
+            (ColorModel::Luv, Luv::<f32, D65>::num_channels()),
+            (ColorModel::Lchuv, Lchuv::<f32, D65>::num_channels()),
+        ];
+        assert_eq!(counts.len(), all.len());
+        for (model, count) in counts.iter() {
+            assert_eq!(model.channel_count(), *count);
+        }
+    }
+}
@@ -0,0 +1,261 @@
+//! Simulation of color vision deficiencies
+//!
+//! People with a color vision deficiency (CVD) are missing one of the three cone types, or have
+//! one with a shifted response. The most severe forms, the dichromacies, leave only two cone
+//! responses, so colors that differ only in the missing response look identical. This module
+//! simulates dichromatic vision, and checks whether a palette stays distinguishable under it.
+
+use crate::channel::{ChannelFormatCast, FreeChannelScalar, PosNormalChannelScalar};
+use crate::color::{Bounded, Color};
+use crate::color_space::{ColorSpace, ConvertToXyz};
+use crate::encoding::{EncodableColor, EncodedColor};
+use crate::lab::{xyz_to_lab_relative_to, Lab};
+use crate::linalg::Matrix3;
+use crate::rgb::Rgb;
+use crate::white_point::D65;
+use crate::xyz::Xyz;
+use num_traits::cast;
+
+/// The Hunt-Pointer-Estevez XYZ to LMS transform, normalized to D65
+///
+/// Unlike the sharpened transforms used for chromatic adaptation, this one models the actual
+/// cone fundamentals, which is what the simulation needs.
+const HUNT_POINTER_ESTEVEZ: [f64; 9] = [
+    0.4002, 0.7076, -0.0808, -0.2263, 1.1653, 0.0457, 0.0, 0.0, 0.9182,
+];
+
+/// A dichromatic color vision deficiency
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ColorVisionDeficiency {
+    /// The long wavelength (red) cones are missing
+    Protanopia,
+    /// The medium wavelength (green) cones are missing
+    Deuteranopia,
+    /// The short wavelength (blue) cones are missing
+    Tritanopia,
+}
+
+impl ColorVisionDeficiency {
+    /// Returns how `xyz` appears to a dichromat, relative to the reference white `white`
+    ///
+    /// This follows Viénot, Brettel and Mollon (1999): the color is converted to LMS and the
+    /// missing cone response is replaced by a combination of the other two, projecting the color
+    /// onto the plane of colors a dichromat sees the same as a trichromat. For protanopia and
+    /// deuteranopia the plane contains the white point and the sRgb blue primary, for tritanopia
+    /// the white point and the sRgb red primary. Those colors, and every gray, are unchanged.
+    pub fn simulate_xyz<T>(self, xyz: &Xyz<T>, white: &Xyz<T>) -> Xyz<T>
+    where
+        T: FreeChannelScalar + num_traits::Float,
+    {
+        let mut to_lms = Matrix3::<T>::zero();
+        for (cell, &value) in to_lms
+            .as_slice_mut()
+            .iter_mut()
+            .zip(HUNT_POINTER_ESTEVEZ.iter())
+        {
+            *cell = cast(value).unwrap();
+        }
+        let to_xyz = to_lms.clone().inverse().unwrap();
+
+        // The anchor chromaticity, in xyY with an arbitrary luminance since the plane contains
+        // the origin
+        let (x, y) = match self {
+            ColorVisionDeficiency::Protanopia | ColorVisionDeficiency::Deuteranopia => (0.15, 0.06),
+            ColorVisionDeficiency::Tritanopia => (0.64, 0.33),
+        };
+        let anchor = (
+            cast::<f64, T>(x / y).unwrap(),
+            T::one(),
+            cast::<f64, T>((1.0 - x - y) / y).unwrap(),
+        );
+
+        let (l, m, s) = to_lms.transform_vector(xyz.clone().to_tuple());
+        let w = to_lms.transform_vector(white.clone().to_tuple());
+        let a = to_lms.transform_vector(anchor);
+
+        // Express the missing response `r` as `c1 * p + c2 * q` of the remaining responses, with
+        // the coefficients chosen so the white point and the anchor are preserved
+        let solve = |w: (T, T, T), a: (T, T, T)| {
+            let (wr, wp, wq) = w;
+            let (ar, ap, aq) = a;
+            let det = wp * aq - wq * ap;
+            ((wr * aq - wq * ar) / det, (wp * ar - wr * ap) / det)
+        };
+        let (l, m, s) = match self {
+            ColorVisionDeficiency::Protanopia => {
+                let (c1, c2) = solve((w.0, w.1, w.2), (a.0, a.1, a.2));
+                (c1 * m + c2 * s, m, s)
+            }
+            ColorVisionDeficiency::Deuteranopia => {
+                let (c1, c2) = solve((w.1, w.0, w.2), (a.1, a.0, a.2));
+                (l, c1 * l + c2 * s, s)
+            }
+            ColorVisionDeficiency::Tritanopia => {
+                let (c1, c2) = solve((w.2, w.0, w.1), (a.2, a.0, a.1));
+                (l, m, c1 * l + c2 * m)
+            }
+        };
+
+        let (x, y, z) = to_xyz.transform_vector((l, m, s));
+        Xyz::new(x, y, z)
+    }
+
+    /// Returns how `color`, in `space` with the space's own encoding, appears to a dichromat
+    ///
+    /// The simulated color is clamped into the gamut of `space` and encoded like the input. See
+    /// [`simulate_xyz`](#method.simulate_xyz) for the model used.
+    pub fn simulate<T, S>(self, color: &Rgb<T>, space: &S) -> Rgb<T>
+    where
+        T: FreeChannelScalar + PosNormalChannelScalar + ChannelFormatCast<f64>,
+        f64: ChannelFormatCast<T>,
+        S: ColorSpace<T>,
+    {
+        let xyz = space.convert_rgb_to_xyz(color, &space.encoding());
+        let simulated = self.simulate_xyz(&xyz, &space.white_point());
+        let (r, g, b) = space
+            .get_inverse_xyz_transform()
+            .transform_vector(simulated.to_tuple());
+        Rgb::new(r, g, b)
+            .normalize()
+            .linear()
+            .encode(space.encoding())
+            .strip_encoding()
+    }
+}
+
+/// Returns true if every pair of colors in `palette` stays distinguishable under `deficiency`
+///
+/// Each color is interpreted as being in `space` with the space's own encoding, simulated with
+/// [`ColorVisionDeficiency::simulate_xyz`](enum.ColorVisionDeficiency.html#method.simulate_xyz)
+/// and converted to CIELAB relative to the space's white point. The palette is safe if the
+/// CIEDE2000 difference of every pair is at least `min_delta_e`.
+///
+/// Palettes with fewer than two colors are always safe.
+pub fn is_cvd_safe<T, S>(
+    palette: &[Rgb<u8>],
+    deficiency: ColorVisionDeficiency,
+    min_delta_e: f64,
+    space: &S,
+) -> bool
+where
+    T: FreeChannelScalar + PosNormalChannelScalar + ChannelFormatCast<f64>,
+    f64: ChannelFormatCast<T>,
+    u8: ChannelFormatCast<T>,
+    S: ConvertToXyz<T, Rgb<T>, <S as ColorSpace<T>>::Encoding, OutputColor = Xyz<T>>,
+{
+    let encoding = space.encoding();
+    let white = space.white_point();
+    let simulated: Vec<Lab<T, D65>> = palette
+        .iter()
+        .map(|pixel| {
+            let color = EncodedColor::new(pixel.color_cast::<T>(), encoding.clone());
+            let xyz = deficiency.simulate_xyz(&space.convert_to_xyz(&color), &white);
+            xyz_to_lab_relative_to(&xyz, &white)
+        })
+        .collect();
+
+    let min_delta_e: T = cast(min_delta_e).unwrap();
+    simulated.iter().enumerate().all(|(i, a)| {
+        simulated[i + 1..]
+            .iter()
+            .all(|b| a.ciede2000(b) >= min_delta_e)
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::color_space::named::SRgb;
+    use approx::*;
+
+    #[test]
+    fn test_simulate() {
+        let space = SRgb::new();
+        let deficiencies = [
+            ColorVisionDeficiency::Protanopia,
+            ColorVisionDeficiency::Deuteranopia,
+            ColorVisionDeficiency::Tritanopia,
+        ];
+        for &deficiency in deficiencies.iter() {
+            for &v in [0.0, 0.2, 0.5, 1.0].iter() {
+                let gray = Rgb::new(v, v, v);
+                assert_relative_eq!(deficiency.simulate(&gray, &space), gray, epsilon = 1e-6);
+            }
+        }
+
+        let blue = Rgb::new(0.0, 0.0, 1.0);
+        let red = Rgb::new(1.0, 0.0, 0.0);
+        let green = Rgb::new(0.0, 1.0, 0.0);
+        let protan = ColorVisionDeficiency::Protanopia;
+        let deutan = ColorVisionDeficiency::Deuteranopia;
+        let tritan = ColorVisionDeficiency::Tritanopia;
+        assert_relative_eq!(protan.simulate(&blue, &space), blue, epsilon = 1e-6);
+        assert_relative_eq!(deutan.simulate(&blue, &space), blue, epsilon = 1e-6);
+        assert_relative_eq!(tritan.simulate(&red, &space), red, epsilon = 1e-6);
+
+        // Red and green collapse onto the yellow-blue axis, keeping little of their difference
+        for &deficiency in [protan, deutan].iter() {
+            let r = deficiency.simulate(&red, &space);
+            let g = deficiency.simulate(&green, &space);
+            assert!(r.red() > r.blue() && g.red() > g.blue());
+            assert_relative_eq!(r.red(), r.green(), epsilon = 0.15);
+            assert_relative_eq!(g.red(), g.green(), epsilon = 0.15);
+        }
+    }
+
+    #[test]
+    fn test_is_cvd_safe() {
+        let space = SRgb::<f64>::new();
+        // A rust red and an olive green, which differ almost only along the red-green axis
+        let red_green = [Rgb::new(180u8, 80, 50), Rgb::new(110, 130, 40)];
+        assert!(is_cvd_safe(
+            &red_green,
+            ColorVisionDeficiency::Tritanopia,
+            10.0,
+            &space
+        ));
+        assert!(!is_cvd_safe(
+            &red_green,
+            ColorVisionDeficiency::Deuteranopia,
+            10.0,
+            &space
+        ));
+        assert!(!is_cvd_safe(
+            &red_green,
+            ColorVisionDeficiency::Deuteranopia,
+            2.0,
+            &space
+        ));
+
+        // Blue, orange and near black differ in lightness and along the yellow-blue axis
+        let safe = [
+            Rgb::new(0u8, 114, 178),
+            Rgb::new(230, 159, 0),
+            Rgb::new(30, 30, 30),
+            Rgb::new(240, 240, 240),
+        ];
+        for &deficiency in [
+            ColorVisionDeficiency::Protanopia,
+            ColorVisionDeficiency::Deuteranopia,
+            ColorVisionDeficiency::Tritanopia,
+        ]
+        .iter()
+        {
+            assert!(is_cvd_safe(&safe, deficiency, 10.0, &space));
+        }
+
+        assert!(is_cvd_safe(
+            &safe[..1],
+            ColorVisionDeficiency::Protanopia,
+            50.0,
+            &space
+        ));
+        assert!(is_cvd_safe(
+            &[],
+            ColorVisionDeficiency::Protanopia,
+            50.0,
+            &space
+        ));
+    }
+}
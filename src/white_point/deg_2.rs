@@ -649,3 +649,35 @@ where
 {
     const DEFAULT: Self = F12;
 }
+
+/// DCI theatrical projection white, about 6300K. Not a CIE illuminant; used by DCI-P3.
+#[derive(Clone, Debug, PartialEq, Eq, Default, Copy)]
+#[cfg_attr(feature = "serde", derive(DeserializeName, SerializeName))]
+pub struct DCI;
+impl<T> WhitePoint<T> for DCI
+where
+    T: Float + FreeChannelScalar + PosNormalChannelScalar,
+{
+    #[inline]
+    fn get_xyz(&self) -> Xyz<T> {
+        Xyz::new(
+            cast(0.894587).unwrap(),
+            cast(1.000000).unwrap(),
+            cast(0.954416).unwrap(),
+        )
+    }
+    #[inline]
+    fn get_xy_chromaticity(&self) -> XyY<T> {
+        XyY::new(
+            cast(0.31400).unwrap(),
+            cast(0.35100).unwrap(),
+            cast(1.0).unwrap(),
+        )
+    }
+}
+impl<T> UnitWhitePoint<T> for DCI
+where
+    T: Float + FreeChannelScalar + PosNormalChannelScalar,
+{
+    const DEFAULT: Self = DCI;
+}
@@ -48,3 +48,11 @@ pub mod deg_10;
 pub mod deg_2;
 
 pub use self::deg_2::*;
+
+/// The white point used by `Lab`, `Luv`, `Lchab` and `Lchuv` when none is specified
+///
+/// This is the 2 degree observer D65, the white point of sRGB and most display color spaces. The
+/// default only applies where the type is written out, as in `Lab<f64>`; it is not used to infer
+/// the white point of a bare `Lab::new`. The aliases `LabD65`, `LuvD65`, `LchabD65` and `LchuvD65`
+/// fix the white point so that their constructors infer cleanly.
+pub type DefaultWhitePoint = D65;
@@ -5,17 +5,38 @@ use crate::channel::{
     ChannelCast, ChannelFormatCast, ColorChannel, FreeChannel, FreeChannelScalar,
     PosNormalBoundedChannel, PosNormalChannelScalar,
 };
-use crate::color::{Bounded, Broadcast, Color, Flatten, FromTuple, HomogeneousColor, Lerp};
+use crate::color::{Bounded, Broadcast, Color, Flatten, FlattenMut, FromTuple, HomogeneousColor, Lerp};
 use crate::convert::FromColor;
 use crate::tags::XyYTag;
 use crate::xyz::Xyz;
 #[cfg(feature = "approx")]
 use approx;
 use num_traits;
+use std::error;
 use std::fmt;
 use std::mem;
 use std::slice;
 
+/// An error produced when an `Xyz` value has no valid `XyY` representation
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum XyYError {
+    /// One or more of the `Xyz` channels was negative
+    NegativeChannel,
+    /// One or more of the `Xyz` channels was infinite or NaN
+    NonFiniteChannel,
+}
+
+impl fmt::Display for XyYError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            XyYError::NegativeChannel => write!(f, "XYZ channels must not be negative"),
+            XyYError::NonFiniteChannel => write!(f, "XYZ channels must be finite"),
+        }
+    }
+}
+
+impl error::Error for XyYError {}
+
 /// The xyY device-independent chromaticity space
 ///
 /// xyY is a chromaticity transformation of XYZ, defined by a *relative* amount of `X`, `Y` and `Z`.
@@ -84,6 +105,23 @@ where
         }
     }
 
+    /// Construct an `XyY` instance from channels in `Y, x, y` order
+    ///
+    /// Some tools and data sets list the luminance first and call the space Yxy. This is the same
+    /// as `XyY::new(x, y, Y)`, and panics under the same conditions.
+    pub fn from_yxy(Y: T, x: T, y: T) -> Self {
+        XyY::new(x, y, Y)
+    }
+
+    /// Returns the channels in `(Y, x, y)` order
+    ///
+    /// This is the luminance first Yxy ordering used by some tools, and the inverse of
+    /// [`from_yxy`](#method.from_yxy). Note that `to_tuple` and the other generic accessors keep
+    /// prisma's own `(x, y, Y)` order.
+    pub fn to_yxy(&self) -> (T, T, T) {
+        (self.Y(), self.x(), self.y())
+    }
+
     impl_color_color_cast_square!(XyY {x, y, Y}, chan_traits={FreeChannelScalar,
         PosNormalChannelScalar});
 
@@ -139,6 +177,55 @@ where
         self.x.0 = x;
         self.y.0 = y;
     }
+    /// Set the `x` value, clamping it into `[0, 1]` instead of panicking
+    ///
+    /// `y` is rescaled as with [`set_x`](#method.set_x). A `NaN` value is treated as zero.
+    pub fn set_x_clamped(&mut self, val: T) {
+        self.set_x(Self::clamp_chromaticity(val));
+    }
+    /// Set the `y` value, clamping it into `[0, 1]` instead of panicking
+    ///
+    /// `x` is rescaled as with [`set_y`](#method.set_y). A `NaN` value is treated as zero.
+    pub fn set_y_clamped(&mut self, val: T) {
+        self.set_y(Self::clamp_chromaticity(val));
+    }
+    /// Set the implicit `z` value, clamping it into `[0, 1]` instead of panicking
+    ///
+    /// `x` and `y` are rescaled as with [`set_z`](#method.set_z). A `NaN` value is treated as zero.
+    pub fn set_z_clamped(&mut self, val: T) {
+        self.set_z(Self::clamp_chromaticity(val));
+    }
+
+    /// Construct an `XyY` value from an `Xyz` value, returning an error instead of panicking
+    ///
+    /// Where `XyY::from_color` panics, this returns `Err` if any channel of `from` is negative or not
+    /// finite. An `Xyz` value of all zeros has no defined chromaticity and is mapped to an `XyY`
+    /// of all zeros. `y` is clamped so that `x + y` does not exceed one through rounding.
+    pub fn try_from_xyz(from: &Xyz<T>) -> Result<Self, XyYError> {
+        let zero = num_traits::cast(0.0).unwrap();
+        let (x, y, z) = (from.x(), from.y(), from.z());
+        if !x.is_finite() || !y.is_finite() || !z.is_finite() {
+            return Err(XyYError::NonFiniteChannel);
+        }
+        if x < zero || y < zero || z < zero {
+            return Err(XyYError::NegativeChannel);
+        }
+        let sum = x + y + z;
+
+        if sum != zero {
+            // Rounding can push x + y slightly above one when z is tiny
+            let x_ratio = x / sum;
+            let y_ratio = (y / sum).min(num_traits::cast::<_, T>(1.0).unwrap() - x_ratio);
+            Ok(XyY::new(x_ratio, y_ratio, y))
+        } else {
+            Ok(XyY::new(zero, zero, zero))
+        }
+    }
+
+    fn clamp_chromaticity(val: T) -> T {
+        val.max(PosNormalBoundedChannel::min_bound())
+            .min(PosNormalBoundedChannel::max_bound())
+    }
 
     /// Rescale `c2` and `c3` based on a fixed `primary` to maintain the property `x + y + z = 1`
     ///
@@ -242,6 +329,13 @@ where
         y:PosNormalBoundedChannel - 1, Y:FreeChannel - 2});
 }
 
+impl<T> FlattenMut for XyY<T>
+where
+    T: FreeChannelScalar + PosNormalChannelScalar + num_traits::Float,
+{
+    impl_color_as_slice_mut!(T);
+}
+
 #[cfg(feature = "approx")]
 impl<T> approx::AbsDiffEq for XyY<T>
 where
@@ -291,21 +385,14 @@ impl<T> FromColor<Xyz<T>> for XyY<T>
 where
     T: FreeChannelScalar + PosNormalChannelScalar + num_traits::Float,
 {
+    /// Panics:
+    /// =======
+    /// Panics if any channel of `from` is negative or not finite. Use `XyY::try_from_xyz` to
+    /// handle such values without panicking.
     fn from_color(from: &Xyz<T>) -> Self {
-        let zero = num_traits::cast(0.0).unwrap();
-        if from.x() < zero || from.y() < zero || from.z() < zero {
-            panic!("Cannot convert an XYZ color with negative channels to xyY");
-        }
-        let sum = from.x() + from.y() + from.z();
-
-        if sum != zero {
-            let x = from.x() / sum;
-            let y = from.y() / sum;
-            let Y = from.y();
-
-            XyY::new(x, y, Y)
-        } else {
-            XyY::new(zero, zero, zero)
+        match XyY::try_from_xyz(from) {
+            Ok(xyy) => xyy,
+            Err(err) => panic!("Cannot convert the XYZ color to xyY: {}", err),
         }
     }
 }
@@ -412,6 +499,28 @@ mod test {
         assert_relative_eq!(c2.Y(), 1.0);
     }
 
+    #[test]
+    fn test_set_channels_clamped() {
+        let mut c1 = XyY::new(0.4, 0.3, 0.5);
+        c1.set_x_clamped(1.5);
+        assert_relative_eq!(c1.x(), 1.0);
+        assert_relative_eq!(c1.y(), 0.0);
+        assert_relative_eq!(c1.z(), 0.0);
+        assert_relative_eq!(c1.Y(), 0.5);
+
+        let mut c2 = XyY::new(0.2, 0.3, 0.9);
+        c2.set_y_clamped(-0.3);
+        assert_relative_eq!(c2, XyY::new(2.0 / 7.0, 0.0, 0.9), epsilon = 1e-6);
+        c2.set_z_clamped(7.0);
+        assert_relative_eq!(c2, XyY::new(0.0, 0.0, 0.9), epsilon = 1e-6);
+        c2.set_x_clamped(f64::NAN);
+        assert_relative_eq!(c2.x(), 0.0);
+
+        let mut c3 = XyY::new(0.4, 0.3, 0.4);
+        c3.set_x_clamped(0.6);
+        assert_relative_eq!(c3, XyY::new(0.6, 0.2, 0.4), epsilon = 1e-6);
+    }
+
     #[test]
     #[should_panic]
     fn test_neg_set_panic() {
@@ -432,6 +541,24 @@ mod test {
         let _ = XyY::from_slice(&[1.2, 0.3, 0.9]);
     }
 
+    #[test]
+    fn test_yxy() {
+        let c1 = XyY::new(0.3127, 0.329, 0.75);
+        assert_eq!(c1.to_yxy(), (0.75, 0.3127, 0.329));
+        assert_eq!(c1.clone().to_tuple(), (0.3127, 0.329, 0.75));
+        assert_eq!(XyY::from_yxy(0.75, 0.3127, 0.329), c1);
+
+        let c2 = XyY::new(0.64f32, 0.33, 0.2126);
+        let (Y, x, y) = c2.to_yxy();
+        assert_eq!(XyY::from_yxy(Y, x, y), c2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_yxy_oob_panic() {
+        let _ = XyY::from_yxy(0.5, 0.8, 0.4);
+    }
+
     #[test]
     fn test_flatten() {
         let c1 = XyY::new(0.5, 0.3, 0.8);
@@ -487,6 +614,54 @@ mod test {
         assert_relative_eq!(Xyz::from_color(&t5), c5, epsilon = 1e-6);
     }
 
+    #[test]
+    fn test_try_from_xyz() {
+        let c1 = Xyz::new(0.3, 0.2, 0.5);
+        assert_relative_eq!(
+            XyY::try_from_xyz(&c1).unwrap(),
+            XyY::new(0.3, 0.2, 0.2),
+            epsilon = 1e-6
+        );
+        assert_eq!(
+            XyY::try_from_xyz(&Xyz::new(0.0, 0.0, 0.0)),
+            Ok(XyY::new(0.0, 0.0, 0.0))
+        );
+        assert_eq!(
+            XyY::try_from_xyz(&Xyz::new(-0.1, 0.2, 0.5)),
+            Err(XyYError::NegativeChannel)
+        );
+        assert_eq!(
+            XyY::try_from_xyz(&Xyz::new(0.1, 0.2, -0.1)),
+            Err(XyYError::NegativeChannel)
+        );
+        assert_eq!(
+            XyY::try_from_xyz(&Xyz::new(0.5, -0.5, 0.0)),
+            Err(XyYError::NegativeChannel)
+        );
+        assert_eq!(
+            XyY::try_from_xyz(&Xyz::new(f64::NAN, 0.2, 0.1)),
+            Err(XyYError::NonFiniteChannel)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "must not be negative")]
+    fn test_from_xyz_neg_panic() {
+        let _ = XyY::from_color(&Xyz::new(-0.1, 0.2, 0.5));
+    }
+
+    #[test]
+    #[should_panic(expected = "must be finite")]
+    fn test_from_xyz_nan_panic() {
+        let _ = XyY::from_color(&Xyz::new(f64::NAN, 0.2, 0.1));
+    }
+
+    #[test]
+    #[should_panic(expected = "must be finite")]
+    fn test_from_xyz_infinite_panic() {
+        let _ = XyY::from_color(&Xyz::new(0.3, 0.2, f32::INFINITY));
+    }
+
     #[test]
     fn test_to_xyz() {
         let c1 = XyY::new(0.5, 0.2, 0.5);
@@ -2,8 +2,13 @@
 
 use crate::channel::{
     ChannelCast, ChannelFormatCast, ColorChannel, FreeChannelScalar, PosFreeChannel,
+    PosNormalChannelScalar,
 };
-use crate::color::{Bounded, Broadcast, Color, Flatten, FromTuple, HomogeneousColor, Lerp};
+use crate::color::{Bounded, Broadcast, Color, Flatten, FlattenMut, FromTuple, HomogeneousColor, Lerp};
+use crate::color_space::named::SRgb;
+use crate::color_space::{ConvertFromXyz, ConvertToXyz};
+use crate::encoding::{EncodedColor, SrgbEncoding};
+use crate::rgb::Rgb;
 use crate::tags::XyzTag;
 #[cfg(feature = "approx")]
 use approx;
@@ -52,6 +57,16 @@ use std::slice;
 /// While $`10^{\circ}`$ standard observer is recommended for use in many applications using more
 /// than about $`4^{\circ}`$ of
 /// vision, the $`2^{\circ}`$ standard observer is still much more widely used in practice.
+///
+/// ## Scale
+///
+/// Prisma uses relative XYZ values where the reference white has a luminance of $`Y = 1`$, as
+/// returned by [`WhitePoint::get_xyz`](white_point/trait.WhitePoint.html#tymethod.get_xyz) and
+/// produced by the Rgb color spaces. Many references and other tools instead scale the white to
+/// $`Y = 100`$. Use [`from_percent`](#method.from_percent) and [`to_percent`](#method.to_percent)
+/// to move between the two, or [`scale_to_reference`](#method.scale_to_reference) for any other
+/// scale, such as absolute luminance in $`cd/m^2`$. Mixing scales gives wrong results in `Lab`,
+/// `Luv` and the other spaces defined relative to a white point.
 #[repr(C)]
 #[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -112,6 +127,47 @@ where
     pub fn set_z(&mut self, val: T) {
         self.z.0 = val;
     }
+
+    /// Rescale a color measured on a scale where the reference white has $`Y`$ = `reference`
+    ///
+    /// Every component is divided by `reference`, giving a color on prisma's scale where the
+    /// reference white has $`Y = 1`$. For example, pass the luminance of the display white in
+    /// $`cd/m^2`$ to convert absolute measurements.
+    pub fn scale_to_reference(self, reference: T) -> Self {
+        Xyz::new(
+            self.x() / reference,
+            self.y() / reference,
+            self.z() / reference,
+        )
+    }
+    /// Construct an `Xyz` instance from components on the scale where white has $`Y = 100`$
+    pub fn from_percent(x: T, y: T, z: T) -> Self {
+        Xyz::new(x, y, z).scale_to_reference(num_traits::cast(100.0).unwrap())
+    }
+    /// Returns the components on the scale where white has $`Y = 100`$
+    pub fn to_percent(&self) -> (T, T, T) {
+        let hundred: T = num_traits::cast(100.0).unwrap();
+        (self.x() * hundred, self.y() * hundred, self.z() * hundred)
+    }
+}
+
+impl<T> Xyz<T>
+where
+    T: num_traits::Float + FreeChannelScalar + PosNormalChannelScalar + ChannelFormatCast<f64>,
+    f64: ChannelFormatCast<T>,
+{
+    /// Convert to an sRgb color, encoded with the sRgb transfer function
+    ///
+    /// This is a shortcut for `SRgb::new().convert_from_xyz(&xyz).strip_space()`.
+    pub fn to_srgb(&self) -> EncodedColor<Rgb<T>, SrgbEncoding> {
+        SRgb::new().convert_from_xyz(self).strip_space()
+    }
+    /// Convert an encoded sRgb color into XYZ
+    ///
+    /// This is the inverse of [`to_srgb`](#method.to_srgb).
+    pub fn from_srgb(color: &EncodedColor<Rgb<T>, SrgbEncoding>) -> Self {
+        SRgb::new().convert_to_xyz(color)
+    }
 }
 
 impl<T> Color for Xyz<T>
@@ -179,6 +235,13 @@ where
     impl_color_from_slice_square!(Xyz<T> {x:PosFreeChannel - 0, y:PosFreeChannel - 1,
         z:PosFreeChannel - 2});
 }
+
+impl<T> FlattenMut for Xyz<T>
+where
+    T: FreeChannelScalar,
+{
+    impl_color_as_slice_mut!(T);
+}
 #[cfg(feature = "approx")]
 impl<T> approx::AbsDiffEq for Xyz<T>
 where
@@ -229,6 +292,63 @@ mod test {
     use super::*;
     use approx::*;
 
+    #[test]
+    fn test_scale() {
+        use crate::lab::Lab;
+        use crate::white_point::{WhitePoint, D65};
+
+        let white = Xyz::from_percent(95.047, 100.0, 108.883);
+        assert_relative_eq!(white, D65.get_xyz(), epsilon = 1e-4);
+        assert_relative_eq!(Lab::from_xyz(&white, D65).L(), 100.0, epsilon = 1e-3);
+
+        // The sRgb red primary, as commonly tabulated with white at Y = 100
+        let red = Xyz::from_percent(41.246, 21.267, 1.933);
+        let lab = Lab::from_xyz(&red, D65);
+        assert_relative_eq!(lab.L(), 53.24, epsilon = 1e-2);
+        assert_relative_eq!(lab.a(), 80.09, epsilon = 1e-1);
+        assert_relative_eq!(lab.b(), 67.20, epsilon = 1e-1);
+        let (x, y, z) = red.to_percent();
+        assert_relative_eq!(x, 41.246, epsilon = 1e-10);
+        assert_relative_eq!(y, 21.267, epsilon = 1e-10);
+        assert_relative_eq!(z, 1.933, epsilon = 1e-10);
+
+        let absolute = Xyz::new(76.0, 80.0, 87.1);
+        assert_relative_eq!(
+            absolute.scale_to_reference(80.0),
+            Xyz::new(0.95, 1.0, 1.08875),
+            epsilon = 1e-12
+        );
+    }
+
+    #[test]
+    fn test_srgb() {
+        use crate::color_space::named::SRgb;
+        use crate::color_space::ConvertFromXyz;
+        use crate::encoding::EncodableColor;
+
+        let colors = [
+            Xyz::new(0.5, 0.4, 0.3),
+            Xyz::new(0.95047, 1.0, 1.08883),
+            Xyz::new(0.1, 0.2, 0.7),
+            Xyz::new(0.0, 0.0, 0.0),
+        ];
+        for xyz in colors.iter() {
+            let rgb = xyz.to_srgb();
+            let expected = SRgb::new().convert_from_xyz(xyz).strip_space();
+            assert_relative_eq!(rgb, expected, epsilon = 1e-12);
+            assert_relative_eq!(Xyz::from_srgb(&rgb), *xyz, epsilon = 1e-9);
+        }
+
+        let white = Xyz::new(0.95047, 1.0, 1.08883).to_srgb();
+        assert_relative_eq!(white.color(), &Rgb::new(1.0, 1.0, 1.0), epsilon = 1e-4);
+        let red = Rgb::new(1.0, 0.0, 0.0).srgb_encoded();
+        assert_relative_eq!(
+            Xyz::from_srgb(&red),
+            Xyz::new(0.4124564, 0.2126729, 0.0193339),
+            epsilon = 1e-6
+        );
+    }
+
     #[test]
     fn test_construction() {
         let c1 = Xyz::new(0.5, 1.2, 0.9);
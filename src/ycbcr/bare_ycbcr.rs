@@ -4,7 +4,7 @@ use crate::channel::{
     ChannelCast, ChannelFormatCast, ColorChannel, NormalBoundedChannel, NormalChannelScalar,
     PosNormalBoundedChannel, PosNormalChannelScalar,
 };
-use crate::color::{Bounded, Broadcast, Color, Flatten, FromTuple, HomogeneousColor, Invert, Lerp};
+use crate::color::{Bounded, Broadcast, Color, Flatten, FlattenMut, FromTuple, HomogeneousColor, Invert, Lerp};
 use crate::encoding::EncodableColor;
 #[cfg(feature = "approx")]
 use approx;
@@ -35,6 +35,25 @@ pub enum YCbCrOutOfGamutMode {
     Clip,
 }
 
+/// The range of integer code values used by an 8-bit `YCbCr` signal.
+///
+/// This selects the offsets and scales used by the exact integer conversions
+/// [`YCbCr::from_rgb_u8_exact`](struct.YCbCr.html#method.from_rgb_u8_exact) and
+/// [`YCbCr::to_rgb_u8_exact`](struct.YCbCr.html#method.to_rgb_u8_exact).
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum YCbCrRange {
+    /// Use every code value: luma runs from 0 to 255, and chroma from 0 to 255 centered on 128.
+    ///
+    /// This is the range used by JPEG (JFIF) and matches the floating point conversions.
+    Full,
+    /// The "studio" range of ITU-R BT.601 and BT.709: luma runs from 16 to 235, and chroma
+    /// from 16 to 240 centered on 128.
+    ///
+    /// The codes outside these ranges are footroom and headroom, and are only reached by
+    /// out of gamut values.
+    Limited,
+}
+
 /// A YCbCr color that does not know its model.
 ///
 /// `BareYCbCr` is used internally to implement `YCbCr` and is provided as
@@ -61,14 +80,22 @@ where
     T: NormalChannelScalar + PosNormalChannelScalar,
 {
     /// Construct a `BareYCbCr` from channel values.
-    pub fn new(luma: T, cb: T, cr: T) -> Self {
+    pub const fn new(luma: T, cb: T, cr: T) -> Self {
         BareYCbCr {
-            luma: PosNormalBoundedChannel::new(luma),
-            cb: NormalBoundedChannel::new(cb),
-            cr: NormalBoundedChannel::new(cr),
+            luma: PosNormalBoundedChannel::new_const(luma),
+            cb: NormalBoundedChannel::new_const(cb),
+            cr: NormalBoundedChannel::new_const(cr),
         }
     }
 
+    impl_color_checked_new!(
+        fn new_checked(luma: T, cb: T, cr: T) {
+            crate::channel::debug_check_bounded::<PosNormalBoundedChannel<T>>(&luma);
+            crate::channel::debug_check_bounded::<NormalBoundedChannel<T>>(&cb);
+            crate::channel::debug_check_bounded::<NormalBoundedChannel<T>>(&cr);
+        }
+    );
+
     impl_color_color_cast_square!(BareYCbCr {luma, cb, cr},
         chan_traits={PosNormalChannelScalar, NormalChannelScalar});
 
@@ -98,14 +125,17 @@ where
     }
     /// Set the luma (Y') channel to a value.
     pub fn set_luma(&mut self, val: T) {
+        crate::channel::debug_check_bounded::<PosNormalBoundedChannel<T>>(&val);
         self.luma.0 = val;
     }
     /// Set the Cb channel to a value.
     pub fn set_cb(&mut self, val: T) {
+        crate::channel::debug_check_bounded::<NormalBoundedChannel<T>>(&val);
         self.cb.0 = val;
     }
     /// Set the Cr channel to a value.
     pub fn set_cr(&mut self, val: T) {
+        crate::channel::debug_check_bounded::<NormalBoundedChannel<T>>(&val);
         self.cr.0 = val;
     }
 
@@ -205,6 +235,13 @@ where
         cb:NormalBoundedChannel - 1, cr:NormalBoundedChannel - 2});
 }
 
+impl<T> FlattenMut for BareYCbCr<T>
+where
+    T: PosNormalChannelScalar + NormalChannelScalar,
+{
+    impl_color_as_slice_mut!(T);
+}
+
 impl<T> EncodableColor for BareYCbCr<T> where T: PosNormalChannelScalar + NormalChannelScalar {}
 
 #[cfg(feature = "approx")]
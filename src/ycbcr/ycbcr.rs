@@ -1,17 +1,23 @@
 //! Implements the core `YCbCr` struct and some convenience types.
 
-use crate::channel::{ChannelFormatCast, NormalChannelScalar, PosNormalChannelScalar};
-use crate::color::{Bounded, Broadcast, Color, Flatten, FromTuple, HomogeneousColor, Invert, Lerp};
+use crate::channel::{
+    ChannelFormatCast, FreeChannelScalar, NormalChannelScalar, PosNormalChannelScalar,
+};
+use crate::color::{
+    Bounded, Broadcast, Color, Flatten, FlattenMut, FromTuple, HomogeneousColor, Invert, Lerp, Lightness,
+};
+use crate::color_space::ColorSpace;
 use crate::convert::{FromColor, FromYCbCr};
 use crate::encoding::EncodableColor;
 use crate::rgb::Rgb;
 use crate::tags::YCbCrTag;
+use crate::xyz::Xyz;
 #[cfg(feature = "approx")]
 use approx;
 use num_traits;
 use std::fmt;
 
-use crate::ycbcr::bare_ycbcr::{BareYCbCr, YCbCrOutOfGamutMode};
+use crate::ycbcr::bare_ycbcr::{BareYCbCr, YCbCrOutOfGamutMode, YCbCrRange};
 use crate::ycbcr::model::{
     Bt709Model, Canonicalize, CustomYCbCrModel, JpegModel, UnitModel, YCbCrModel, YiqModel,
 };
@@ -217,6 +223,22 @@ where
     }
 }
 
+impl<T, M> Lightness for YCbCr<T, M>
+where
+    T: NormalChannelScalar + PosNormalChannelScalar,
+    M: YCbCrModel<T>,
+{
+    type LightnessFormat = T;
+
+    fn lightness(&self) -> T {
+        self.luma()
+    }
+    fn with_lightness(mut self, lightness: T) -> Self {
+        self.set_luma(lightness);
+        self
+    }
+}
+
 impl<T, M> FromTuple for YCbCr<T, M>
 where
     T: NormalChannelScalar + PosNormalChannelScalar,
@@ -304,6 +326,16 @@ where
     }
 }
 
+impl<T, M> FlattenMut for YCbCr<T, M>
+where
+    T: NormalChannelScalar + PosNormalChannelScalar,
+    M: YCbCrModel<T> + UnitModel<T>,
+{
+    fn as_slice_mut(&mut self) -> &mut [T] {
+        self.ycbcr.as_slice_mut()
+    }
+}
+
 impl<T, M> EncodableColor for YCbCr<T, M>
 where
     T: NormalChannelScalar + PosNormalChannelScalar,
@@ -419,6 +451,126 @@ where
     }
 }
 
+impl<T, M> YCbCr<T, M>
+where
+    T: NormalChannelScalar
+        + PosNormalChannelScalar
+        + FreeChannelScalar
+        + num_traits::Float
+        + ChannelFormatCast<f64>,
+    f64: ChannelFormatCast<T>,
+    M: YCbCrModel<T>,
+{
+    /// Convert from YCbCr to XYZ, through the RGB color space `space`.
+    ///
+    /// YCbCr is a transformation of a parent RGB space. The color is first converted to RGB as
+    /// with [`to_rgb`](#method.to_rgb), then taken to be encoded in `space`'s own encoding and
+    /// converted to XYZ.
+    ///
+    /// # Params
+    ///
+    /// * space - The parent RGB space of the color.
+    /// * out_of_gamut_mode - How to handle colors that are out of gamut in `Rgb`.
+    pub fn to_xyz<S>(&self, space: &S, out_of_gamut_mode: YCbCrOutOfGamutMode) -> Xyz<T>
+    where
+        S: ColorSpace<T>,
+    {
+        let rgb = self.to_rgb(out_of_gamut_mode);
+        space.convert_rgb_to_xyz(&rgb, &space.encoding())
+    }
+}
+
+impl<M> YCbCr<u8, M>
+where
+    M: YCbCrModel<u8>,
+{
+    /// Convert an 8-bit RGB color to 8-bit YCbCr using integer arithmetic only.
+    ///
+    /// Unlike [`from_rgb_and_model`](#method.from_rgb_and_model), which converts through
+    /// floating point values and truncates, this follows the rounding of the ITU-R
+    /// recommendations and of libjpeg, so the result is bit exact and can be checked against
+    /// conformance data.
+    ///
+    /// Each row $`(m_{i0}, m_{i1}, m_{i2})`$ of the model's forward transform is scaled by the
+    /// range's scale $`s_i`$ and stored as a fixed point integer with 16 fractional bits,
+    /// $`c_{ij} = \operatorname{round}(2^{16} s_i m_{ij})`$. Each output channel is then
+    ///
+    /// ```math
+    /// \operatorname{clamp}\left(\left\lfloor \frac{c_{i0} R + c_{i1} G + c_{i2} B + 2^{15}}{2^{16}}
+    /// \right\rfloor + o_i, 0, 255\right)
+    /// ```
+    ///
+    /// For [`YCbCrRange::Full`](enum.YCbCrRange.html) every $`s_i`$ is 1 and the offsets
+    /// $`o_i`$ are (0, 128, 128). For [`YCbCrRange::Limited`](enum.YCbCrRange.html) the scales
+    /// are (219/255, 224/255, 224/255) and the offsets are (16, 128, 128).
+    ///
+    /// The returned value is in the code values of `range`, and should be converted back with
+    /// [`to_rgb_u8_exact`](#method.to_rgb_u8_exact) using the same range.
+    pub fn from_rgb_u8_exact(from: &Rgb<u8>, model: M, range: YCbCrRange) -> Self {
+        let (scale, offset) = range_scale_and_offset(range);
+        let transform = model.forward_transform();
+        let m = transform.as_slice();
+        let rgb = [
+            i32::from(from.red()),
+            i32::from(from.green()),
+            i32::from(from.blue()),
+        ];
+        let channel = |i: usize| -> u8 {
+            let sum: i32 = (0..3)
+                .map(|j| fixed_point(m[i * 3 + j] * scale[i]) * rgb[j])
+                .sum();
+            (((sum + (1 << 15)) >> 16) + offset[i]).clamp(0, 255) as u8
+        };
+        YCbCr::new_and_model(channel(0), channel(1), channel(2), model)
+    }
+
+    /// Convert an 8-bit YCbCr color in the code values of `range` to 8-bit RGB using integer
+    /// arithmetic only.
+    ///
+    /// This is the inverse of [`from_rgb_u8_exact`](#method.from_rgb_u8_exact). The offsets
+    /// $`o_j`$ are removed from each channel, and the columns of the model's inverse transform
+    /// are scaled by the reciprocals of the range's scales before being made fixed point:
+    ///
+    /// ```math
+    /// \operatorname{clamp}\left(\left\lfloor \frac{\sum_j d_{ij} (v_j - o_j) + 2^{15}}{2^{16}}
+    /// \right\rfloor, 0, 255\right), \quad d_{ij} = \operatorname{round}(2^{16} m^{-1}_{ij} / s_j)
+    /// ```
+    ///
+    /// Out of gamut results are clipped. As limited range has fewer code values than RGB, a
+    /// round trip through it may be off by one or two.
+    pub fn to_rgb_u8_exact(&self, range: YCbCrRange) -> Rgb<u8> {
+        let (scale, offset) = range_scale_and_offset(range);
+        let transform = self.model.inverse_transform();
+        let m = transform.as_slice();
+        let ycbcr = [
+            i32::from(self.luma()) - offset[0],
+            i32::from(self.cb()) - offset[1],
+            i32::from(self.cr()) - offset[2],
+        ];
+        let channel = |i: usize| -> u8 {
+            let sum: i32 = (0..3)
+                .map(|j| fixed_point(m[i * 3 + j] / scale[j]) * ycbcr[j])
+                .sum();
+            ((sum + (1 << 15)) >> 16).clamp(0, 255) as u8
+        };
+        Rgb::new(channel(0), channel(1), channel(2))
+    }
+}
+
+fn range_scale_and_offset(range: YCbCrRange) -> ([f64; 3], [i32; 3]) {
+    match range {
+        YCbCrRange::Full => ([1.0, 1.0, 1.0], [0, 128, 128]),
+        YCbCrRange::Limited => (
+            [219.0 / 255.0, 224.0 / 255.0, 224.0 / 255.0],
+            [16, 128, 128],
+        ),
+    }
+}
+
+fn fixed_point(coefficient: f64) -> i32 {
+    (coefficient * f64::from(1 << 16)).round() as i32
+}
+
 impl<T, M> FromColor<Rgb<T>> for YCbCr<T, M>
 where
     T: NormalChannelScalar + PosNormalChannelScalar + num_traits::NumCast,
@@ -638,6 +790,118 @@ mod test {
     }
     */
 
+    #[test]
+    fn test_rgb_u8_exact() {
+        // 100% color bars: white, yellow, cyan, green, magenta, red, blue and black
+        let bars = [
+            (255u8, 255u8, 255u8),
+            (255, 255, 0),
+            (0, 255, 255),
+            (0, 255, 0),
+            (255, 0, 255),
+            (255, 0, 0),
+            (0, 0, 255),
+            (0, 0, 0),
+        ];
+        let bt601_limited = [
+            (235u8, 128u8, 128u8),
+            (210, 16, 146),
+            (170, 166, 16),
+            (145, 54, 34),
+            (106, 202, 222),
+            (81, 90, 240),
+            (41, 240, 110),
+            (16, 128, 128),
+        ];
+        let bt709_limited = [
+            (235u8, 128u8, 128u8),
+            (219, 16, 138),
+            (188, 154, 16),
+            (173, 42, 26),
+            (78, 214, 230),
+            (63, 102, 240),
+            (32, 240, 118),
+            (16, 128, 128),
+        ];
+        let jpeg_full = [
+            (255u8, 128u8, 128u8),
+            (226, 1, 149),
+            (179, 171, 1),
+            (150, 44, 21),
+            (105, 212, 235),
+            (76, 85, 255),
+            (29, 255, 107),
+            (0, 128, 128),
+        ];
+        for (i, &(r, g, b)) in bars.iter().enumerate() {
+            let rgb = Rgb::new(r, g, b);
+            let c1 = YCbCr::from_rgb_u8_exact(&rgb, JpegModel, YCbCrRange::Limited);
+            assert_eq!(c1.to_tuple(), bt601_limited[i]);
+            let c2 = YCbCr::from_rgb_u8_exact(&rgb, Bt709Model, YCbCrRange::Limited);
+            assert_eq!(c2.to_tuple(), bt709_limited[i]);
+            let c3 = YCbCr::from_rgb_u8_exact(&rgb, JpegModel, YCbCrRange::Full);
+            assert_eq!(c3.to_tuple(), jpeg_full[i]);
+
+            // Limited range has fewer codes than RGB, so round trips can be off slightly
+            for back in [
+                c1.to_rgb_u8_exact(YCbCrRange::Limited),
+                c2.to_rgb_u8_exact(YCbCrRange::Limited),
+                c3.to_rgb_u8_exact(YCbCrRange::Full),
+            ]
+            .iter()
+            {
+                assert!((i32::from(back.red()) - i32::from(r)).abs() <= 2);
+                assert!((i32::from(back.green()) - i32::from(g)).abs() <= 2);
+                assert!((i32::from(back.blue()) - i32::from(b)).abs() <= 2);
+            }
+        }
+
+        let gray = YCbCr::from_rgb_u8_exact(&Rgb::new(128, 128, 128), JpegModel, YCbCrRange::Full);
+        assert_eq!(gray.to_tuple(), (128, 128, 128));
+        assert_eq!(
+            gray.to_rgb_u8_exact(YCbCrRange::Full),
+            Rgb::new(128, 128, 128)
+        );
+        let white = YCbCr::new_and_model(235u8, 128, 128, Bt709Model);
+        assert_eq!(
+            white.to_rgb_u8_exact(YCbCrRange::Limited),
+            Rgb::new(255, 255, 255)
+        );
+        let black = YCbCr::new_and_model(16u8, 128, 128, Bt709Model);
+        assert_eq!(
+            black.to_rgb_u8_exact(YCbCrRange::Limited),
+            Rgb::new(0, 0, 0)
+        );
+    }
+
+    #[test]
+    fn test_to_xyz() {
+        use crate::color_space::named::SRgb;
+        use crate::white_point::{WhitePoint, D65};
+        let space = SRgb::new();
+
+        let gray = YCbCrJpeg::new(0.5, 0.0, 0.0);
+        let xyz = gray.to_xyz(&space, YCbCrOutOfGamutMode::Clip);
+        // sRgb 0.5 decodes to a linear value of about 0.214, giving a scaled D65 white
+        let white: Xyz<f64> = D65.get_xyz();
+        assert_relative_eq!(xyz.y(), 0.21404114, epsilon = 1e-6);
+        assert_relative_eq!(xyz.x() / xyz.y(), white.x(), epsilon = 1e-3);
+        assert_relative_eq!(xyz.z() / xyz.y(), white.z(), epsilon = 1e-3);
+
+        let white_xyz = YCbCrJpeg::new(1.0, 0.0, 0.0).to_xyz(&space, YCbCrOutOfGamutMode::Clip);
+        assert_relative_eq!(white_xyz.y(), 1.0, epsilon = 1e-6);
+        let black_xyz = YCbCrJpeg::new(0.0, 0.0, 0.0).to_xyz(&space, YCbCrOutOfGamutMode::Clip);
+        assert_relative_eq!(black_xyz, Xyz::new(0.0, 0.0, 0.0), epsilon = 1e-9);
+
+        let rgb = Rgb::new(0.8, 0.3, 0.1);
+        let ycbcr = YCbCrJpeg::from_rgb(&rgb);
+        assert_relative_eq!(
+            ycbcr.to_xyz(&space, YCbCrOutOfGamutMode::Preserve),
+            space.convert_rgb_to_xyz(&rgb, &space.encoding()),
+            epsilon = 1e-5
+        );
+    }
+
     #[test]
     fn test_color_cast() {
         let c1 = YCbCrJpeg::new(0.65f32, -0.3, 0.5);
@@ -656,4 +920,14 @@ mod test {
             YCbCrJpeg::new(0.39215686f32, 0.56862745f32, -0.21568627f32)
         );
     }
+
+    #[test]
+    fn test_lightness() {
+        use crate::color::Lightness;
+
+        let c1 = YCbCrJpeg::new(0.5, 0.25, -0.125);
+        assert_eq!(c1.lightness(), 0.5);
+        let c2 = c1.with_lightness(0.75);
+        assert_eq!(c2, YCbCrJpeg::new(0.75, 0.25, -0.125));
+    }
 }
@@ -0,0 +1,462 @@
+//! The YCoCg luma and chroma color model and its reversible integer variant YCoCg-R
+
+use crate::channel::{
+    ChannelCast, ChannelFormatCast, ColorChannel, NormalBoundedChannel, NormalChannelScalar,
+    PosNormalBoundedChannel, PosNormalChannelScalar,
+};
+use crate::color::{Bounded, Broadcast, Color, Flatten, FlattenMut, FromTuple, HomogeneousColor, Invert, Lerp};
+use crate::convert::FromColor;
+use crate::encoding::EncodableColor;
+use crate::rgb::Rgb;
+use crate::tags::YCoCgTag;
+use num_traits::Float;
+use std::fmt;
+use std::mem;
+use std::slice;
+
+/// The YCoCg luma, chroma orange and chroma green color model
+///
+/// YCoCg splits an Rgb color into a luma channel and two chroma channels using only
+/// multiplications by powers of two, which makes it cheap to compute and exactly invertible.
+/// It is used in image and video compression as a simpler alternative to
+/// [`YCbCr`](ycbcr/struct.YCbCr.html):
+///
+/// ```math
+/// \begin{aligned}
+/// Y &= \frac{R}{4} + \frac{G}{2} + \frac{B}{4} \\
+/// C_o &= \frac{R - B}{2} \\
+/// C_g &= -\frac{R}{4} + \frac{G}{2} - \frac{B}{4}
+/// \end{aligned}
+/// ```
+///
+/// For an Rgb color in the normal range, `luma` is in $`[0, 1]`$ while `co` and `cg` are in
+/// $`[-\frac{1}{2}, \frac{1}{2}]`$. For lossless conversion of 8-bit colors, see
+/// [`YCoCgR`](struct.YCoCgR.html).
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct YCoCg<T> {
+    luma: PosNormalBoundedChannel<T>,
+    co: NormalBoundedChannel<T>,
+    cg: NormalBoundedChannel<T>,
+}
+
+impl<T> YCoCg<T>
+where
+    T: PosNormalChannelScalar + NormalChannelScalar,
+{
+    /// Construct a `YCoCg` instance from the luma, chroma orange and chroma green channels
+    pub const fn new(luma: T, co: T, cg: T) -> Self {
+        YCoCg {
+            luma: PosNormalBoundedChannel::new_const(luma),
+            co: NormalBoundedChannel::new_const(co),
+            cg: NormalBoundedChannel::new_const(cg),
+        }
+    }
+
+    impl_color_checked_new!(
+        fn new_checked(luma: T, co: T, cg: T) {
+            crate::channel::debug_check_bounded::<PosNormalBoundedChannel<T>>(&luma);
+            crate::channel::debug_check_bounded::<NormalBoundedChannel<T>>(&co);
+            crate::channel::debug_check_bounded::<NormalBoundedChannel<T>>(&cg);
+        }
+    );
+
+    impl_color_color_cast_square!(YCoCg {luma, co, cg},
+        chan_traits={PosNormalChannelScalar, NormalChannelScalar});
+
+    /// Get the luma (Y) channel
+    pub fn luma(&self) -> T {
+        self.luma.0.clone()
+    }
+    /// Get the chroma orange (Co) channel
+    pub fn co(&self) -> T {
+        self.co.0.clone()
+    }
+    /// Get the chroma green (Cg) channel
+    pub fn cg(&self) -> T {
+        self.cg.0.clone()
+    }
+    /// Get a mutable reference to the luma (Y) channel
+    pub fn luma_mut(&mut self) -> &mut T {
+        &mut self.luma.0
+    }
+    /// Get a mutable reference to the chroma orange (Co) channel
+    pub fn co_mut(&mut self) -> &mut T {
+        &mut self.co.0
+    }
+    /// Get a mutable reference to the chroma green (Cg) channel
+    pub fn cg_mut(&mut self) -> &mut T {
+        &mut self.cg.0
+    }
+    /// Set the luma (Y) channel to a value
+    pub fn set_luma(&mut self, val: T) {
+        crate::channel::debug_check_bounded::<PosNormalBoundedChannel<T>>(&val);
+        self.luma.0 = val;
+    }
+    /// Set the chroma orange (Co) channel to a value
+    pub fn set_co(&mut self, val: T) {
+        crate::channel::debug_check_bounded::<NormalBoundedChannel<T>>(&val);
+        self.co.0 = val;
+    }
+    /// Set the chroma green (Cg) channel to a value
+    pub fn set_cg(&mut self, val: T) {
+        crate::channel::debug_check_bounded::<NormalBoundedChannel<T>>(&val);
+        self.cg.0 = val;
+    }
+}
+
+impl<T> Color for YCoCg<T>
+where
+    T: PosNormalChannelScalar + NormalChannelScalar,
+{
+    type Tag = YCoCgTag;
+    type ChannelsTuple = (T, T, T);
+
+    #[inline]
+    fn num_channels() -> u32 {
+        3
+    }
+
+    fn to_tuple(self) -> Self::ChannelsTuple {
+        (self.luma.0, self.co.0, self.cg.0)
+    }
+}
+
+impl<T> FromTuple for YCoCg<T>
+where
+    T: PosNormalChannelScalar + NormalChannelScalar,
+{
+    fn from_tuple(values: Self::ChannelsTuple) -> Self {
+        YCoCg::new(values.0, values.1, values.2)
+    }
+}
+
+impl<T> Invert for YCoCg<T>
+where
+    T: PosNormalChannelScalar + NormalChannelScalar,
+{
+    impl_color_invert!(YCoCg { luma, co, cg });
+}
+
+impl<T> Bounded for YCoCg<T>
+where
+    T: PosNormalChannelScalar + NormalChannelScalar,
+{
+    impl_color_bounded!(YCoCg { luma, co, cg });
+}
+
+impl<T> Lerp for YCoCg<T>
+where
+    T: PosNormalChannelScalar + NormalChannelScalar + Lerp,
+{
+    type Position = <T as Lerp>::Position;
+    impl_color_lerp_square!(YCoCg { luma, co, cg });
+}
+
+impl<T> HomogeneousColor for YCoCg<T>
+where
+    T: PosNormalChannelScalar + NormalChannelScalar,
+{
+    type ChannelFormat = T;
+
+    impl_color_homogeneous_color_square!(YCoCg<T> {luma, co, cg});
+}
+
+impl<T> Broadcast for YCoCg<T>
+where
+    T: PosNormalChannelScalar + NormalChannelScalar,
+{
+    fn broadcast(value: T) -> Self {
+        YCoCg {
+            luma: PosNormalBoundedChannel(value.clone()),
+            co: NormalBoundedChannel(value.clone()),
+            cg: NormalBoundedChannel(value),
+        }
+    }
+}
+
+impl<T> Flatten for YCoCg<T>
+where
+    T: PosNormalChannelScalar + NormalChannelScalar,
+{
+    impl_color_as_slice!(T);
+    impl_color_from_slice_square!(YCoCg<T> {luma:PosNormalBoundedChannel - 0,
+        co:NormalBoundedChannel - 1, cg:NormalBoundedChannel - 2});
+}
+
+impl<T> FlattenMut for YCoCg<T>
+where
+    T: PosNormalChannelScalar + NormalChannelScalar,
+{
+    impl_color_as_slice_mut!(T);
+}
+
+impl<T> EncodableColor for YCoCg<T> where T: PosNormalChannelScalar + NormalChannelScalar {}
+
+#[cfg(feature = "approx")]
+impl<T> approx::AbsDiffEq for YCoCg<T>
+where
+    T: PosNormalChannelScalar + NormalChannelScalar + approx::AbsDiffEq,
+    T::Epsilon: Clone,
+{
+    impl_abs_diff_eq!({luma, co, cg});
+}
+#[cfg(feature = "approx")]
+impl<T> approx::RelativeEq for YCoCg<T>
+where
+    T: PosNormalChannelScalar + NormalChannelScalar + approx::RelativeEq,
+    T::Epsilon: Clone,
+{
+    impl_rel_eq!({luma, co, cg});
+}
+#[cfg(feature = "approx")]
+impl<T> approx::UlpsEq for YCoCg<T>
+where
+    T: PosNormalChannelScalar + NormalChannelScalar + approx::UlpsEq,
+    T::Epsilon: Clone,
+{
+    impl_ulps_eq!({luma, co, cg});
+}
+
+impl<T> Default for YCoCg<T>
+where
+    T: PosNormalChannelScalar + NormalChannelScalar + num_traits::Zero,
+{
+    impl_color_default!(YCoCg {
+        luma: PosNormalBoundedChannel,
+        co: NormalBoundedChannel,
+        cg: NormalBoundedChannel
+    });
+}
+
+impl<T> fmt::Display for YCoCg<T>
+where
+    T: PosNormalChannelScalar + NormalChannelScalar + fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "YCoCg({}, {}, {})", self.luma, self.co, self.cg)
+    }
+}
+
+impl<T> FromColor<Rgb<T>> for YCoCg<T>
+where
+    T: PosNormalChannelScalar + NormalChannelScalar + Float,
+{
+    fn from_color(from: &Rgb<T>) -> Self {
+        let half: T = num_traits::cast(0.5).unwrap();
+        let quarter: T = num_traits::cast(0.25).unwrap();
+        let (r, g, b) = (from.red(), from.green(), from.blue());
+
+        let luma = quarter * r + half * g + quarter * b;
+        let co = half * (r - b);
+        let cg = half * g - quarter * (r + b);
+
+        YCoCg::new(luma, co, cg)
+    }
+}
+
+impl<T> FromColor<YCoCg<T>> for Rgb<T>
+where
+    T: PosNormalChannelScalar + NormalChannelScalar + Float,
+{
+    fn from_color(from: &YCoCg<T>) -> Self {
+        let tmp = from.luma() - from.cg();
+        let green = from.luma() + from.cg();
+        let red = tmp + from.co();
+        let blue = tmp - from.co();
+
+        Rgb::new(red, green, blue)
+    }
+}
+
+/// The reversible integer YCoCg-R color model
+///
+/// YCoCg-R is a lifting-based variant of [`YCoCg`](struct.YCoCg.html) that maps every
+/// `Rgb<u8>` color to a unique set of integers and back without any loss. The chroma channels
+/// are scaled by two relative to `YCoCg` and need one more bit than the input, so they are
+/// stored as `i16`:
+///
+/// ```math
+/// \begin{aligned}
+/// C_o &= R - B \\
+/// t &= B + \lfloor C_o / 2 \rfloor \\
+/// C_g &= G - t \\
+/// Y &= t + \lfloor C_g / 2 \rfloor
+/// \end{aligned}
+/// ```
+///
+/// For colors converted from `Rgb<u8>`, `y` is in $`[0, 255]`$ while `co` and `cg` are in
+/// $`[-255, 255]`$.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct YCoCgR {
+    y: u8,
+    co: i16,
+    cg: i16,
+}
+
+impl YCoCgR {
+    /// Construct a `YCoCgR` instance from the luma, chroma orange and chroma green channels
+    pub fn new(y: u8, co: i16, cg: i16) -> Self {
+        YCoCgR { y, co, cg }
+    }
+
+    /// Get the luma (Y) channel
+    pub fn y(&self) -> u8 {
+        self.y
+    }
+    /// Get the chroma orange (Co) channel
+    pub fn co(&self) -> i16 {
+        self.co
+    }
+    /// Get the chroma green (Cg) channel
+    pub fn cg(&self) -> i16 {
+        self.cg
+    }
+    /// Set the luma (Y) channel to a value
+    pub fn set_y(&mut self, val: u8) {
+        self.y = val;
+    }
+    /// Set the chroma orange (Co) channel to a value
+    pub fn set_co(&mut self, val: i16) {
+        self.co = val;
+    }
+    /// Set the chroma green (Cg) channel to a value
+    pub fn set_cg(&mut self, val: i16) {
+        self.cg = val;
+    }
+}
+
+impl fmt::Display for YCoCgR {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "YCoCgR({}, {}, {})", self.y, self.co, self.cg)
+    }
+}
+
+impl FromColor<Rgb<u8>> for YCoCgR {
+    fn from_color(from: &Rgb<u8>) -> Self {
+        let (r, g, b) = (
+            i16::from(from.red()),
+            i16::from(from.green()),
+            i16::from(from.blue()),
+        );
+
+        let co = r - b;
+        let tmp = b + (co >> 1);
+        let cg = g - tmp;
+        let y = tmp + (cg >> 1);
+
+        YCoCgR::new(y as u8, co, cg)
+    }
+}
+
+impl FromColor<YCoCgR> for Rgb<u8> {
+    /// Convert back to `Rgb<u8>`
+    ///
+    /// This is the exact inverse of the forward conversion. `YCoCgR` values that did not come from
+    /// an `Rgb<u8>` may produce channels outside of $`[0, 255]`$, which are clamped.
+    fn from_color(from: &YCoCgR) -> Self {
+        // Out of range chroma values could overflow i16 in the lifting steps
+        let (y, co, cg) = (i32::from(from.y), i32::from(from.co), i32::from(from.cg));
+
+        let tmp = y - (cg >> 1);
+        let green = cg + tmp;
+        let blue = tmp - (co >> 1);
+        let red = blue + co;
+
+        let clamp = |v: i32| v.clamp(0, 255) as u8;
+        Rgb::new(clamp(red), clamp(green), clamp(blue))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test;
+    use approx::*;
+
+    #[test]
+    fn test_construct() {
+        let c1 = YCoCg::new(0.5, -0.25, 0.125);
+        assert_relative_eq!(c1.luma(), 0.5);
+        assert_relative_eq!(c1.co(), -0.25);
+        assert_relative_eq!(c1.cg(), 0.125);
+        assert_eq!(c1.to_tuple(), (0.5, -0.25, 0.125));
+        assert_eq!(YCoCg::from_tuple(c1.to_tuple()), c1);
+
+        let mut c2 = c1;
+        c2.set_luma(0.75);
+        *c2.co_mut() = 0.3;
+        c2.set_cg(-0.1);
+        assert_eq!(c2, YCoCg::new(0.75, 0.3, -0.1));
+        assert_eq!(
+            format!("{}", YCoCg::new(0.5, -0.25, 0.0)),
+            "YCoCg(0.5, -0.25, 0)"
+        );
+
+        let c3 = YCoCgR::new(100, -20, 31);
+        assert_eq!((c3.y(), c3.co(), c3.cg()), (100, -20, 31));
+    }
+
+    #[test]
+    fn test_from_rgb() {
+        let white = YCoCg::from_color(&Rgb::new(1.0, 1.0, 1.0));
+        assert_relative_eq!(white, YCoCg::new(1.0, 0.0, 0.0));
+        let red = YCoCg::from_color(&Rgb::new(1.0, 0.0, 0.0));
+        assert_relative_eq!(red, YCoCg::new(0.25, 0.5, -0.25));
+        let green = YCoCg::from_color(&Rgb::new(0.0, 1.0, 0.0));
+        assert_relative_eq!(green, YCoCg::new(0.5, 0.0, 0.5));
+        let blue = YCoCg::from_color(&Rgb::new(0.0, 0.0, 1.0));
+        assert_relative_eq!(blue, YCoCg::new(0.25, -0.5, -0.25));
+
+        for color in test::build_hs_test_data() {
+            let ycocg = YCoCg::from_color(&color.rgb);
+            assert!(ycocg.is_normalized());
+            let rgb = Rgb::from_color(&ycocg);
+            assert_relative_eq!(rgb, color.rgb, epsilon = 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_lerp() {
+        let c1 = YCoCg::new(0.2, -0.4, 0.1);
+        let c2 = YCoCg::new(0.6, 0.4, -0.1);
+        assert_relative_eq!(c1.lerp(&c2, 0.5), YCoCg::new(0.4, 0.0, 0.0));
+        assert_relative_eq!(c1.invert(), YCoCg::new(0.8, 0.4, -0.1));
+    }
+
+    #[test]
+    fn test_ycocg_r_lossless() {
+        for r in 0..=255u8 {
+            for g in 0..=255u8 {
+                for b in (0..=255u8).step_by(5) {
+                    let rgb = Rgb::new(r, g, b);
+                    let ycocg = YCoCgR::from_color(&rgb);
+                    assert_eq!(Rgb::from_color(&ycocg), rgb);
+                }
+            }
+        }
+        for v in 0..=255u8 {
+            let ycocg = YCoCgR::from_color(&Rgb::new(v, v, v));
+            assert_eq!(ycocg, YCoCgR::new(v, 0, 0));
+        }
+
+        assert_eq!(
+            YCoCgR::from_color(&Rgb::new(255, 0, 0)),
+            YCoCgR::new(63, 255, -127)
+        );
+        assert_eq!(
+            Rgb::from_color(&YCoCgR::new(255, 255, 255)),
+            Rgb::new(255, 255, 1)
+        );
+        assert_eq!(
+            Rgb::from_color(&YCoCgR::new(255, -32768, -32768)),
+            Rgb::new(255, 0, 255)
+        );
+        assert_eq!(
+            Rgb::from_color(&YCoCgR::new(0, 32767, 32767)),
+            Rgb::new(1, 255, 0)
+        );
+    }
+}